@@ -0,0 +1,48 @@
+//! Compares rendering the countdown with the font parsed on every refresh
+//! against the renderer kept by the font cache.
+//!
+//! Run with `cargo bench -p deadliner-core --bench font_cache`.
+
+use std::{
+    fs,
+    hint::black_box,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use deadliner_core::cached_text_renderer;
+use text_to_png::TextRenderer;
+
+const ITERATIONS: u32 = 200;
+const TEXT: &str = "12 Days 4 Hours";
+
+fn bench(name: &str, mut render: impl FnMut()) {
+    // Warm up so the first iteration doesn't pay for the page cache or the font cache.
+    render();
+
+    let start = Instant::now();
+
+    for _ in 0..ITERATIONS {
+        render();
+    }
+
+    let average: Duration = start.elapsed() / ITERATIONS;
+    println!("{:<8} {:>10.3?} per render", name, average);
+}
+
+fn main() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/fonts/PoppinsBlack.ttf");
+
+    bench("cold", || {
+        let font_data = fs::read(&path).unwrap();
+        let renderer = TextRenderer::try_new_with_ttf_font_data(font_data).unwrap();
+
+        black_box(renderer.render_text_to_png_data(TEXT, 64, "#FFFFFF").unwrap());
+    });
+
+    bench("cached", || {
+        let renderer = cached_text_renderer(&path).unwrap();
+
+        black_box(renderer.render_text_to_png_data(TEXT, 64, "#FFFFFF").unwrap());
+    });
+}
@@ -0,0 +1,143 @@
+use std::{fs, path::PathBuf, sync::Mutex};
+
+use chrono::{DateTime, Local, TimeZone, Utc};
+use image::{DynamicImage, GenericImageView};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    get_cache_dir, render_text_block, trace, Branding, CalendarEvent, Corner, SanitizedConf,
+    TextLine,
+};
+
+/// Today's next events from the synced calendar, drawn small in a corner next to the countdown.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct AgendaConf {
+    pub enabled: bool,
+    pub max_events: u32,
+    pub corner: Corner,
+    pub font_size: u8,
+}
+
+impl Default for AgendaConf {
+    fn default() -> Self {
+        AgendaConf {
+            enabled: false,
+            max_events: 3,
+            corner: Corner::TopRight,
+            font_size: 22,
+        }
+    }
+}
+
+impl AgendaConf {
+    /// A strip of more than a handful of events would compete with the countdown.
+    pub fn sanitized(&self) -> AgendaConf {
+        AgendaConf {
+            max_events: self.max_events.clamp(1, 5),
+            font_size: self.font_size.max(8),
+            ..*self
+        }
+    }
+}
+
+/// An event of the synced calendar, every event counts whatever the sync's tag is.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AgendaEvent {
+    pub title: String,
+    /// Unix timestamp of when it starts.
+    pub start: i64,
+}
+
+static AGENDA: Mutex<Option<Vec<AgendaEvent>>> = Mutex::new(None);
+
+fn agenda_path() -> PathBuf {
+    get_cache_dir().join("agenda.json")
+}
+
+/// Keeps the events of the last calendar sync for the agenda strip.
+pub fn save_agenda(events: &[CalendarEvent]) {
+    let agenda: Vec<AgendaEvent> = events
+        .iter()
+        .map(|event| AgendaEvent {
+            title: event.summary.clone(),
+            start: event.start_instant().timestamp(),
+        })
+        .collect();
+
+    fs::write(agenda_path(), serde_json::to_string(&agenda).unwrap()).ok();
+    *AGENDA.lock().unwrap() = Some(agenda);
+}
+
+/// Events that are still to start today, the soonest first.
+pub fn todays_agenda(conf: &SanitizedConf, now: DateTime<Utc>) -> Vec<AgendaEvent> {
+    let mut agenda = AGENDA.lock().unwrap();
+    let agenda = agenda.get_or_insert_with(|| {
+        fs::read_to_string(agenda_path())
+            .ok()
+            .and_then(|agenda| serde_json::from_str(&agenda).ok())
+            .unwrap_or_default()
+    });
+
+    let today = now.with_timezone(&Local).date();
+
+    let mut upcoming: Vec<AgendaEvent> = agenda
+        .iter()
+        .filter(|event| {
+            let start = Local.timestamp(event.start, 0);
+            event.start > now.timestamp() && start.date() == today
+        })
+        .cloned()
+        .collect();
+
+    upcoming.sort_by_key(|event| event.start);
+    upcoming.truncate(conf.agenda.max_events as usize);
+
+    upcoming
+}
+
+/// Draws the agenda strip in its corner when a calendar is synced and there's something left today.
+pub(crate) fn draw_agenda(
+    background: &mut DynamicImage,
+    conf: &SanitizedConf,
+    branding: &Option<Branding>,
+) -> Result<(), String> {
+    if !conf.agenda.enabled || !conf.calendar_sync.enabled {
+        return Ok(());
+    }
+
+    let events = todays_agenda(conf, Utc::now());
+    if events.is_empty() {
+        return Ok(());
+    }
+
+    trace!("drawing {} agenda events", events.len());
+
+    let line = |text: String| TextLine {
+        text,
+        font_size: conf.agenda.font_size,
+        font_color: conf.title_font_color.clone(),
+    };
+
+    let mut lines = vec![line(String::from("Today"))];
+    for event in &events {
+        let start = Local.timestamp(event.start, 0).format("%H:%M");
+        lines.push(line(format!("{}  {}", start, event.title)));
+    }
+
+    let strip = render_text_block(&lines, conf, branding)?;
+
+    let offset = 30;
+    let right = background.width().saturating_sub(strip.width() + offset);
+    let bottom = background.height().saturating_sub(strip.height() + offset);
+
+    let (x, y) = match conf.agenda.corner {
+        Corner::TopLeft => (offset, offset),
+        Corner::TopRight => (right, offset),
+        Corner::BottomLeft => (offset, bottom),
+        Corner::BottomRight => (right, bottom),
+    };
+
+    image::imageops::overlay(background, &strip, x, y);
+
+    Ok(())
+}
@@ -0,0 +1,128 @@
+use std::{fs::File, path::Path};
+
+use image::{
+    codecs::gif::{GifEncoder, Repeat},
+    imageops::FilterType,
+    Delay, Frame,
+};
+
+use crate::{
+    compose_wallpaper, deadline_lines, fill_template, fill_text_variables, load_background,
+    load_branding, unwrap_or_return, SanitizedConf, TextLine, TimeUnit,
+};
+
+/// Width of the exported animations, small enough to share in chats and slides.
+const ANIMATION_WIDTH: u32 = 640;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum AnimationStyle {
+    /// The current countdown gently growing and shrinking, Ex: "3 Days Left."
+    Pulse,
+    /// The final 10 seconds of the first deadline.
+    FinalSeconds,
+}
+
+/// Renders a looping GIF of the countdown frame by frame with the wallpaper's styling.
+pub fn export_animation(
+    conf: &SanitizedConf,
+    style: AnimationStyle,
+    output: &Path,
+) -> Result<(), String> {
+    let branding = load_branding(&conf.branding_location)?;
+
+    let scale = ANIMATION_WIDTH as f32 / conf.screen_dimensions.width.max(1) as f32;
+    let width = ANIMATION_WIDTH;
+    let height = (conf.screen_dimensions.height as f32 * scale).max(1.) as u32;
+
+    let background =
+        load_background(conf, &branding)?.resize_to_fill(width, height, FilterType::Triangle);
+
+    let scaled = |lines: &[TextLine], factor: f32| -> Vec<TextLine> {
+        lines
+            .iter()
+            .map(|line| line.scaled(scale * factor))
+            .collect()
+    };
+
+    // Lines of every frame with how long each one is shown in milliseconds
+    let frames: Vec<(Vec<TextLine>, u32)> = match style {
+        AnimationStyle::Pulse => {
+            let lines: Vec<TextLine> = deadline_lines(conf, false)
+                .into_iter()
+                .map(|(_, line)| line)
+                .collect();
+
+            if lines.is_empty() {
+                return Err(String::from("There's no countdown to export!"));
+            }
+
+            [1., 1.03, 1.06, 1.09, 1.06, 1.03]
+                .iter()
+                .map(|factor| (scaled(&lines, *factor), 100))
+                .collect()
+        }
+        AnimationStyle::FinalSeconds => {
+            let deadline = match conf.deadlines.first() {
+                Some(deadline) => deadline,
+                None => return Err(String::from("There's no countdown to export!")),
+            };
+
+            let with_title = |text: &str| {
+                let mut lines = Vec::new();
+
+                if !deadline.title.is_empty() {
+                    lines.push(TextLine::title(conf, &deadline.title));
+                }
+                lines.push(TextLine::countdown(conf, text));
+
+                scaled(&lines, 1.)
+            };
+
+            let mut frames: Vec<(Vec<TextLine>, u32)> = (1..=10)
+                .rev()
+                .map(|seconds| {
+                    let countdown =
+                        conf.unit_format
+                            .format_unit(deadline.locale, TimeUnit::Second, seconds);
+
+                    (
+                        with_title(&fill_text_variables(
+                            &fill_template(&deadline.template, &countdown),
+                            deadline,
+                            conf,
+                        )),
+                        1000,
+                    )
+                })
+                .collect();
+
+            frames.push((with_title(deadline.locale.deadline_over()), 2000));
+
+            frames
+        }
+    };
+
+    let file = unwrap_or_return!(File::create(output), "Couldn't create the animation file!");
+
+    let mut encoder = GifEncoder::new(file);
+    unwrap_or_return!(
+        encoder.set_repeat(Repeat::Infinite),
+        "Couldn't encode the animation!"
+    );
+
+    for (lines, delay_ms) in frames {
+        let frame = compose_wallpaper(background.clone(), &lines, conf, &branding)?;
+
+        unwrap_or_return!(
+            encoder.encode_frame(Frame::from_parts(
+                frame.to_rgba8(),
+                0,
+                0,
+                Delay::from_numer_denom_ms(delay_ms, 1),
+            )),
+            "Couldn't encode the animation!"
+        );
+    }
+
+    Ok(())
+}
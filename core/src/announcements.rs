@@ -0,0 +1,97 @@
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    break_down, unwrap_or_return, Locale, RoundingMode, SanitizedConf, SanitizedDeadline, TimeUnit,
+    UnitFormat,
+};
+
+/// Speaks the time left out loud with the OS text-to-speech engine at each threshold.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AnnouncementsConf {
+    pub enabled: bool,
+    /// Minutes left at which the time left is spoken, Ex: 120 for "2 Hours remaining".
+    pub thresholds: Vec<u32>,
+}
+
+impl Default for AnnouncementsConf {
+    fn default() -> Self {
+        AnnouncementsConf {
+            enabled: false,
+            thresholds: vec![24 * 60, 2 * 60, 30, 5],
+        }
+    }
+}
+
+/// What's spoken when `deadline` reaches `minutes` left if that's one of the thresholds.
+pub fn milestone_announcement(
+    conf: &SanitizedConf,
+    deadline: &SanitizedDeadline,
+    minutes: u32,
+) -> Option<String> {
+    if !conf.announcements.enabled || !conf.announcements.thresholds.contains(&minutes) {
+        return None;
+    }
+
+    let announcement = announcement(deadline.locale, minutes as i64);
+
+    Some(if deadline.title.is_empty() {
+        announcement
+    } else {
+        format!("{}: {}", deadline.title, announcement)
+    })
+}
+
+/// Ex: "1 Hour, 30 Minutes remaining".
+pub fn announcement(locale: Locale, minutes: i64) -> String {
+    let units = break_down(
+        minutes * 60,
+        &[TimeUnit::Day, TimeUnit::Hour, TimeUnit::Minute],
+        RoundingMode::Down,
+    );
+
+    let countdown = UnitFormat::default().format(locale, &units, false);
+
+    locale
+        .announcement_template()
+        .replace("{countdown}", &countdown)
+}
+
+/// Reads `text` out loud without waiting for it to finish.
+pub fn speak(text: &str) -> Result<(), String> {
+    let mut command = if cfg!(target_os = "windows") {
+        let mut command = Command::new("powershell");
+        command.args([
+            "-NoProfile",
+            "-Command",
+            &format!(
+                "Add-Type -AssemblyName System.Speech; (New-Object System.Speech.Synthesis.SpeechSynthesizer).Speak('{}')",
+                text.replace('\'', "''")
+            ),
+        ]);
+        command
+    } else if cfg!(target_os = "macos") {
+        let mut command = Command::new("say");
+        command.arg(text);
+        command
+    } else {
+        // Speech Dispatcher is on most desktops, espeak is the usual fallback
+        let engine = if Command::new("spd-say").arg("--version").output().is_ok() {
+            "spd-say"
+        } else {
+            "espeak"
+        };
+
+        let mut command = Command::new(engine);
+        command.arg(text);
+        command
+    };
+
+    unwrap_or_return!(
+        command.spawn(),
+        "Couldn't find a text-to-speech engine, install speech-dispatcher or espeak!"
+    );
+
+    Ok(())
+}
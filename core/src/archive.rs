@@ -0,0 +1,135 @@
+use std::{fs, path::PathBuf};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    get_profile_cache_dir, parse_timezone, resolve_deadline, resolve_deadline_date, trace,
+    unwrap_or_return, Deadline, DeadlinerConf, Recurrence, SanitizedDeadline,
+};
+
+/// A deadline that's over, kept with when it was completed so it can be looked back at.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ArchivedDeadline {
+    pub title: String,
+    #[serde(default)]
+    pub tag: String,
+    /// Unix timestamp of the deadline, the occurrence that's over for recurring ones.
+    pub date: i64,
+    /// Unix timestamp of when it was first saved if it's known.
+    pub saved_at: Option<i64>,
+    /// Unix timestamp of when it was archived, its date when it was over while deadliner wasn't running.
+    pub completed_at: i64,
+}
+
+impl ArchivedDeadline {
+    /// The occurrence of `deadline` that's over at `now`.
+    pub fn passed(deadline: &SanitizedDeadline, now: DateTime<Utc>) -> ArchivedDeadline {
+        // The deadline is passed up to a minute early, before a recurring one rolls over
+        let date = deadline.date_at(now - Duration::minutes(1));
+
+        ArchivedDeadline {
+            title: deadline.title.clone(),
+            tag: deadline.tag.clone(),
+            date: date.timestamp(),
+            saved_at: deadline.started_at,
+            completed_at: now.timestamp(),
+        }
+    }
+
+    fn is_same(&self, other: &ArchivedDeadline) -> bool {
+        self.title == other.title && self.date == other.date
+    }
+}
+
+/// Archives are per profile, like the deadlines they come from.
+fn archive_path() -> PathBuf {
+    get_profile_cache_dir().join("archive.json")
+}
+
+/// Archived deadlines, the most recently completed one first.
+pub fn load_archive() -> Vec<ArchivedDeadline> {
+    fs::read_to_string(archive_path())
+        .ok()
+        .and_then(|archive| serde_json::from_str(&archive).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_archive(archive: &[ArchivedDeadline]) -> Result<(), String> {
+    unwrap_or_return!(
+        fs::write(archive_path(), serde_json::to_string(archive).unwrap()),
+        "Couldn't save the archive!"
+    );
+
+    Ok(())
+}
+
+/// Adds `deadline` to the archive unless it's already there.
+pub fn archive_deadline(deadline: ArchivedDeadline) -> Result<(), String> {
+    let mut archive = load_archive();
+
+    if archive.iter().any(|archived| archived.is_same(&deadline)) {
+        return Ok(());
+    }
+
+    trace!("archiving \"{}\"", deadline.title);
+
+    archive.push(deadline);
+    archive.sort_by_key(|archived| -archived.completed_at);
+
+    save_archive(&archive)
+}
+
+/// When `deadline` is over if it's a deadline that can be over,
+/// recurring deadlines and ones counting up never are.
+fn passed_at(deadline: &Deadline, use_24_hour: bool, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    if deadline.counts_up || deadline.recurrence != Recurrence::Never {
+        return None;
+    }
+
+    let date = resolve_deadline_date(deadline, use_24_hour).ok()?;
+    let date = resolve_deadline(date, parse_timezone(&deadline.timezone).ok()?);
+
+    if date <= now {
+        Some(date)
+    } else {
+        None
+    }
+}
+
+/// Moves the deadlines that are over from the inputs to the archive instead of
+/// leaving them to fail the next save, returns how many were archived.
+pub fn archive_passed_deadlines(conf: &mut DeadlinerConf) -> usize {
+    let now = Utc::now();
+    let use_24_hour = conf.use_24_hour;
+    let mut archived = 0;
+
+    conf.deadlines.retain(|deadline| {
+        let date = match passed_at(deadline, use_24_hour, now) {
+            Some(date) => date,
+            None => return true,
+        };
+
+        let deadline = ArchivedDeadline {
+            title: deadline.title.trim().to_string(),
+            tag: deadline.tag.trim().to_string(),
+            date: date.timestamp(),
+            saved_at: None,
+            completed_at: date.timestamp(),
+        };
+
+        if archive_deadline(deadline).is_err() {
+            return true;
+        }
+
+        archived += 1;
+        false
+    });
+
+    // There's always at least one deadline to edit
+    if conf.deadlines.is_empty() {
+        conf.deadlines.push(Deadline::default());
+    }
+
+    archived
+}
@@ -0,0 +1,67 @@
+use image::{DynamicImage, GenericImage, GenericImageView, Pixel, Rgba};
+use serde::{Deserialize, Serialize};
+
+/// A rounded, semi-transparent box drawn behind the text to keep it legible on busy images.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct BackdropConf {
+    pub enabled: bool,
+    pub color: [u8; 3],
+    /// From 0 to 100.
+    pub opacity: u8,
+    /// Space between the text and the edges of the box in pixels.
+    pub padding: u32,
+    pub corner_radius: u32,
+}
+
+impl Default for BackdropConf {
+    fn default() -> Self {
+        BackdropConf {
+            enabled: false,
+            color: [0, 0, 0],
+            opacity: 60,
+            padding: 40,
+            corner_radius: 24,
+        }
+    }
+}
+
+/// Blends a box around the area at (`x`, `y`) sized `width`x`height` into `background`.
+pub fn draw_backdrop_box(
+    background: &mut DynamicImage,
+    (x, y, width, height): (u32, u32, u32, u32),
+    conf: &BackdropConf,
+) {
+    let left = x.saturating_sub(conf.padding);
+    let top = y.saturating_sub(conf.padding);
+    let right = (x + width + conf.padding).min(background.width());
+    let bottom = (y + height + conf.padding).min(background.height());
+
+    let radius = conf
+        .corner_radius
+        .min((right - left) / 2)
+        .min((bottom - top) / 2) as f32;
+    let alpha = conf.opacity.min(100) as f32 / 100. * 255.;
+    let [r, g, b] = conf.color;
+
+    for py in top..bottom {
+        for px in left..right {
+            // Distance into the rounded corner, pixels outside of the corners are fully covered
+            let dx = (left as f32 + radius - px as f32 - 0.5)
+                .max(px as f32 + 0.5 - (right as f32 - radius))
+                .max(0.);
+            let dy = (top as f32 + radius - py as f32 - 0.5)
+                .max(py as f32 + 0.5 - (bottom as f32 - radius))
+                .max(0.);
+            let coverage = (radius - (dx * dx + dy * dy).sqrt() + 0.5).clamp(0., 1.);
+
+            if coverage <= 0. {
+                continue;
+            }
+
+            let mut pixel = background.get_pixel(px, py);
+            pixel.blend(&Rgba([r, g, b, (alpha * coverage) as u8]));
+
+            background.put_pixel(px, py, pixel);
+        }
+    }
+}
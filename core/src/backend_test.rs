@@ -0,0 +1,349 @@
+use std::{
+    env, fs,
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+    process::{self, Child, Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use image::{Rgb, RgbImage};
+
+use crate::{get_cache_dir, is_installed, trace, CheckReport};
+
+/// The `XDG_CURRENT_DESKTOP` values the wallpaper crate has a backend for
+/// and the tools each backend runs.
+const LINUX_BACKENDS: &[(&str, &[&str])] = &[
+    ("GNOME", &["gsettings"]),
+    ("KDE", &["qdbus"]),
+    ("X-Cinnamon", &["dconf"]),
+    ("MATE", &["dconf"]),
+    ("XFCE", &["xfconf-query"]),
+    ("LXDE", &["pcmanfm"]),
+    ("Deepin", &["dconf"]),
+    // Every other desktop falls back to swaybg, then to feh
+    ("", &["swaybg", "feh"]),
+];
+
+/// A headless X server the backends are tested against, stopped when it's dropped.
+struct FakeDisplay {
+    xvfb: Child,
+    previous_display: Option<String>,
+}
+
+impl FakeDisplay {
+    /// Starts Xvfb on the first free display from :90 and points `DISPLAY` at it.
+    fn start() -> Result<FakeDisplay, String> {
+        let number = (90..100)
+            .find(|n| !Path::new(&format!("/tmp/.X11-unix/X{}", n)).exists())
+            .ok_or("Every display from :90 to :99 is taken")?;
+        let display = format!(":{}", number);
+
+        let xvfb = Command::new("Xvfb")
+            .args([&display, "-screen", "0", "1280x720x24", "-nolisten", "tcp"])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("Couldn't start Xvfb: {}", e))?;
+
+        let fake_display = FakeDisplay {
+            xvfb,
+            previous_display: env::var("DISPLAY").ok(),
+        };
+
+        // Xvfb is ready once it created its socket
+        let started = Instant::now();
+        while !Path::new(&format!("/tmp/.X11-unix/X{}", number)).exists() {
+            if started.elapsed() > Duration::from_secs(5) {
+                return Err(format!("Xvfb didn't start on {} within 5s", display));
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+
+        trace!("Xvfb is running on {}", display);
+
+        env::set_var("DISPLAY", &display);
+
+        Ok(fake_display)
+    }
+}
+
+impl Drop for FakeDisplay {
+    fn drop(&mut self) {
+        self.xvfb.kill().ok();
+        self.xvfb.wait().ok();
+
+        match &self.previous_display {
+            Some(display) => env::set_var("DISPLAY", display),
+            None => env::remove_var("DISPLAY"),
+        }
+    }
+}
+
+/// The settings a backend writes to while it's tested on the fake display.
+const SESSION_VARS: &[&str] = &[
+    "HOME",
+    "XDG_CONFIG_HOME",
+    "DCONF_PROFILE",
+    "DBUS_SESSION_BUS_ADDRESS",
+];
+
+/// A throwaway session bus with its own home and dconf database, like `dbus-run-session`,
+/// so the backends tested on the fake display don't change the desktop's real settings.
+/// It's stopped and the session's variables are put back when it's dropped.
+struct PrivateSession {
+    dbus_daemon: Child,
+    dir: PathBuf,
+    previous_vars: Vec<(&'static str, Option<String>)>,
+}
+
+impl PrivateSession {
+    fn start() -> Result<PrivateSession, String> {
+        let dir = env::temp_dir().join(format!("deadliner-backend-test-{}", process::id()));
+        let profile = dir.join("dconf-profile");
+
+        fs::create_dir_all(dir.join("config"))
+            .and_then(|_| fs::write(&profile, "user-db:user\n"))
+            .map_err(|e| format!("Couldn't create a temporary session dir: {}", e))?;
+
+        let previous_vars = SESSION_VARS
+            .iter()
+            .map(|var| (*var, env::var(var).ok()))
+            .collect();
+
+        // Set before the bus starts so the services it starts (Ex: dconf) use them too
+        env::set_var("HOME", &dir);
+        env::set_var("XDG_CONFIG_HOME", dir.join("config"));
+        env::set_var("DCONF_PROFILE", &profile);
+
+        let mut session = PrivateSession {
+            dbus_daemon: Command::new("dbus-daemon")
+                .args(["--session", "--nofork", "--print-address=1"])
+                .stdout(Stdio::piped())
+                .stderr(Stdio::null())
+                .spawn()
+                .map_err(|e| format!("Couldn't start a session bus: {}", e))?,
+            dir,
+            previous_vars,
+        };
+
+        let mut address = String::new();
+        if let Some(stdout) = session.dbus_daemon.stdout.take() {
+            BufReader::new(stdout).read_line(&mut address).ok();
+        }
+
+        if address.trim().is_empty() {
+            return Err(String::from("The session bus didn't print its address"));
+        }
+
+        trace!("started a private session bus at {}", address.trim());
+
+        env::set_var("DBUS_SESSION_BUS_ADDRESS", address.trim());
+
+        Ok(session)
+    }
+}
+
+impl Drop for PrivateSession {
+    fn drop(&mut self) {
+        self.dbus_daemon.kill().ok();
+        self.dbus_daemon.wait().ok();
+
+        for (var, value) in &self.previous_vars {
+            match value {
+                Some(value) => env::set_var(var, value),
+                None => env::remove_var(var),
+            }
+        }
+
+        fs::remove_dir_all(&self.dir).ok();
+    }
+}
+
+/// Points the wallpaper crate at a desktop's backend, the previous desktop is put back when it's dropped.
+struct DesktopOverride {
+    previous_desktop: Option<String>,
+}
+
+impl DesktopOverride {
+    fn set(desktop: &str) -> DesktopOverride {
+        let previous_desktop = env::var("XDG_CURRENT_DESKTOP").ok();
+        env::set_var("XDG_CURRENT_DESKTOP", desktop);
+
+        DesktopOverride { previous_desktop }
+    }
+}
+
+impl Drop for DesktopOverride {
+    fn drop(&mut self) {
+        match &self.previous_desktop {
+            Some(desktop) => env::set_var("XDG_CURRENT_DESKTOP", desktop),
+            None => env::remove_var("XDG_CURRENT_DESKTOP"),
+        }
+    }
+}
+
+/// Sets a test image with one backend and reads it back, the backend's previous wallpaper
+/// is put back so running it in a real session doesn't leave the test image behind.
+fn test_backend(
+    report: &mut CheckReport,
+    desktop: &str,
+    tools: &[&str],
+    test_image: &str,
+    fake_display: bool,
+) {
+    let name = if desktop.is_empty() {
+        "Fallback"
+    } else {
+        desktop
+    };
+    let tools_label = tools.join(" or ");
+
+    if !tools.iter().any(|tool| is_installed(tool)) {
+        report.warn(
+            &format!("{}: skipped, {} isn't installed", name, tools_label),
+            &format!("Install {} to test this backend", tools_label),
+        );
+        return;
+    }
+
+    if tools.contains(&"swaybg") && env::var("WAYLAND_DISPLAY").is_err() && is_installed("swaybg") {
+        report.warn(
+            &format!("{}: skipped, swaybg needs a Wayland session", name),
+            "Run it in a headless compositor, Ex: `WLR_BACKENDS=headless sway`, or uninstall swaybg to test feh",
+        );
+        return;
+    }
+
+    let _desktop = DesktopOverride::set(desktop);
+    trace!("testing the {} backend with {}", name, tools_label);
+
+    // The test image would stay the wallpaper if the previous one can't be put back,
+    // the private session of the fake display has nothing to put back
+    let previous = match wallpaper::get() {
+        Ok(previous) => Some(previous),
+        Err(_) if fake_display => None,
+        Err(_) => {
+            report.warn(
+                &format!(
+                    "{}: skipped, {} can't put the previous wallpaper back",
+                    name, tools_label
+                ),
+                "Run it with --fake-display to test it on Xvfb in a private session",
+            );
+            return;
+        }
+    };
+
+    if let Err(err) = wallpaper::set_from_path(test_image) {
+        report.error(
+            &format!(
+                "{}: couldn't set the wallpaper with {}: {}",
+                name, tools_label, err
+            ),
+            "Make sure a session bus is running, Ex: run it in `dbus-run-session`",
+        );
+        return;
+    }
+
+    // The fallback backends can't be read back
+    match wallpaper::get() {
+        Ok(current) if current.contains(test_image) => {
+            report.pass(&format!("{}: set and read back the wallpaper", name))
+        }
+        Ok(current) => report.error(
+            &format!(
+                "{}: set the wallpaper but it reads back as \"{}\"",
+                name, current
+            ),
+            "The settings might not be persisted, Ex: GSettings' memory backend without a session bus",
+        ),
+        Err(_) if desktop.is_empty() => {
+            report.pass(&format!("{}: set the wallpaper with {}", name, tools_label))
+        }
+        Err(err) => report.error(
+            &format!("{}: set the wallpaper but couldn't read it back: {}", name, err),
+            "The original wallpaper can't be restored on this desktop",
+        ),
+    }
+
+    if let Some(previous) = previous {
+        wallpaper::set_from_path(&previous).ok();
+    }
+}
+
+/// Exercises every Linux wallpaper backend whose tools are installed and reports which ones work,
+/// used by `deadliner test-backend` to catch backend regressions.
+/// With `fake_display` they're tested against a headless Xvfb display and a private session bus
+/// instead of the session's.
+pub fn run_backend_tests(fake_display: bool) -> CheckReport {
+    let mut report = CheckReport::default();
+
+    if !cfg!(target_os = "linux") {
+        report.error(
+            "The backend tests are for Linux desktops",
+            "Use `deadliner doctor` to test setting the wallpaper on this OS",
+        );
+        return report;
+    }
+
+    let _display = if fake_display {
+        match FakeDisplay::start() {
+            Ok(display) => {
+                report.pass(&format!(
+                    "Started Xvfb on {}",
+                    env::var("DISPLAY").unwrap_or_default()
+                ));
+                Some(display)
+            }
+            Err(err) => {
+                report.error(
+                    &err,
+                    "Install Xvfb (Ex: the xvfb or xorg-server-xvfb package)",
+                );
+                return report;
+            }
+        }
+    } else {
+        None
+    };
+
+    let test_image = get_cache_dir().join("backend-test.png");
+    let test_image = test_image.to_str().unwrap().to_owned();
+
+    if RgbImage::from_pixel(64, 64, Rgb([11, 16, 26]))
+        .save(&test_image)
+        .is_err()
+    {
+        report.error(
+            "Couldn't write a test image to the cache dir",
+            &format!("Make sure {} is writable", get_cache_dir().display()),
+        );
+        return report;
+    }
+
+    // Started after the test image is written, it moves the home and with it the cache dir
+    let _session = if fake_display {
+        match PrivateSession::start() {
+            Ok(session) => Some(session),
+            Err(err) => {
+                fs::remove_file(&test_image).ok();
+                report.error(
+                    &err,
+                    "Install dbus (Ex: the dbus or dbus-daemon package) so the backends don't change your desktop's settings",
+                );
+                return report;
+            }
+        }
+    } else {
+        None
+    };
+
+    for (backend, tools) in LINUX_BACKENDS {
+        test_backend(&mut report, backend, tools, &test_image, fake_display);
+    }
+
+    fs::remove_file(&test_image).ok();
+
+    report
+}
@@ -0,0 +1,262 @@
+use std::{path::Path, sync::Mutex};
+
+use image::DynamicImage;
+
+use crate::{
+    download_daily_image, download_with_ttl, next_folder_image, open_capped, render_gradient,
+    render_pattern, trace, Branding, DeadlinerError, SanitizedBackground, SanitizedConf,
+    ScreenDimensions,
+};
+
+/// A source of wallpaper backgrounds, Ex: a solid color, an image on disk or a daily photo.
+pub trait BackgroundProvider: Sync {
+    /// Whether this provider draws `background`.
+    fn provides(&self, background: &SanitizedBackground) -> bool;
+
+    fn load(
+        &self,
+        background: &SanitizedBackground,
+        conf: &SanitizedConf,
+        branding: &Option<Branding>,
+    ) -> Result<DynamicImage, DeadlinerError>;
+
+    /// Whether the background filters (blur, brightness, ...) are applied to what's loaded,
+    /// backgrounds that are exactly the picked colors shouldn't be changed.
+    fn filterable(&self) -> bool {
+        true
+    }
+}
+
+const BUILT_IN_PROVIDERS: &[&dyn BackgroundProvider] = &[
+    &SolidProvider,
+    &GradientProvider,
+    &DiskProvider,
+    &FolderProvider,
+    &UrlProvider,
+    &OnlineProvider,
+];
+
+static REGISTERED_PROVIDERS: Mutex<Vec<&'static dyn BackgroundProvider>> = Mutex::new(Vec::new());
+
+/// Adds a provider that's asked before the built-in ones, so it can also replace them.
+pub fn register_background_provider(provider: &'static dyn BackgroundProvider) {
+    REGISTERED_PROVIDERS.lock().unwrap().push(provider);
+}
+
+/// The provider that draws `background`, the last registered one wins.
+pub fn background_provider(
+    background: &SanitizedBackground,
+) -> Result<&'static dyn BackgroundProvider, String> {
+    let registered = REGISTERED_PROVIDERS.lock().unwrap();
+
+    registered
+        .iter()
+        .rev()
+        .chain(BUILT_IN_PROVIDERS.iter())
+        .find(|provider| provider.provides(background))
+        .copied()
+        .ok_or_else(|| format!("No provider can draw a {:?} background", background))
+}
+
+struct SolidProvider;
+
+impl BackgroundProvider for SolidProvider {
+    fn provides(&self, background: &SanitizedBackground) -> bool {
+        matches!(background, SanitizedBackground::Solid { .. })
+    }
+
+    fn load(
+        &self,
+        background: &SanitizedBackground,
+        conf: &SanitizedConf,
+        branding: &Option<Branding>,
+    ) -> Result<DynamicImage, DeadlinerError> {
+        let (rgb, pattern) = match background {
+            SanitizedBackground::Solid { rgb, pattern, .. } => (*rgb, *pattern),
+            _ => unreachable!(),
+        };
+
+        // Brand colors take precedence over the picked solid color
+        let rgb = branding
+            .as_ref()
+            .and_then(|b| b.background_color_rgb())
+            .unwrap_or(rgb);
+
+        let ScreenDimensions { width, height } = conf.screen_dimensions;
+
+        Ok(DynamicImage::ImageRgb8(render_pattern(
+            width, height, rgb, pattern,
+        )))
+    }
+
+    fn filterable(&self) -> bool {
+        false
+    }
+}
+
+struct GradientProvider;
+
+impl BackgroundProvider for GradientProvider {
+    fn provides(&self, background: &SanitizedBackground) -> bool {
+        matches!(background, SanitizedBackground::Gradient { .. })
+    }
+
+    fn load(
+        &self,
+        background: &SanitizedBackground,
+        conf: &SanitizedConf,
+        _: &Option<Branding>,
+    ) -> Result<DynamicImage, DeadlinerError> {
+        let (start, end, direction) = match background {
+            SanitizedBackground::Gradient {
+                start,
+                end,
+                direction,
+            } => (*start, *end, *direction),
+            _ => unreachable!(),
+        };
+
+        let ScreenDimensions { width, height } = conf.screen_dimensions;
+
+        Ok(DynamicImage::ImageRgb8(render_gradient(
+            width, height, start, end, direction,
+        )))
+    }
+
+    fn filterable(&self) -> bool {
+        false
+    }
+}
+
+struct DiskProvider;
+
+impl BackgroundProvider for DiskProvider {
+    fn provides(&self, background: &SanitizedBackground) -> bool {
+        matches!(background, SanitizedBackground::FromDisk(_))
+    }
+
+    fn load(
+        &self,
+        background: &SanitizedBackground,
+        conf: &SanitizedConf,
+        _: &Option<Branding>,
+    ) -> Result<DynamicImage, DeadlinerError> {
+        let path = match background {
+            SanitizedBackground::FromDisk(path) => path,
+            _ => unreachable!(),
+        };
+
+        open_capped(
+            Path::new(path),
+            conf.memory_cap_mb,
+            &format!("The image {}", path),
+        )
+    }
+}
+
+struct FolderProvider;
+
+impl BackgroundProvider for FolderProvider {
+    fn provides(&self, background: &SanitizedBackground) -> bool {
+        matches!(background, SanitizedBackground::FromFolder { .. })
+    }
+
+    fn load(
+        &self,
+        background: &SanitizedBackground,
+        conf: &SanitizedConf,
+        _: &Option<Branding>,
+    ) -> Result<DynamicImage, DeadlinerError> {
+        let (path, shuffle) = match background {
+            SanitizedBackground::FromFolder { path, shuffle } => (path, *shuffle),
+            _ => unreachable!(),
+        };
+
+        let image = next_folder_image(path, shuffle).map_err(|e| DeadlinerError::Io {
+            msg: e,
+            source: None,
+        })?;
+
+        trace!("picked {} from the folder", image.display());
+
+        open_capped(
+            &image,
+            conf.memory_cap_mb,
+            &format!("The image {}", image.display()),
+        )
+    }
+}
+
+struct UrlProvider;
+
+impl BackgroundProvider for UrlProvider {
+    fn provides(&self, background: &SanitizedBackground) -> bool {
+        matches!(background, SanitizedBackground::FromURL(_))
+    }
+
+    fn load(
+        &self,
+        background: &SanitizedBackground,
+        conf: &SanitizedConf,
+        _: &Option<Branding>,
+    ) -> Result<DynamicImage, DeadlinerError> {
+        let url = match background {
+            SanitizedBackground::FromURL(url) => url,
+            _ => unreachable!(),
+        };
+
+        let downloaded_image = download_with_ttl(
+            url,
+            conf.url_cache_ttl_hours,
+            conf.download_timeout_secs,
+        )
+        .map_err(|e| {
+            DeadlinerError::download("Couldn't download the Image from the supplied URL!", e)
+        })?;
+
+        open_capped(
+            Path::new(&downloaded_image),
+            conf.memory_cap_mb,
+            "The Image from the supplied URL",
+        )
+    }
+}
+
+struct OnlineProvider;
+
+impl BackgroundProvider for OnlineProvider {
+    fn provides(&self, background: &SanitizedBackground) -> bool {
+        matches!(background, SanitizedBackground::Online { .. })
+    }
+
+    fn load(
+        &self,
+        background: &SanitizedBackground,
+        conf: &SanitizedConf,
+        _: &Option<Branding>,
+    ) -> Result<DynamicImage, DeadlinerError> {
+        let (source, keyword) = match background {
+            SanitizedBackground::Online { source, keyword } => (*source, keyword),
+            _ => unreachable!(),
+        };
+
+        let downloaded_image = download_daily_image(
+            source,
+            keyword,
+            conf.screen_dimensions,
+            conf.download_timeout_secs,
+        )
+        .map_err(|e| {
+            DeadlinerError::download(
+                format!("Couldn't download today's {:?} image: {}", source, e),
+                e,
+            )
+        })?;
+
+        open_capped(
+            Path::new(&downloaded_image),
+            conf.memory_cap_mb,
+            &format!("Today's {:?} image", source),
+        )
+    }
+}
@@ -0,0 +1,116 @@
+use std::fs;
+
+use image::{imageops::FilterType, DynamicImage, GenericImageView};
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+
+use crate::{hex_to_rgb, unwrap_or_return};
+
+/// A shared branding preset that teams can drop on every machine so all of the
+/// countdown wallpapers look the same.
+///
+/// Example `branding.json`:
+/// ```json
+/// {
+///     "logo_location": "/mnt/shared/acme-logo.png",
+///     "font_color": "#FED843",
+///     "background_color": "#0B101A",
+///     "watermark_corner": "BottomRight"
+/// }
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct Branding {
+    #[serde(default)]
+    pub logo_location: Option<String>,
+    /// Overrides the font color of every render.
+    #[serde(default)]
+    pub font_color: Option<String>,
+    /// Overrides the color of solid backgrounds.
+    #[serde(default)]
+    pub background_color: Option<String>,
+    #[serde(default)]
+    pub watermark_corner: Corner,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize, EnumIter, Default)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+}
+
+impl Branding {
+    pub fn font_color_rgb(&self) -> Option<[u8; 3]> {
+        self.font_color.as_deref().and_then(hex_to_rgb)
+    }
+
+    pub fn background_color_rgb(&self) -> Option<[u8; 3]> {
+        self.background_color.as_deref().and_then(hex_to_rgb)
+    }
+}
+
+/// Reads the branding preset from `location`, an empty location means that no branding is used.
+pub fn load_branding(location: &str) -> Result<Option<Branding>, String> {
+    if location.is_empty() {
+        return Ok(None);
+    }
+
+    let branding_str = unwrap_or_return!(
+        fs::read_to_string(location),
+        "Couldn't read the branding preset!"
+    );
+    let branding: Branding = unwrap_or_return!(
+        serde_json::from_str(&branding_str),
+        "Branding preset is malformed!"
+    );
+
+    if branding.font_color.is_some() && branding.font_color_rgb().is_none()
+        || branding.background_color.is_some() && branding.background_color_rgb().is_none()
+    {
+        return Err(String::from(
+            "Branding colors must be HEX colors like #FED843!",
+        ));
+    }
+
+    Ok(Some(branding))
+}
+
+/// Stamps the brand logo as a watermark in the configured corner of the wallpaper.
+pub fn apply_branding(background: &mut DynamicImage, branding: &Branding) -> Result<(), String> {
+    let logo_location = match &branding.logo_location {
+        Some(location) if !location.trim().is_empty() => location.trim(),
+        _ => return Ok(()),
+    };
+
+    let logo = unwrap_or_return!(image::open(logo_location), "Couldn't open the brand logo!");
+
+    // Logo takes 8% of the wallpaper's width at most without upscaling it
+    let logo_width = {
+        let width = (background.width() as f64 * 0.08) as u32;
+        if width > logo.width() {
+            logo.width()
+        } else {
+            width
+        }
+    };
+    let logo_height = logo.height() * logo_width / logo.width().max(1);
+
+    let logo = image::imageops::resize(&logo, logo_width, logo_height, FilterType::Triangle);
+
+    let offset = 30;
+    let right = background.width().saturating_sub(logo.width() + offset);
+    let bottom = background.height().saturating_sub(logo.height() + offset);
+
+    let (x, y) = match branding.watermark_corner {
+        Corner::TopLeft => (offset, offset),
+        Corner::TopRight => (right, offset),
+        Corner::BottomLeft => (offset, bottom),
+        Corner::BottomRight => (right, bottom),
+    };
+
+    image::imageops::overlay(background, &logo, x, y);
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ScreenDimensions;
+
+/// Serves the countdown over the local network so classroom displays
+/// and info screens can show the same deadline in a browser.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct BroadcastConf {
+    pub enabled: bool,
+    pub port: u16,
+    /// Resolution the broadcasted countdown is rendered at.
+    pub dimensions: ScreenDimensions,
+    /// How often the page reloads the countdown, in seconds.
+    pub refresh_secs: u32,
+}
+
+impl Default for BroadcastConf {
+    fn default() -> Self {
+        BroadcastConf {
+            enabled: false,
+            port: 8080,
+            dimensions: ScreenDimensions {
+                width: 1920,
+                height: 1080,
+            },
+            refresh_secs: 60,
+        }
+    }
+}
+
+impl BroadcastConf {
+    /// Auto-refreshing page showing the rendered countdown fullscreen.
+    pub fn page(&self) -> String {
+        format!(
+            r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<meta http-equiv="refresh" content="{refresh}">
+<title>Deadliner</title>
+<style>
+html, body {{ margin: 0; height: 100%; background: #000; }}
+img {{ width: 100%; height: 100%; object-fit: contain; }}
+</style>
+</head>
+<body><img src="/countdown.png" alt="Countdown"></body>
+</html>"#,
+            refresh = self.refresh_secs.max(1)
+        )
+    }
+}
@@ -0,0 +1,295 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    sync::Mutex,
+};
+
+use chrono::Utc;
+use reqwest::{
+    blocking::RequestBuilder,
+    header::{CONTENT_TYPE, USER_AGENT},
+    Method,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    get_cache_dir, get_config_dir, http_client, parse_ics, progress, sanitize_deadline,
+    save_agenda, send_with_retry, trace, unwrap_or_return, upcoming_events, CalendarEvent,
+    SanitizedConf, SanitizedDeadline,
+};
+
+/// Keeps deadlines in sync with the events of a CalDAV calendar (Nextcloud, iCloud, Fastmail, ...).
+/// Google Calendar works through its CalDAV endpoint
+/// (`https://apidata.googleusercontent.com/caldav/v2/<calendar id>/events`) with an OAuth token.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CalendarSyncConf {
+    pub enabled: bool,
+    /// The calendar collection, Ex: "https://cloud.example.com/remote.php/dav/calendars/me/exams/".
+    pub url: String,
+    pub username: String,
+    /// Never written to the configs, it's kept with the token in `calendar_credentials.json`.
+    /// Still read from configs saved before it was moved out.
+    #[serde(default, skip_serializing)]
+    pub password: String,
+    /// Sent as a bearer token instead of the username and the password when it's not empty.
+    #[serde(default, skip_serializing)]
+    pub token: String,
+    /// Only events with this category (or with it in their summary) are synced, all when empty.
+    pub tag: String,
+    pub interval_minutes: u32,
+}
+
+impl Default for CalendarSyncConf {
+    fn default() -> Self {
+        CalendarSyncConf {
+            enabled: false,
+            url: String::new(),
+            username: String::new(),
+            password: String::new(),
+            token: String::new(),
+            tag: String::new(),
+            interval_minutes: 15,
+        }
+    }
+}
+
+impl CalendarSyncConf {
+    pub fn sanitized(&self) -> Result<CalendarSyncConf, String> {
+        let url = self.url.trim().to_string();
+
+        if self.enabled && !url.starts_with("http") {
+            return Err(String::from(
+                "The calendar URL should start with http:// or https://",
+            ));
+        }
+
+        Ok(CalendarSyncConf {
+            url,
+            username: self.username.trim().to_string(),
+            token: self.token.trim().to_string(),
+            tag: self.tag.trim().to_string(),
+            interval_minutes: self.interval_minutes.max(1),
+            ..self.clone()
+        })
+    }
+
+    /// With the password and the token saved by `save_calendar_credentials`,
+    /// ones read from an older config are kept.
+    pub fn with_credentials(&self) -> CalendarSyncConf {
+        if !self.password.is_empty() || !self.token.is_empty() {
+            return self.clone();
+        }
+
+        let credentials: CalendarCredentials = fs::read_to_string(credentials_path())
+            .ok()
+            .and_then(|credentials| serde_json::from_str(&credentials).ok())
+            .unwrap_or_default();
+
+        CalendarSyncConf {
+            password: credentials.password,
+            token: credentials.token,
+            ..self.clone()
+        }
+    }
+
+    fn authenticated(&self, request: RequestBuilder) -> RequestBuilder {
+        if !self.token.is_empty() {
+            request.bearer_auth(&self.token)
+        } else if !self.username.is_empty() {
+            request.basic_auth(&self.username, Some(&self.password))
+        } else {
+            request
+        }
+    }
+
+    fn matches_tag(&self, event: &CalendarEvent) -> bool {
+        let tag = self.tag.to_lowercase();
+
+        tag.is_empty()
+            || event.summary.to_lowercase().contains(&tag)
+            || event
+                .categories
+                .iter()
+                .any(|category| category.to_lowercase() == tag)
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct CalendarCredentials {
+    password: String,
+    token: String,
+}
+
+/// Next to the config instead of in it, readable only by the user.
+fn credentials_path() -> PathBuf {
+    get_config_dir().join("calendar_credentials.json")
+}
+
+/// Saves the calendar's password and token, the configs are saved without them.
+pub fn save_calendar_credentials(sync: &CalendarSyncConf) -> Result<(), String> {
+    if sync.password.is_empty() && sync.token.is_empty() {
+        fs::remove_file(credentials_path()).ok();
+        return Ok(());
+    }
+
+    let credentials = CalendarCredentials {
+        password: sync.password.clone(),
+        token: sync.token.clone(),
+    };
+
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+
+        options.mode(0o600);
+    }
+
+    let mut file = unwrap_or_return!(
+        options.open(credentials_path()),
+        "Couldn't save the calendar's credentials!"
+    );
+    unwrap_or_return!(
+        file.write_all(serde_json::to_string(&credentials).unwrap().as_bytes()),
+        "Couldn't save the calendar's credentials!"
+    );
+
+    Ok(())
+}
+
+/// Upcoming events only, the server filters the rest out.
+const CALENDAR_QUERY: &str = r#"<?xml version="1.0" encoding="utf-8" ?>
+<C:calendar-query xmlns:D="DAV:" xmlns:C="urn:ietf:params:xml:ns:caldav">
+  <D:prop><C:calendar-data/></D:prop>
+  <C:filter>
+    <C:comp-filter name="VCALENDAR">
+      <C:comp-filter name="VEVENT">
+        <C:time-range start="{start}"/>
+      </C:comp-filter>
+    </C:comp-filter>
+  </C:filter>
+</C:calendar-query>"#;
+
+/// Deadlines from the last successful sync, they're shown next to the configured ones.
+static SYNCED: Mutex<Option<Vec<SanitizedDeadline>>> = Mutex::new(None);
+
+fn synced_path() -> PathBuf {
+    get_cache_dir().join("synced_deadlines.json")
+}
+
+/// The synced deadlines, read from the cache when nothing was synced since the launch.
+pub fn synced_deadlines() -> Vec<SanitizedDeadline> {
+    let mut synced = SYNCED.lock().unwrap();
+
+    synced
+        .get_or_insert_with(|| {
+            fs::read_to_string(synced_path())
+                .ok()
+                .and_then(|synced| serde_json::from_str(&synced).ok())
+                .unwrap_or_default()
+        })
+        .clone()
+}
+
+/// `conf` with the synced deadlines added after the configured ones.
+pub fn with_synced_deadlines(conf: &SanitizedConf) -> SanitizedConf {
+    if !conf.calendar_sync.enabled {
+        return conf.clone();
+    }
+
+    let mut conf = conf.clone();
+    conf.deadlines.extend(synced_deadlines());
+
+    conf
+}
+
+/// Pulls the calendar's upcoming events and replaces the synced deadlines with them,
+/// returns whether they changed.
+pub fn sync_calendar(conf: &SanitizedConf) -> Result<bool, String> {
+    let sync = &conf.calendar_sync.with_credentials();
+    let client = http_client(conf.download_timeout_secs);
+    let query = CALENDAR_QUERY.replace("{start}", &Utc::now().format("%Y%m%dT%H%M%SZ").to_string());
+    let report = Method::from_bytes(b"REPORT").unwrap();
+
+    progress(&format!("Syncing {}…", sync.url));
+
+    let response = unwrap_or_return!(
+        send_with_retry(|| {
+            sync.authenticated(client.request(report.clone(), &sync.url))
+                .header("Depth", "1")
+                .header(CONTENT_TYPE, "application/xml; charset=utf-8")
+                .header(USER_AGENT, "deadliner")
+                .body(query.clone())
+        }),
+        "Couldn't reach the calendar, check the URL and the credentials!"
+    );
+    let multistatus = unwrap_or_return!(response.text(), "Couldn't read the calendar!");
+
+    let mut events = Vec::new();
+    for ics in calendar_data(&multistatus) {
+        events.extend(parse_ics(&ics)?);
+    }
+
+    save_agenda(&events);
+
+    let deadlines: Vec<SanitizedDeadline> = upcoming_events(events, Utc::now())
+        .into_iter()
+        .filter(|event| sync.matches_tag(event))
+        .filter_map(|event| sanitize_deadline(&event.to_deadline(false), false).ok())
+        .collect();
+
+    trace!("synced {} deadlines from {}", deadlines.len(), sync.url);
+    progress(&format!("Synced {} upcoming deadlines", deadlines.len()));
+
+    let changed = serde_json::to_string(&deadlines).unwrap()
+        != serde_json::to_string(&synced_deadlines()).unwrap();
+
+    fs::write(synced_path(), serde_json::to_string(&deadlines).unwrap()).ok();
+    *SYNCED.lock().unwrap() = Some(deadlines);
+
+    Ok(changed)
+}
+
+/// The iCalendar objects in a CalDAV multistatus response,
+/// Ex: `<cal:calendar-data>BEGIN:VCALENDAR…</cal:calendar-data>`.
+fn calendar_data(multistatus: &str) -> Vec<String> {
+    let mut found = Vec::new();
+    let mut rest = multistatus;
+
+    while let Some(start) = rest.find("calendar-data") {
+        let tag = &rest[start..];
+        let open_end = match tag.find('>') {
+            Some(i) => i,
+            None => break,
+        };
+
+        // An empty `<calendar-data/>` has no content to read
+        if tag[..open_end].ends_with('/') {
+            rest = &tag[open_end..];
+            continue;
+        }
+
+        let body = &tag[open_end + 1..];
+        let close = match body.find("calendar-data>") {
+            Some(i) => i,
+            None => break,
+        };
+        let content = &body[..body[..close].rfind("</").unwrap_or(close)];
+
+        found.push(unescape_xml(content));
+        rest = &body[close + "calendar-data>".len()..];
+    }
+
+    found
+}
+
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#13;", "\r")
+        .replace("&amp;", "&")
+}
@@ -0,0 +1,291 @@
+use std::{path::Path, time::Duration};
+
+use crate::{
+    embedded_font, folder_images, font_path, get_config_file, hex_to_rgb, load_branding,
+    load_managed_layer, load_sanitized_conf, parse_timezone, OverdueBehavior, SanitizedBackground,
+    SanitizedConf,
+};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CheckLevel {
+    Pass,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct CheckItem {
+    pub level: CheckLevel,
+    pub message: String,
+    /// How to fix a warning or an error.
+    pub fix: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CheckReport {
+    pub items: Vec<CheckItem>,
+}
+
+impl CheckReport {
+    pub fn pass(&mut self, message: &str) {
+        self.push(CheckLevel::Pass, message, None);
+    }
+
+    pub fn warn(&mut self, message: &str, fix: &str) {
+        self.push(CheckLevel::Warning, message, Some(fix));
+    }
+
+    pub fn error(&mut self, message: &str, fix: &str) {
+        self.push(CheckLevel::Error, message, Some(fix));
+    }
+
+    fn push(&mut self, level: CheckLevel, message: &str, fix: Option<&str>) {
+        self.items.push(CheckItem {
+            level,
+            message: message.to_string(),
+            fix: fix.map(String::from),
+        });
+    }
+
+    pub fn count(&self, level: CheckLevel) -> usize {
+        self.items.iter().filter(|item| item.level == level).count()
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.count(CheckLevel::Error) > 0
+    }
+
+    /// Human readable report with a line per check and a summary at the end.
+    pub fn summary(&self) -> String {
+        let mut lines: Vec<String> = Vec::new();
+
+        for item in &self.items {
+            let icon = match item.level {
+                CheckLevel::Pass => "✔",
+                CheckLevel::Warning => "⚠",
+                CheckLevel::Error => "✖",
+            };
+            lines.push(format!("{} {}", icon, item.message));
+
+            if let Some(fix) = &item.fix {
+                lines.push(format!("    Fix: {}", fix));
+            }
+        }
+
+        lines.push(String::new());
+        lines.push(format!(
+            "{} passed, {} warnings, {} errors",
+            self.count(CheckLevel::Pass),
+            self.count(CheckLevel::Warning),
+            self.count(CheckLevel::Error)
+        ));
+
+        lines.join("\n")
+    }
+}
+
+/// Validates the saved config and everything it points at, used by `deadliner check`.
+pub fn check_config() -> CheckReport {
+    let mut report = CheckReport::default();
+
+    match load_managed_layer() {
+        Ok(layer) if layer.is_empty() => (),
+        Ok(layer) => report.pass(&format!("Managed config locks {} settings", layer.len())),
+        Err(msg) => report.error(&msg, "Ask your administrator to fix the managed config"),
+    }
+
+    let conf = match load_sanitized_conf() {
+        Ok(conf) => {
+            report.pass(&format!(
+                "Config loaded from {}",
+                get_config_file().display()
+            ));
+            conf
+        }
+        Err(msg) => {
+            report.error(
+                &msg,
+                "Open Deadliner and hit \"Save!\" to write a new config",
+            );
+            return report;
+        }
+    };
+
+    check_deadlines(&conf, &mut report);
+    check_styling(&conf, &mut report);
+    check_font(&conf, &mut report);
+    check_background(&conf, &mut report);
+
+    if let Err(msg) = load_branding(&conf.branding_location) {
+        report.error(
+            &msg,
+            "Pick another branding preset or clear it in the Styling section",
+        );
+    } else if !conf.branding_location.is_empty() {
+        report.pass("Branding preset is valid");
+    }
+
+    report
+}
+
+fn check_deadlines(conf: &SanitizedConf, report: &mut CheckReport) {
+    if conf.deadlines.is_empty() {
+        report.error("There are no deadlines", "Add a deadline and save again");
+        return;
+    }
+
+    for deadline in &conf.deadlines {
+        let name = if deadline.title.is_empty() {
+            deadline.deadline_str.clone()
+        } else {
+            format!("\"{}\"", deadline.title)
+        };
+
+        if let Some(timezone) = &deadline.timezone {
+            if let Err(msg) = parse_timezone(timezone) {
+                report.error(
+                    &format!("Deadline {}: {}", name, msg),
+                    "Use an IANA name like Europe/Berlin or leave it empty",
+                );
+                continue;
+            }
+        }
+
+        if !deadline.template.contains("{countdown}") {
+            report.error(
+                &format!("Deadline {}: the template is missing {{countdown}}", name),
+                "Add {countdown} to the template or clear it",
+            );
+        }
+
+        if deadline.counts_up {
+            report.pass(&format!("Deadline {} counts up", name));
+        } else if deadline.minutes_left() <= 0 {
+            match conf.overdue {
+                OverdueBehavior::CountUp | OverdueBehavior::Message(_) => {
+                    report.pass(&format!("Deadline {} is over and still shown", name))
+                }
+                _ => report.warn(
+                    &format!("Deadline {} is over", name),
+                    "Pick a new date or remove the deadline",
+                ),
+            }
+        } else {
+            report.pass(&format!("Deadline {} is in the future", name));
+        }
+    }
+}
+
+fn check_styling(conf: &SanitizedConf, report: &mut CheckReport) {
+    if conf.shown_units().is_empty() {
+        report.error(
+            "No time units are shown",
+            "Tick at least one unit that's not smaller than the precision",
+        );
+    }
+
+    for (name, color) in [
+        ("Font color", &conf.font_color),
+        ("Title color", &conf.title_font_color),
+    ] {
+        if hex_to_rgb(color).is_none() {
+            report.error(
+                &format!("{} \"{}\" isn't a HEX color", name, color),
+                "Pick the color again in the Styling section",
+            );
+        }
+    }
+
+    if conf.broadcast.enabled && conf.broadcast.port < 1024 {
+        report.warn(
+            &format!(
+                "Broadcast port {} usually needs admin rights",
+                conf.broadcast.port
+            ),
+            "Use a port above 1024, Ex: 8080",
+        );
+    }
+}
+
+fn check_font(conf: &SanitizedConf, report: &mut CheckReport) {
+    let font = font_path(conf);
+
+    if font.is_file() {
+        report.pass(&format!("Font found at {}", font.display()));
+    } else if embedded_font(conf.font).is_some() {
+        report.pass(&format!("Using the built-in {:?} font", conf.font));
+    } else {
+        report.warn(
+            &format!("Font file {} doesn't exist", font.display()),
+            "The bundled font is used instead, pick the font again to use it",
+        );
+    }
+}
+
+fn check_background(conf: &SanitizedConf, report: &mut CheckReport) {
+    match &conf.default_bg {
+        SanitizedBackground::Solid { .. } => report.pass("Solid background"),
+        SanitizedBackground::Gradient { .. } => report.pass("Gradient background"),
+        SanitizedBackground::FromDisk(location) => {
+            if !Path::new(location).is_file() {
+                report.error(
+                    &format!("Background image {} doesn't exist", location),
+                    "Pick the background image again",
+                );
+            } else if image::image_dimensions(location).is_err() {
+                report.error(
+                    &format!("Background image {} can't be decoded", location),
+                    "Use a PNG or a JPEG image",
+                );
+            } else {
+                report.pass(&format!("Background image found at {}", location));
+            }
+        }
+        SanitizedBackground::FromFolder { path, .. } => match folder_images(path) {
+            Ok(images) if images.is_empty() => report.error(
+                &format!("Background folder {} has no images", path),
+                "Add PNG or JPEG images to the folder or pick another one",
+            ),
+            Ok(images) => report.pass(&format!(
+                "Background folder {} has {} images",
+                path,
+                images.len()
+            )),
+            Err(_) => report.error(
+                &format!("Background folder {} can't be read", path),
+                "Pick the background folder again",
+            ),
+        },
+        SanitizedBackground::Online { source, keyword } => {
+            match source.image_url(keyword, conf.screen_dimensions, 5) {
+                Ok(_) => report.pass(&format!("{:?} background is reachable", source)),
+                Err(e) => report.warn(
+                    &format!("{:?} background isn't available: {}", source, e),
+                    "Today's image is used once it's available, the last one is kept till then",
+                ),
+            }
+        }
+        SanitizedBackground::FromURL(url) => {
+            let reachable = reqwest::blocking::Client::builder()
+                .timeout(Duration::from_secs(5))
+                .build()
+                .ok()
+                .and_then(|client| client.get(url).send().ok())
+                .map(|res| res.status());
+
+            match reachable {
+                Some(status) if status.is_success() => {
+                    report.pass(&format!("Background URL {} is reachable", url))
+                }
+                Some(status) => report.error(
+                    &format!("Background URL {} responded with {}", url, status),
+                    "Check the URL or download the image and pick it from disk",
+                ),
+                None => report.warn(
+                    &format!("Background URL {} isn't reachable", url),
+                    "Check your internet connection, images downloaded before keep working",
+                ),
+            }
+        }
+    }
+}
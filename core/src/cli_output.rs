@@ -0,0 +1,102 @@
+use std::{
+    env,
+    io::{stderr, IsTerminal, Write},
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+};
+
+use crate::DownloadProgress;
+
+/// Progress is only printed by the CLI, the GUI and the schedular keep quiet.
+static PROGRESS: AtomicBool = AtomicBool::new(false);
+static QUIET: AtomicBool = AtomicBool::new(false);
+static COLOR: AtomicBool = AtomicBool::new(false);
+/// The last download progress printed, in percent or in MB when the size isn't known.
+static LAST_DOWNLOAD_STEP: AtomicU64 = AtomicU64::new(u64::MAX);
+
+const DIM: &str = "2";
+const CYAN: &str = "36";
+const RED: &str = "31";
+const GREEN: &str = "32";
+
+/// Turns on the CLI's progress output, `quiet` leaves only the errors for cron logs.
+/// Colors are off with `--no-color`, with the `NO_COLOR` env var or when stderr isn't a terminal.
+pub fn set_cli_output(quiet: bool, color: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+    PROGRESS.store(!quiet, Ordering::Relaxed);
+    COLOR.store(
+        color && env::var_os("NO_COLOR").is_none() && stderr().is_terminal(),
+        Ordering::Relaxed,
+    );
+}
+
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Wraps `text` in an ANSI color when colors are on.
+fn paint(text: &str, color: &str) -> String {
+    if COLOR.load(Ordering::Relaxed) {
+        format!("\x1b[{}m{}\x1b[0m", color, text)
+    } else {
+        text.to_string()
+    }
+}
+
+pub fn paint_error(text: &str) -> String {
+    paint(text, RED)
+}
+
+pub fn paint_success(text: &str) -> String {
+    paint(text, GREEN)
+}
+
+/// Prints a step of a long-running CLI command, Ex: "→ Rendering the wallpaper…".
+pub fn progress(step: &str) {
+    if !PROGRESS.load(Ordering::Relaxed) {
+        return;
+    }
+
+    eprintln!("{} {}", paint("→", CYAN), step);
+}
+
+/// Prints how far a download got, a terminal gets a line updated in place
+/// while logs get a line per 25% so they don't fill up.
+pub(crate) fn print_download_progress(progress: &DownloadProgress, finished: bool) {
+    if !PROGRESS.load(Ordering::Relaxed) {
+        return;
+    }
+
+    let is_terminal = stderr().is_terminal();
+
+    let step = match progress.total {
+        Some(total) if total > 0 => {
+            let percent = progress.downloaded * 100 / total;
+            if is_terminal {
+                percent
+            } else {
+                percent / 25 * 25
+            }
+        }
+        _ => progress.downloaded / (1024 * 1024),
+    };
+
+    if !finished && LAST_DOWNLOAD_STEP.swap(step, Ordering::Relaxed) == step {
+        return;
+    }
+
+    let line = format!("{} {}", paint("↓", CYAN), paint(&progress.to_string(), DIM));
+
+    if is_terminal {
+        eprint!("\r\x1b[2K{}", line);
+        if finished {
+            eprintln!();
+        }
+        stderr().flush().ok();
+    } else {
+        eprintln!("{}", line);
+    }
+
+    if finished {
+        LAST_DOWNLOAD_STEP.store(u64::MAX, Ordering::Relaxed);
+    }
+}
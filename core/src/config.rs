@@ -0,0 +1,492 @@
+use std::fs;
+
+use chrono::{NaiveDateTime, Timelike};
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+use wallpaper::Mode;
+
+use crate::{
+    current_config_version, get_profile_cache_dir, merge_monitors, migrate_config, trace,
+    AgendaConf, AnnouncementsConf, BackdropConf, BroadcastConf, CalendarSyncConf, CountdownStyle,
+    DeadlineHookConf, DeadlineTag, FinalHoursConf, FocusConf, GradientDirection, ImageFilters,
+    Layout, Locale, MonitorConf, NotificationsConf, OnlineSource, OutputTargetConf,
+    OverdueBehavior, Pattern, PerformanceBudget, PngCompression, Recurrence, RotationMode,
+    RoundingMode, ScriptVariableConf, SeasonalConf, SoundAlertsConf, StaleWarningConf,
+    StressFreeConf, TextPosition, TimeUnit, UnitFormat, UrgencyColorsConf, WebhooksConf,
+    WorkdaysConf, CONFIG_VERSION,
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DeadlinerConf {
+    /// Schema version the inputs were saved with, see `migrate_config`.
+    #[serde(default = "current_config_version")]
+    pub version: u32,
+
+    pub screen_dimensions: ScreenDimensions,
+    #[serde(default)]
+    pub monitors: Vec<MonitorConf>,
+
+    pub default_background: Background,
+    #[serde(default)]
+    pub render_mode_in_app: bool,
+    #[serde(default)]
+    pub background_filters: ImageFilters,
+    #[serde(default)]
+    pub png_compression: PngCompression,
+    #[serde(default)]
+    pub performance: PerformanceBudget,
+    /// A refresh taking longer than this is aborted and retried later.
+    #[serde(default = "default_refresh_timeout_secs")]
+    pub refresh_timeout_secs: u32,
+    /// How long a downloaded URL background is used before checking if it changed.
+    #[serde(default = "default_url_cache_ttl_hours")]
+    pub url_cache_ttl_hours: u32,
+    /// A download attempt is given up after this, failed attempts are retried a few times.
+    #[serde(default = "default_download_timeout_secs")]
+    pub download_timeout_secs: u32,
+    /// Backgrounds that would take more memory decoded are scaled down or refused, 0 doesn't cap them.
+    #[serde(default = "default_memory_cap_mb")]
+    pub memory_cap_mb: u32,
+    /// Where the rendered wallpaper goes, Ex: the desktop and the lock screen.
+    #[serde(default = "default_output_targets")]
+    pub output_targets: Vec<OutputTargetConf>,
+    /// Starts the schedular (and its tray icon) when the user logs in.
+    #[serde(default = "default_launch_on_login")]
+    pub launch_on_login: bool,
+
+    pub show_months: bool,
+    pub show_weeks: bool,
+    pub show_days: bool,
+    pub show_hours: bool,
+    #[serde(default)]
+    pub show_minutes: bool,
+    #[serde(default)]
+    pub show_seconds: bool,
+    /// Precision of the countdown, smaller units are never shown.
+    #[serde(default = "default_smallest_unit")]
+    pub smallest_unit: TimeUnit,
+    #[serde(default)]
+    pub rounding: RoundingMode,
+    #[serde(default)]
+    pub unit_format: UnitFormat,
+    #[serde(default)]
+    pub countdown_style: CountdownStyle,
+    #[serde(default)]
+    pub stress_free: StressFreeConf,
+    #[serde(default)]
+    pub focus: FocusConf,
+    #[serde(default)]
+    pub workdays: WorkdaysConf,
+
+    #[serde(default)]
+    pub layout: Layout,
+    pub font: Font,
+    pub font_size: u8,
+    pub font_color: [u8; 3],
+    #[serde(default)]
+    pub urgency_colors: UrgencyColorsConf,
+    #[serde(default)]
+    pub final_hours: FinalHoursConf,
+    pub custom_font_location: String,
+
+    #[serde(default = "default_title_font_size")]
+    pub title_font_size: u8,
+    #[serde(default = "default_title_font_color")]
+    pub title_font_color: [u8; 3],
+
+    #[serde(default)]
+    pub text_position: TextPosition,
+    #[serde(default)]
+    pub high_contrast: bool,
+    #[serde(default)]
+    pub backdrop: BackdropConf,
+    /// Time zones the deadline's time is also shown in, Ex: ["Europe/Berlin", "America/New_York"].
+    #[serde(default)]
+    pub world_clocks: Vec<String>,
+    /// Commands whose output fills `{name}` in the templates and the titles.
+    #[serde(default)]
+    pub text_variables: Vec<ScriptVariableConf>,
+
+    #[serde(default)]
+    pub branding_location: String,
+
+    pub deadlines: Vec<Deadline>,
+    /// Deadlines' hours are entered from 0 to 23 without a period.
+    #[serde(default)]
+    pub use_24_hour: bool,
+    #[serde(default)]
+    pub overdue: OverdueBehavior,
+    #[serde(default)]
+    pub rotation: RotationMode,
+
+    #[serde(default)]
+    pub broadcast: BroadcastConf,
+    #[serde(default)]
+    pub announcements: AnnouncementsConf,
+    #[serde(default)]
+    pub sound_alerts: SoundAlertsConf,
+    #[serde(default)]
+    pub notifications: NotificationsConf,
+    #[serde(default)]
+    pub deadline_hook: DeadlineHookConf,
+    #[serde(default)]
+    pub webhooks: WebhooksConf,
+    #[serde(default)]
+    pub calendar_sync: CalendarSyncConf,
+    #[serde(default)]
+    pub agenda: AgendaConf,
+    #[serde(default)]
+    pub seasonal: SeasonalConf,
+    /// Categories the deadlines can be tagged with, each one with its own color.
+    #[serde(default)]
+    pub tags: Vec<DeadlineTag>,
+    #[serde(default)]
+    pub stale_warning: StaleWarningConf,
+}
+
+impl DeadlinerConf {
+    pub fn new(screen_dimensions: ScreenDimensions, monitors: Vec<MonitorConf>) -> DeadlinerConf {
+        DeadlinerConf {
+            version: CONFIG_VERSION,
+            screen_dimensions,
+            monitors,
+            default_background: Background::Solid {
+                color: [0; 3],
+                pattern: Pattern::None,
+            },
+            render_mode_in_app: false,
+            background_filters: ImageFilters::default(),
+            png_compression: PngCompression::Fast,
+            performance: PerformanceBudget::Normal,
+            refresh_timeout_secs: default_refresh_timeout_secs(),
+            url_cache_ttl_hours: default_url_cache_ttl_hours(),
+            download_timeout_secs: default_download_timeout_secs(),
+            memory_cap_mb: default_memory_cap_mb(),
+            output_targets: default_output_targets(),
+            launch_on_login: default_launch_on_login(),
+            custom_font_location: String::new(),
+            text_position: TextPosition::Center,
+            high_contrast: false,
+            backdrop: BackdropConf::default(),
+            world_clocks: Vec::new(),
+            text_variables: Vec::new(),
+            branding_location: String::new(),
+            layout: Layout::Text,
+            font: Font::PoppinsBlack,
+            deadlines: vec![Deadline::default()],
+            use_24_hour: false,
+            overdue: OverdueBehavior::Celebrate,
+            rotation: RotationMode::Stacked,
+            broadcast: BroadcastConf::default(),
+            announcements: AnnouncementsConf::default(),
+            sound_alerts: SoundAlertsConf::default(),
+            notifications: NotificationsConf::default(),
+            deadline_hook: DeadlineHookConf::default(),
+            webhooks: WebhooksConf::default(),
+            calendar_sync: CalendarSyncConf::default(),
+            agenda: AgendaConf::default(),
+            seasonal: SeasonalConf::default(),
+            tags: Vec::new(),
+            stale_warning: StaleWarningConf::default(),
+            font_size: 100,
+            font_color: [255, 255, 255],
+            urgency_colors: UrgencyColorsConf::default(),
+            final_hours: FinalHoursConf::default(),
+            title_font_size: default_title_font_size(),
+            title_font_color: default_title_font_color(),
+            show_hours: true,
+            show_days: true,
+            show_weeks: false,
+            show_months: false,
+            show_minutes: false,
+            show_seconds: false,
+            smallest_unit: default_smallest_unit(),
+            rounding: RoundingMode::HalfUp,
+            unit_format: UnitFormat::default(),
+            countdown_style: CountdownStyle::Exact,
+            stress_free: StressFreeConf::default(),
+            focus: FocusConf::default(),
+            workdays: WorkdaysConf::default(),
+        }
+    }
+
+    /// The inputs from the last time they were edited, shared by the GUI and the TUI.
+    /// `detected_monitors` is `None` where monitors can't be detected (Ex: over SSH),
+    /// the saved ones are kept as they are then.
+    pub fn load_cached(
+        screen_dimensions: ScreenDimensions,
+        detected_monitors: Option<Vec<MonitorConf>>,
+    ) -> DeadlinerConf {
+        let cached = get_profile_cache_dir().join("raw_config.json");
+        let default = || {
+            DeadlinerConf::new(
+                screen_dimensions,
+                detected_monitors.clone().unwrap_or_default(),
+            )
+        };
+
+        if !cached.exists() {
+            return default();
+        }
+
+        let migrated = fs::read_to_string(&cached)
+            .map_err(|e| e.to_string())
+            .and_then(|conf_str| migrate_config(&conf_str));
+
+        let mut conf = migrated.unwrap_or_else(|e| {
+            trace!("starting over with the default inputs: {}", e);
+
+            // Kept around so the inputs can still be recovered by hand
+            fs::rename(&cached, cached.with_extension("json.bak")).ok();
+
+            default()
+        });
+
+        if conf.deadlines.is_empty() {
+            conf.deadlines.push(Deadline::default());
+        }
+
+        // Monitors could've been plugged or unplugged since the last time
+        if let Some(detected) = detected_monitors.clone() {
+            conf.monitors = merge_monitors(detected, &conf.monitors);
+        }
+
+        conf.calendar_sync = conf.calendar_sync.with_credentials();
+
+        conf
+    }
+}
+
+fn default_smallest_unit() -> TimeUnit {
+    TimeUnit::Minute
+}
+
+fn default_refresh_timeout_secs() -> u32 {
+    60
+}
+
+fn default_url_cache_ttl_hours() -> u32 {
+    24
+}
+
+fn default_download_timeout_secs() -> u32 {
+    15
+}
+
+fn default_memory_cap_mb() -> u32 {
+    512
+}
+
+fn default_output_targets() -> Vec<OutputTargetConf> {
+    vec![OutputTargetConf::Desktop]
+}
+
+pub(crate) fn default_launch_on_login() -> bool {
+    true
+}
+
+fn default_title_font_size() -> u8 {
+    50
+}
+
+fn default_title_font_color() -> [u8; 3] {
+    [231, 231, 231]
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Deadline {
+    /// Rendered above the countdown, Ex: "Thesis submission".
+    #[serde(default)]
+    pub title: String,
+
+    pub date: String,
+    pub hours: String,
+    pub minutes: String,
+    pub period: Periods,
+    /// IANA time zone name, Ex: "America/New_York", empty for the local time zone.
+    #[serde(default)]
+    pub timezone: String,
+    /// Once an occurrence passes the countdown rolls to the next one.
+    #[serde(default)]
+    pub recurrence: Recurrence,
+    /// Counts up the time since a past date instead, Ex: "It's been 127 Days".
+    #[serde(default)]
+    pub counts_up: bool,
+    /// Name of one of the conf's tags, Ex: "uni", empty leaves the deadline untagged.
+    #[serde(default)]
+    pub tag: String,
+
+    pub locale: Locale,
+    /// Overrides the locale's default template when it's not empty.
+    pub template: String,
+}
+
+impl Deadline {
+    /// Fills the date and the time inputs with `at`.
+    pub fn set_date_time(&mut self, at: NaiveDateTime, use_24_hour: bool) {
+        self.date = at.format("%Y-%m-%d").to_string();
+        self.minutes = at.minute().to_string();
+
+        if use_24_hour {
+            self.hours = at.hour().to_string();
+        } else {
+            let (is_pm, hours) = at.hour12();
+
+            self.hours = hours.to_string();
+            self.period = if is_pm { Periods::PM } else { Periods::AM };
+        }
+    }
+
+    /// Converts the entered hours between the 12-hour and the 24-hour formats.
+    pub fn convert_hours(&mut self, to_24_hour: bool) {
+        let hours: u32 = match self.hours.trim().parse() {
+            Ok(hours) => hours,
+            Err(_) => return,
+        };
+
+        if to_24_hour {
+            let hours = match (hours % 12, self.period) {
+                (hours, Periods::AM) => hours,
+                (hours, Periods::PM) => hours + 12,
+            };
+
+            self.hours = hours.to_string();
+        } else {
+            self.period = if hours >= 12 {
+                Periods::PM
+            } else {
+                Periods::AM
+            };
+            self.hours = match hours % 12 {
+                0 => 12,
+                hours => hours,
+            }
+            .to_string();
+        }
+    }
+}
+
+impl Default for Deadline {
+    fn default() -> Self {
+        Deadline {
+            title: String::new(),
+            date: String::new(),
+            // The end of the day unless another time is picked
+            hours: String::from("11"),
+            minutes: String::from("59"),
+            period: Periods::PM,
+            timezone: String::new(),
+            recurrence: Recurrence::Never,
+            counts_up: false,
+            tag: String::new(),
+            locale: Locale::English,
+            template: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct ScreenDimensions {
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone, EnumIter, Serialize, Deserialize)]
+pub enum Periods {
+    AM,
+    PM,
+}
+
+#[derive(Debug, PartialEq, Clone, EnumIter, Serialize, Deserialize)]
+pub enum Background {
+    Solid {
+        color: [u8; 3],
+        /// A subtle texture over the color.
+        #[serde(default)]
+        pattern: Pattern,
+    },
+    Gradient {
+        start: [u8; 3],
+        end: [u8; 3],
+        direction: GradientDirection,
+    },
+    FromDisk {
+        location: String,
+        mode: WallpaperMode,
+    },
+    FromURL {
+        url: String,
+        mode: WallpaperMode,
+    },
+    /// A different image of the folder every time the wallpaper regenerates.
+    FromFolder {
+        path: String,
+        shuffle: bool,
+        mode: WallpaperMode,
+    },
+    /// A fresh photo every day from a built-in source.
+    Online {
+        source: OnlineSource,
+        /// Only used by the sources that search, Ex: Unsplash.
+        keyword: String,
+        mode: WallpaperMode,
+    },
+}
+
+impl Background {
+    pub fn mode(&self) -> WallpaperMode {
+        match self {
+            Background::FromURL { mode, .. }
+            | Background::FromDisk { mode, .. }
+            | Background::FromFolder { mode, .. }
+            | Background::Online { mode, .. } => *mode,
+            Background::Solid { .. } | Background::Gradient { .. } => WallpaperMode::Center,
+        }
+    }
+}
+
+impl std::fmt::Display for Background {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Solid { .. } => "Solid",
+                Self::Gradient { .. } => "Gradient",
+                Self::FromDisk { .. } => "From Disk",
+                Self::FromURL { .. } => "From URL",
+                Self::FromFolder { .. } => "From Folder",
+                Self::Online { .. } => "Online",
+            }
+        )
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Copy, EnumIter, Serialize, Deserialize)]
+pub enum Font {
+    PoppinsBlack,
+    PoppinsMedium,
+    PoppinsRegular,
+    PoppinsLight,
+    ChooseFromDisk,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, EnumIter, Serialize, Deserialize, Default)]
+pub enum WallpaperMode {
+    #[default]
+    Center,
+    Crop,
+    Fit,
+    Span,
+}
+
+impl From<WallpaperMode> for Mode {
+    fn from(mode: WallpaperMode) -> Self {
+        match mode {
+            WallpaperMode::Center => Mode::Center,
+            WallpaperMode::Crop => Mode::Crop,
+            WallpaperMode::Fit => Mode::Fit,
+            WallpaperMode::Span => Mode::Span,
+        }
+    }
+}
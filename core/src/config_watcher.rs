@@ -0,0 +1,53 @@
+use std::{
+    fs,
+    sync::mpsc::{self, Receiver},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::{get_profile_cache_dir, trace};
+
+const POLL_EVERY: Duration = Duration::from_secs(2);
+
+/// Watches the inputs of the active profile (`raw_config.json`) and sends whenever they're
+/// changed, Ex: edited by hand or by a sync tool. `on_change` wakes up whoever's receiving.
+pub fn watch_config(on_change: impl Fn() + Send + 'static) -> Receiver<()> {
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        let mut last = config_stamp();
+
+        loop {
+            thread::sleep(POLL_EVERY);
+
+            let stamp = config_stamp();
+            if stamp == last {
+                continue;
+            }
+
+            // Wait for the file to be fully written before reading it
+            thread::sleep(POLL_EVERY);
+            let settled = config_stamp();
+            if settled != stamp {
+                continue;
+            }
+
+            trace!("the saved inputs changed on disk");
+
+            last = settled;
+            if sender.send(()).is_err() {
+                return;
+            }
+            on_change();
+        }
+    });
+
+    receiver
+}
+
+/// Modification time and size of the inputs, `None` while they don't exist.
+fn config_stamp() -> Option<(Option<SystemTime>, u64)> {
+    let metadata = fs::metadata(get_profile_cache_dir().join("raw_config.json")).ok()?;
+
+    Some((metadata.modified().ok(), metadata.len()))
+}
@@ -0,0 +1,122 @@
+use image::{DynamicImage, GenericImageView, Pixel, Rgba, RgbaImage};
+
+use crate::{draw_backdrop_box, trace, BackdropConf};
+
+/// WCAG AA's minimum contrast ratio for text.
+pub const MIN_CONTRAST_RATIO: f32 = 4.5;
+
+const WHITE: [u8; 3] = [255, 255, 255];
+const BLACK: [u8; 3] = [0, 0, 0];
+
+/// Source: https://www.w3.org/TR/WCAG21/#dfn-relative-luminance
+pub fn relative_luminance([r, g, b]: [u8; 3]) -> f32 {
+    let channel = |c: u8| {
+        let c = c as f32 / 255.;
+
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+
+    0.2126 * channel(r) + 0.7152 * channel(g) + 0.0722 * channel(b)
+}
+
+pub fn contrast_ratio(a: f32, b: f32) -> f32 {
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// Makes sure `text` is readable where it's placed on `background`, by switching it to
+/// white or black and by drawing a backdrop behind it when the background is too busy for both.
+pub fn enforce_contrast(background: &mut DynamicImage, text: &mut RgbaImage, x: u32, y: u32) {
+    let (darkest, brightest) = luminance_range(background, x, y, text.width(), text.height());
+
+    // The lowest contrast against any part of the background behind the text
+    let worst_contrast = |luminance: f32| {
+        contrast_ratio(luminance, darkest).min(contrast_ratio(luminance, brightest))
+    };
+
+    let text_luminance = match average_color(text) {
+        Some(color) => relative_luminance(color),
+        None => return,
+    };
+
+    if worst_contrast(text_luminance) >= MIN_CONTRAST_RATIO {
+        return;
+    }
+
+    let white_contrast = worst_contrast(1.);
+    let black_contrast = worst_contrast(0.);
+
+    if white_contrast.max(black_contrast) >= MIN_CONTRAST_RATIO {
+        let color = if white_contrast >= black_contrast {
+            WHITE
+        } else {
+            BLACK
+        };
+
+        trace!("recoloring the text to {:?} for contrast", color);
+
+        recolor(text, color);
+    } else {
+        trace!("drawing a backdrop behind the text for contrast");
+
+        recolor(text, WHITE);
+        draw_backdrop(background, x, y, text.width(), text.height());
+    }
+}
+
+/// Luminance of the darkest and the brightest parts behind the text.
+fn luminance_range(image: &DynamicImage, x: u32, y: u32, width: u32, height: u32) -> (f32, f32) {
+    let mut range = (1f32, 0f32);
+
+    // Every 4th pixel is plenty to know how busy the background is
+    for py in (y..(y + height).min(image.height())).step_by(4) {
+        for px in (x..(x + width).min(image.width())).step_by(4) {
+            let [r, g, b, _] = image.get_pixel(px, py).0;
+            let luminance = relative_luminance([r, g, b]);
+
+            range = (range.0.min(luminance), range.1.max(luminance));
+        }
+    }
+
+    range
+}
+
+/// Average color of the visible pixels, `None` when there are none.
+fn average_color(image: &RgbaImage) -> Option<[u8; 3]> {
+    let mut sum = [0u64; 3];
+    let mut count = 0;
+
+    for pixel in image.pixels().filter(|pixel| pixel[3] > 128) {
+        for (total, channel) in sum.iter_mut().zip(pixel.channels()) {
+            *total += *channel as u64;
+        }
+        count += 1;
+    }
+
+    if count == 0 {
+        return None;
+    }
+
+    Some(sum.map(|total| (total / count) as u8))
+}
+
+fn recolor(image: &mut RgbaImage, [r, g, b]: [u8; 3]) {
+    for pixel in image.pixels_mut() {
+        *pixel = Rgba([r, g, b, pixel[3]]);
+    }
+}
+
+fn draw_backdrop(background: &mut DynamicImage, x: u32, y: u32, width: u32, height: u32) {
+    let backdrop = BackdropConf {
+        enabled: true,
+        color: BLACK,
+        opacity: 82,
+        padding: height / 6,
+        corner_radius: 0,
+    };
+
+    draw_backdrop_box(background, (x, y, width, height), &backdrop);
+}
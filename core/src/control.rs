@@ -0,0 +1,140 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    path::PathBuf,
+};
+
+use chrono::NaiveDateTime;
+use serde_json::{json, Value};
+
+use crate::{
+    format_deadline, get_config_dir, get_schedular_port, is_paused, load_sanitized_conf, publish,
+    refresh_now, sanitize_inputs, set_paused, trace, update_wallpaper, write_inputs, DeadlinerConf,
+    DeadlinerEvent,
+};
+
+const CONTROL_USAGE: &str =
+    "Commands: refresh, pause, resume, set-deadline <YYYY-MM-DD HH:MM>, query";
+
+/// Where the running schedular listens for control commands on Unix,
+/// every OS user has their own config dir so scripts only reach their own deadliner.
+pub fn control_socket_path() -> PathBuf {
+    get_config_dir().join("control.sock")
+}
+
+/// Windows has no Unix sockets in std, the control protocol goes over a local port there.
+/// Shifted by 2000 to stay clear of the schedular's and the GUI's ports.
+pub fn control_port() -> u16 {
+    get_schedular_port().wrapping_add(2000)
+}
+
+/// Runs a control command, one line of text, and answers with one line of JSON,
+/// Ex: `query` answers `{"ok":true,"paused":false,"deadlines":[...]}`.
+pub fn handle_control_command(command: &str) -> Value {
+    let mut parts = command.trim().splitn(2, ' ');
+    let name = parts.next().unwrap_or_default();
+    let args = parts.next().unwrap_or_default().trim();
+
+    trace!("control command: {}", command.trim());
+
+    let result = match name {
+        "refresh" => refresh_now().map(|_| json!({})),
+        "pause" => set_paused(true).map(|_| json!({})),
+        "resume" => set_paused(false)
+            .and_then(|_| refresh_now())
+            .map(|_| json!({})),
+        "set-deadline" => set_deadline(args).map(|_| json!({})),
+        "query" => query(),
+        _ => Err(format!("Unknown command `{}`. {}", name, CONTROL_USAGE)),
+    };
+
+    match result {
+        Ok(Value::Object(mut response)) => {
+            response.insert(String::from("ok"), json!(true));
+            Value::Object(response)
+        }
+        Ok(response) => response,
+        Err(e) => json!({ "ok": false, "error": e }),
+    }
+}
+
+/// Moves the first deadline to `at` (Ex: "2024-06-01 17:00") and saves it.
+/// Control commands are run by the schedular itself, so it isn't restarted like a save
+/// from the GUI does, its updates are scheduled again in place.
+fn set_deadline(at: &str) -> Result<(), String> {
+    let at = NaiveDateTime::parse_from_str(at, "%Y-%m-%d %H:%M")
+        .map_err(|_| format!("`{}` isn't a date like 2024-06-01 17:00", at))?;
+
+    let saved = load_sanitized_conf()?;
+    let mut conf = DeadlinerConf::load_cached(saved.screen_dimensions, None);
+
+    let use_24_hour = conf.use_24_hour;
+    match conf.deadlines.first_mut() {
+        Some(deadline) => deadline.set_date_time(at, use_24_hour),
+        None => return Err(String::from("There's no deadline to move")),
+    }
+
+    let sanitized_conf = sanitize_inputs(&conf)?;
+
+    // Check for any potential errors before saving this conf, like the GUI does
+    update_wallpaper(&sanitized_conf, true)?;
+
+    write_inputs(&conf, &sanitized_conf)?;
+    publish(DeadlinerEvent::InputsSaved);
+
+    refresh_now()
+}
+
+fn query() -> Result<Value, String> {
+    let conf = load_sanitized_conf()?;
+
+    let deadlines: Vec<Value> = conf
+        .deadlines
+        .iter()
+        .map(|deadline| {
+            json!({
+                "title": deadline.title,
+                "date": deadline.date().to_rfc3339(),
+                "minutes_left": deadline.minutes_left(),
+                "countdown": format_deadline(&conf, deadline, false),
+            })
+        })
+        .collect();
+
+    Ok(json!({ "paused": is_paused(), "deadlines": deadlines }))
+}
+
+/// Sends `command` to the running schedular and returns its JSON answer.
+pub fn send_control_command(command: &str) -> Result<String, String> {
+    let not_running = |_| String::from("Deadliner isn't running, save your deadline to start it");
+
+    #[cfg(unix)]
+    let stream =
+        std::os::unix::net::UnixStream::connect(control_socket_path()).map_err(not_running)?;
+    #[cfg(not(unix))]
+    let stream =
+        std::net::TcpStream::connect(("127.0.0.1", control_port())).map_err(not_running)?;
+
+    writeln!(&stream, "{}", command.trim()).map_err(|e| e.to_string())?;
+
+    let mut response = String::new();
+    BufReader::new(&stream)
+        .read_line(&mut response)
+        .map_err(|e| e.to_string())?;
+
+    Ok(response.trim().to_string())
+}
+
+/// Answers every command of a connection, used by the schedular's control listener.
+pub fn serve_control_connection(stream: impl std::io::Read + Write) {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+
+    while matches!(reader.read_line(&mut line), Ok(read) if read > 0) {
+        let response = handle_control_command(&line);
+        line.clear();
+
+        if writeln!(reader.get_mut(), "{}", response).is_err() {
+            return;
+        }
+    }
+}
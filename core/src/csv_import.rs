@@ -0,0 +1,135 @@
+use std::fs;
+
+use chrono::{Local, NaiveDateTime, NaiveTime};
+
+use crate::{parse_natural_date, unwrap_or_return, CalendarEvent};
+
+/// Columns a header can name for the title, the date and the tag of a deadline.
+const NAME_HEADERS: &[&str] = &["name", "title", "summary", "deadline", "task"];
+const DATE_HEADERS: &[&str] = &["datetime", "date", "due", "due date", "end", "when"];
+const TAG_HEADERS: &[&str] = &["tag", "category", "label", "course", "project"];
+
+/// Reads deadlines from a spreadsheet exported as CSV, see `parse_csv`.
+pub fn load_csv(path: &str) -> Result<Vec<CalendarEvent>, String> {
+    let csv = unwrap_or_return!(
+        fs::read_to_string(path.trim()),
+        "Couldn't read the CSV file!"
+    );
+
+    parse_csv(&csv)
+}
+
+/// Rows of `name, datetime, tag` (the tag is optional), Ex: "Essay,2022-08-26 17:30,English".
+/// A header row can put the columns in another order, dates without a time end the day
+/// and commas or semicolons (Excel in some languages) separate the columns.
+pub fn parse_csv(csv: &str) -> Result<Vec<CalendarEvent>, String> {
+    let csv = csv.trim_start_matches('\u{feff}');
+    let delimiter = match csv.lines().next() {
+        Some(first) if first.matches(';').count() > first.matches(',').count() => ';',
+        _ => ',',
+    };
+
+    let mut rows = parse_rows(csv, delimiter)
+        .into_iter()
+        .filter(|row| row.iter().any(|cell| !cell.trim().is_empty()));
+
+    let first = match rows.next() {
+        Some(first) => first,
+        None => return Err(String::from("The CSV file is empty!")),
+    };
+
+    let column = |row: &[String], names: &[&str]| {
+        row.iter()
+            .position(|cell| names.contains(&cell.trim().to_lowercase().as_str()))
+    };
+
+    // Without a header the first row is already a deadline
+    let (first_row, (name, date, tag)) =
+        match (column(&first, NAME_HEADERS), column(&first, DATE_HEADERS)) {
+            (Some(name), Some(date)) if parse_csv_date(&first[date]).is_none() => {
+                (None, (name, date, column(&first, TAG_HEADERS)))
+            }
+            _ => (Some(first), (0, 1, Some(2))),
+        };
+    let skipped_rows = if first_row.is_none() { 2 } else { 1 };
+
+    let mut events = Vec::new();
+
+    for (i, row) in first_row.into_iter().chain(rows).enumerate() {
+        let cell = |column: usize| row.get(column).map(|cell| cell.trim()).unwrap_or_default();
+
+        let end = match parse_csv_date(cell(date)) {
+            Some(end) => end,
+            None => {
+                return Err(format!(
+                    "Row {}: \"{}\" isn't a date, Ex: 2022-08-26 17:30",
+                    i + skipped_rows,
+                    cell(date)
+                ))
+            }
+        };
+
+        events.push(CalendarEvent {
+            summary: cell(name).to_string(),
+            start: end,
+            end,
+            timezone: String::new(),
+            categories: tag
+                .map(cell)
+                .filter(|tag| !tag.is_empty())
+                .map(|tag| vec![tag.to_string()])
+                .unwrap_or_default(),
+        });
+    }
+
+    Ok(events)
+}
+
+fn parse_csv_date(cell: &str) -> Option<NaiveDateTime> {
+    let cell = cell.trim();
+
+    for format in ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M:%S"] {
+        if let Ok(date) = NaiveDateTime::parse_from_str(cell, format) {
+            return Some(date);
+        }
+    }
+
+    // Also what can be typed in the date input, Ex: "2022-08-26 5pm" or "next friday"
+    let (date, time) = parse_natural_date(cell, Local::now().naive_local())?;
+
+    Some(date.and_time(time.unwrap_or_else(|| NaiveTime::from_hms(23, 59, 0))))
+}
+
+/// Splits the CSV into rows of cells, quoted cells can have the delimiter,
+/// line breaks and `""` for a quote in them.
+fn parse_rows(csv: &str, delimiter: char) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = csv.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                cell.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            c if c == delimiter && !quoted => row.push(std::mem::take(&mut cell)),
+            '\r' if !quoted => (),
+            '\n' if !quoted => {
+                row.push(std::mem::take(&mut cell));
+                rows.push(std::mem::take(&mut row));
+            }
+            c => cell.push(c),
+        }
+    }
+
+    if !cell.is_empty() || !row.is_empty() {
+        row.push(cell);
+        rows.push(row);
+    }
+
+    rows
+}
@@ -0,0 +1,59 @@
+use std::{fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{get_cache_dir, SanitizedConf, SanitizedDeadline};
+
+/// A command that's run once when a deadline is reached, Ex: an alarm, a script or a shutdown.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct DeadlineHookConf {
+    pub enabled: bool,
+    pub command: String,
+    pub args: Vec<String>,
+}
+
+impl DeadlineHookConf {
+    pub fn sanitized(&self) -> Result<DeadlineHookConf, String> {
+        let command = self.command.trim().to_string();
+
+        if self.enabled && command.is_empty() {
+            return Err(String::from("The command to run on deadline is empty!"));
+        }
+
+        Ok(DeadlineHookConf {
+            command,
+            ..self.clone()
+        })
+    }
+}
+
+/// Deadlines whose hook was already run, so a restarted schedular doesn't run it again.
+fn ran_hooks_path() -> PathBuf {
+    get_cache_dir().join("deadline_hooks.json")
+}
+
+fn hook_key(deadline: &SanitizedDeadline) -> String {
+    // Every occurrence of a recurring deadline runs the hook
+    format!("{} {}", deadline.title, deadline.date().to_rfc3339())
+}
+
+/// Whether the hook should run for the reached `deadline`, it's remembered as run right away.
+pub fn deadline_hook_due(conf: &SanitizedConf, deadline: &SanitizedDeadline) -> bool {
+    if !conf.deadline_hook.enabled {
+        return false;
+    }
+
+    let mut ran: Vec<String> = fs::read_to_string(ran_hooks_path())
+        .ok()
+        .and_then(|ran| serde_json::from_str(&ran).ok())
+        .unwrap_or_default();
+
+    if ran.contains(&hook_key(deadline)) {
+        return false;
+    }
+
+    ran.push(hook_key(deadline));
+    fs::write(ran_hooks_path(), serde_json::to_string(&ran).unwrap()).ok();
+
+    true
+}
@@ -0,0 +1,146 @@
+use std::{fs, path::Path};
+
+use chrono::{DateTime, TimeZone, Utc};
+use serde::Serialize;
+
+use crate::{parse_timezone, unwrap_or_return, SanitizedConf, SanitizedDeadline};
+
+/// A deadline with what happened to it so far, one row of the CSV or one object of the JSON.
+#[derive(Debug, Serialize)]
+pub struct DeadlineRecord {
+    pub name: String,
+    /// Wall clock time in `timezone`, Ex: "2022-08-26 17:30", what the CSV import reads back.
+    pub datetime: String,
+    /// IANA time zone name, empty for the local time zone.
+    pub timezone: String,
+    /// The exact instant in RFC 3339.
+    pub instant: String,
+    pub recurrence: String,
+    pub counts_up: bool,
+    pub locale: String,
+    pub template: String,
+    /// When the deadline was first saved in RFC 3339, empty if it's not known.
+    pub saved_at: String,
+    /// "upcoming", "over" or "counting up".
+    pub status: String,
+    /// Negative once it's over.
+    pub minutes_left: i64,
+}
+
+impl DeadlineRecord {
+    pub fn new(deadline: &SanitizedDeadline, now: DateTime<Utc>) -> DeadlineRecord {
+        let date = deadline.date_at(now);
+        let timezone = deadline.timezone.clone().unwrap_or_default();
+        let minutes_left = deadline.time_left_at(now).num_minutes();
+
+        let datetime = match parse_timezone(&timezone).ok().flatten() {
+            Some(tz) => date.with_timezone(&tz).format("%Y-%m-%d %H:%M").to_string(),
+            None => date
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string(),
+        };
+
+        let status = if deadline.counts_up {
+            "counting up"
+        } else if minutes_left <= 0 {
+            "over"
+        } else {
+            "upcoming"
+        };
+
+        DeadlineRecord {
+            name: deadline.title.clone(),
+            datetime,
+            timezone,
+            instant: date.to_rfc3339(),
+            recurrence: format!("{:?}", deadline.recurrence),
+            counts_up: deadline.counts_up,
+            locale: format!("{:?}", deadline.locale),
+            template: deadline.template.clone(),
+            saved_at: deadline
+                .started_at
+                .map(|saved_at| Utc.timestamp(saved_at, 0).to_rfc3339())
+                .unwrap_or_default(),
+            status: status.to_string(),
+            minutes_left,
+        }
+    }
+}
+
+/// Writes every saved deadline to `output`, as JSON when it ends with `.json` and as CSV otherwise.
+pub fn export_deadlines(conf: &SanitizedConf, output: &Path) -> Result<(), String> {
+    if conf.deadlines.is_empty() {
+        return Err(String::from("There are no deadlines to export!"));
+    }
+
+    let is_json = output
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+
+    let exported = if is_json {
+        deadlines_to_json(conf, Utc::now())
+    } else {
+        deadlines_to_csv(conf, Utc::now())
+    };
+
+    unwrap_or_return!(
+        fs::write(output, exported),
+        format!("Couldn't write {}", output.display())
+    );
+
+    Ok(())
+}
+
+pub fn deadlines_to_json(conf: &SanitizedConf, now: DateTime<Utc>) -> String {
+    let records: Vec<DeadlineRecord> = conf
+        .deadlines
+        .iter()
+        .map(|deadline| DeadlineRecord::new(deadline, now))
+        .collect();
+
+    serde_json::to_string_pretty(&records).unwrap()
+}
+
+/// The first columns are the ones the CSV import reads, so an export can be imported again.
+pub fn deadlines_to_csv(conf: &SanitizedConf, now: DateTime<Utc>) -> String {
+    let mut rows = vec![String::from(
+        "name,datetime,timezone,instant,recurrence,counts_up,locale,template,saved_at,status,minutes_left",
+    )];
+
+    for deadline in &conf.deadlines {
+        let record = DeadlineRecord::new(deadline, now);
+
+        let cells = [
+            record.name,
+            record.datetime,
+            record.timezone,
+            record.instant,
+            record.recurrence,
+            record.counts_up.to_string(),
+            record.locale,
+            record.template,
+            record.saved_at,
+            record.status,
+            record.minutes_left.to_string(),
+        ];
+
+        rows.push(
+            cells
+                .iter()
+                .map(|cell| escape_cell(cell))
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+    }
+
+    rows.join("\r\n") + "\r\n"
+}
+
+fn escape_cell(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
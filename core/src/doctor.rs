@@ -0,0 +1,120 @@
+use std::{env, fs};
+
+use image::{Rgb, RgbImage};
+use wallpaper::Mode;
+
+use crate::{
+    get_cache_dir, get_desktop_wallpaper, get_desktop_wallpaper_mode, set_desktop_wallpaper,
+    CheckReport, WaylandBackend,
+};
+
+/// Name of the desktop environment and the tool the wallpaper gets set with on it.
+pub fn detect_backend() -> (String, String) {
+    if cfg!(target_os = "windows") {
+        return (
+            String::from("Windows"),
+            String::from("SystemParametersInfo"),
+        );
+    }
+
+    if cfg!(target_os = "macos") {
+        return (String::from("macOS"), String::from("AppleScript"));
+    }
+
+    let desktop = env::var("XDG_CURRENT_DESKTOP").unwrap_or_default();
+    let session = if env::var("WAYLAND_DISPLAY").is_ok() {
+        "Wayland"
+    } else {
+        "X11"
+    };
+
+    let backend = match WaylandBackend::detect() {
+        Some(backend) => backend.name(),
+        None => match desktop.as_str() {
+            d if d.contains("GNOME") || d == "Unity" || d == "Pantheon" => "gsettings",
+            "KDE" => "qdbus (Plasma shell script)",
+            "X-Cinnamon" | "MATE" | "Deepin" => "dconf",
+            "XFCE" => "xfconf-query",
+            "LXDE" => "pcmanfm",
+            _ => "swaybg or feh",
+        },
+    };
+
+    let desktop = if desktop.is_empty() {
+        String::from("Unknown desktop")
+    } else {
+        desktop
+    };
+
+    (format!("{} ({})", desktop, session), backend.to_string())
+}
+
+/// Diagnoses setting the wallpaper by setting a test image and putting the current
+/// wallpaper back right after, used by `deadliner doctor`.
+pub fn run_doctor() -> CheckReport {
+    let mut report = CheckReport::default();
+
+    let (desktop, backend) = detect_backend();
+    report.pass(&format!("Desktop: {}", desktop));
+    report.pass(&format!("Backend: {}", backend));
+
+    let current = match get_desktop_wallpaper() {
+        Ok(current) => {
+            report.pass(&format!("Read the current wallpaper: {}", current));
+            current
+        }
+        Err(err) => {
+            report.warn(
+                &format!("Couldn't read the current wallpaper: {}", err),
+                "The original wallpaper can't be restored later, set XDG_CURRENT_DESKTOP if it's missing",
+            );
+            // A test image that can't be swapped back would be left as the wallpaper
+            report.warn(
+                "Skipped setting a test wallpaper",
+                "Fix reading the current wallpaper first so it can be put back",
+            );
+            return report;
+        }
+    };
+
+    let current_mode = get_desktop_wallpaper_mode().unwrap_or(Mode::Crop);
+
+    let test_image = get_cache_dir().join("doctor.png");
+    let test_image = test_image.to_str().unwrap().to_owned();
+
+    if RgbImage::from_pixel(64, 64, Rgb([11, 16, 26]))
+        .save(&test_image)
+        .is_err()
+    {
+        report.error(
+            "Couldn't write a test image to the cache dir",
+            &format!("Make sure {} is writable", get_cache_dir().display()),
+        );
+        return report;
+    }
+
+    match set_desktop_wallpaper(&test_image, Mode::Crop) {
+        Ok(_) => report.pass(&format!("Set a test wallpaper with {}", backend)),
+        Err(err) => report.error(
+            &format!("Couldn't set the wallpaper with {}: {}", backend, err),
+            &format!(
+                "Make sure {} is installed and works in your session",
+                backend
+            ),
+        ),
+    }
+
+    // Keep the test image around if it can't be swapped back, it might still be the wallpaper
+    match set_desktop_wallpaper(&current, current_mode) {
+        Ok(_) => {
+            report.pass("Restored the current wallpaper");
+            fs::remove_file(&test_image).ok();
+        }
+        Err(err) => report.error(
+            &format!("Couldn't restore the current wallpaper: {}", err),
+            &format!("Set {} as your wallpaper manually", current),
+        ),
+    }
+
+    report
+}
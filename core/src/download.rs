@@ -0,0 +1,155 @@
+use std::{
+    error::Error,
+    fmt,
+    io::{self, Read, Write},
+    sync::Mutex,
+    thread,
+    time::Duration,
+};
+
+use reqwest::blocking::{Client, RequestBuilder, Response};
+
+use crate::{print_download_progress, record_response, take_request, trace};
+
+/// Attempts of a download before giving up on it.
+const ATTEMPTS: u32 = 3;
+
+/// The download that's currently in flight.
+#[derive(Debug, Clone)]
+pub struct DownloadProgress {
+    pub url: String,
+    pub downloaded: u64,
+    /// Unknown when the server doesn't send a content length.
+    pub total: Option<u64>,
+}
+
+impl std::fmt::Display for DownloadProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let downloaded = self.downloaded / 1024;
+
+        match self.total {
+            Some(total) if total > 0 => write!(
+                f,
+                "Downloading {}… {}% ({} KB)",
+                self.url,
+                self.downloaded * 100 / total,
+                downloaded
+            ),
+            _ => write!(f, "Downloading {}… {} KB", self.url, downloaded),
+        }
+    }
+}
+
+static PROGRESS: Mutex<Option<DownloadProgress>> = Mutex::new(None);
+
+pub fn download_progress() -> Option<DownloadProgress> {
+    PROGRESS.lock().unwrap().clone()
+}
+
+/// Every request gives up after `timeout_secs` instead of hanging on a dead URL.
+pub fn http_client(timeout_secs: u32) -> Client {
+    Client::builder()
+        .timeout(Duration::from_secs(timeout_secs.max(1) as u64))
+        .build()
+        .unwrap_or_default()
+}
+
+#[derive(Debug)]
+pub enum SendError {
+    Http(reqwest::Error),
+    /// The request wasn't sent to stay within the provider's rate limit.
+    RateLimited(String),
+}
+
+impl fmt::Display for SendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SendError::Http(e) => write!(f, "{}", e),
+            SendError::RateLimited(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl Error for SendError {}
+
+impl From<reqwest::Error> for SendError {
+    fn from(e: reqwest::Error) -> Self {
+        SendError::Http(e)
+    }
+}
+
+/// Sends the request, failed attempts are retried after waiting a bit longer every time.
+/// Every attempt counts towards the provider's rate limit, see `take_request`.
+pub fn send_with_retry(request: impl Fn() -> RequestBuilder) -> Result<Response, SendError> {
+    let url = request().build().ok().map(|request| request.url().clone());
+    let mut attempt = 1;
+
+    loop {
+        if let Some(url) = &url {
+            take_request(url).map_err(SendError::RateLimited)?;
+        }
+
+        let result = request().send().and_then(|res| {
+            if let Some(url) = &url {
+                record_response(url, &res);
+            }
+
+            res.error_for_status()
+        });
+
+        match result {
+            // Client errors (Ex: 404) won't get better by retrying
+            Err(e) if attempt < ATTEMPTS && !e.status().is_some_and(|s| s.is_client_error()) => {
+                let backoff = Duration::from_secs(2u64.pow(attempt - 1));
+
+                trace!(
+                    "attempt {} failed ({}), retrying in {:?}",
+                    attempt,
+                    e,
+                    backoff
+                );
+
+                thread::sleep(backoff);
+                attempt += 1;
+            }
+            result => return result.map_err(SendError::Http),
+        }
+    }
+}
+
+/// Copies the response body to `writer` while keeping track of the progress.
+pub fn copy_with_progress(
+    url: &str,
+    mut response: Response,
+    mut writer: impl Write,
+) -> io::Result<u64> {
+    let mut progress = DownloadProgress {
+        url: url.to_string(),
+        downloaded: 0,
+        total: response.content_length(),
+    };
+
+    let mut buffer = [0; 64 * 1024];
+
+    let result = loop {
+        *PROGRESS.lock().unwrap() = Some(progress.clone());
+        print_download_progress(&progress, false);
+
+        match response.read(&mut buffer) {
+            Ok(0) => break writer.flush().map(|_| progress.downloaded),
+            Ok(read) => {
+                if let Err(e) = writer.write_all(&buffer[..read]) {
+                    break Err(e);
+                }
+                progress.downloaded += read as u64;
+            }
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => break Err(e),
+        }
+    };
+
+    *PROGRESS.lock().unwrap() = None;
+    print_download_progress(&progress, true);
+
+    result
+}
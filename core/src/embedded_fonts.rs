@@ -0,0 +1,21 @@
+use crate::Font;
+
+/// The bundled fonts are compiled in so rendering and the GUI work
+/// wherever deadliner is launched from, even without the assets directory.
+pub const POPPINS_BLACK: &[u8] = include_bytes!("../assets/fonts/PoppinsBlack.ttf");
+pub const POPPINS_MEDIUM: &[u8] = include_bytes!("../assets/fonts/PoppinsMedium.ttf");
+pub const POPPINS_REGULAR: &[u8] = include_bytes!("../assets/fonts/PoppinsRegular.ttf");
+pub const POPPINS_LIGHT: &[u8] = include_bytes!("../assets/fonts/PoppinsLight.ttf");
+pub const EMOJI_FONT: &[u8] = include_bytes!("../assets/fonts/EmojiFont.ttf");
+pub const NOTO_EMOJI_REGULAR: &[u8] = include_bytes!("../assets/fonts/NotoEmojiRegular.ttf");
+
+/// The compiled in data of a bundled font, `None` for a custom one.
+pub fn embedded_font(font: Font) -> Option<&'static [u8]> {
+    match font {
+        Font::PoppinsBlack => Some(POPPINS_BLACK),
+        Font::PoppinsMedium => Some(POPPINS_MEDIUM),
+        Font::PoppinsRegular => Some(POPPINS_REGULAR),
+        Font::PoppinsLight => Some(POPPINS_LIGHT),
+        Font::ChooseFromDisk => None,
+    }
+}
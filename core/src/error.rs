@@ -0,0 +1,105 @@
+use std::{error::Error as StdError, io};
+
+use image::ImageError;
+use thiserror::Error;
+
+use crate::SendError;
+
+/// Why saving or refreshing the wallpaper failed, the GUI explains each kind differently.
+#[derive(Debug, Error)]
+pub enum DeadlinerError {
+    /// A file couldn't be read or written, Ex: the config or the rendered wallpaper.
+    #[error("{msg}")]
+    Io {
+        msg: String,
+        #[source]
+        source: Option<io::Error>,
+    },
+    /// A download failed, timed out or was held back by the rate limit.
+    #[error("{msg}")]
+    Network {
+        msg: String,
+        #[source]
+        source: Option<reqwest::Error>,
+    },
+    /// An image couldn't be decoded, Ex: a corrupt file or an unsupported format.
+    #[error("{msg}")]
+    Decode {
+        msg: String,
+        #[source]
+        source: ImageError,
+    },
+    /// An input or a preset couldn't be understood.
+    #[error("{0}")]
+    Parse(String),
+    /// The text or the background couldn't be drawn.
+    #[error("{0}")]
+    Render(String),
+    /// The OS (or another output) refused the rendered wallpaper.
+    #[error("{0}")]
+    WallpaperBackend(String),
+}
+
+impl DeadlinerError {
+    pub(crate) fn io(msg: impl Into<String>, source: io::Error) -> DeadlinerError {
+        Self::Io {
+            msg: msg.into(),
+            source: Some(source),
+        }
+    }
+
+    pub(crate) fn decode(msg: impl Into<String>, source: ImageError) -> DeadlinerError {
+        Self::Decode {
+            msg: msg.into(),
+            source,
+        }
+    }
+
+    /// A failed download, classified by what actually failed:
+    /// writing the image to the cache is a file error, the rest are network errors.
+    pub(crate) fn download(msg: impl Into<String>, error: Box<dyn StdError>) -> DeadlinerError {
+        let msg = msg.into();
+
+        let error = match error.downcast::<io::Error>() {
+            Ok(source) => return Self::io(msg, *source),
+            Err(error) => error,
+        };
+
+        let source = match error.downcast::<SendError>() {
+            Ok(error) => match *error {
+                SendError::Http(source) => Some(source),
+                SendError::RateLimited(_) => None,
+            },
+            Err(error) => error
+                .downcast::<reqwest::Error>()
+                .ok()
+                .map(|source| *source),
+        };
+
+        Self::Network { msg, source }
+    }
+
+    /// The error with a hint of what to try next, shown in the GUI.
+    pub fn user_message(&self) -> String {
+        match self {
+            Self::Io { msg, .. } => format!(
+                "{}\nCheck that deadliner can write to its directories.",
+                msg
+            ),
+            Self::Network { msg, .. } => format!("{}\nCheck your connection and try again.", msg),
+            Self::Decode { msg, .. } => format!("{}\nTry another image.", msg),
+            Self::Parse(msg) => msg.clone(),
+            Self::Render(msg) => format!("{}\nTry a smaller font size or another background.", msg),
+            Self::WallpaperBackend(msg) => format!(
+                "{}\nYour desktop didn't accept the wallpaper, try another output or mode.",
+                msg
+            ),
+        }
+    }
+}
+
+impl From<DeadlinerError> for String {
+    fn from(error: DeadlinerError) -> String {
+        error.to_string()
+    }
+}
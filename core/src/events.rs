@@ -0,0 +1,81 @@
+use std::sync::{
+    mpsc::{self, Receiver, Sender},
+    Mutex,
+};
+
+use crate::{SanitizedConf, SanitizedDeadline};
+
+/// Something that happened while keeping the wallpaper up to date, integrations
+/// (notifications, hooks, webhooks, the GUI) subscribe to these instead of being called directly.
+#[derive(Debug, Clone)]
+pub enum DeadlinerEvent {
+    RefreshStarted,
+    /// The wallpaper is rendered and about to be handed to the outputs.
+    RenderCompleted,
+    RefreshFailed(String),
+    /// The deadline reached a milestone, 0 is when it's over.
+    MilestoneCrossed {
+        deadline: SanitizedDeadline,
+        minutes: u32,
+    },
+    DeadlinePassed {
+        deadline: SanitizedDeadline,
+    },
+    /// New inputs were saved from inside the schedular, Ex: by a control command,
+    /// its updates are scheduled again for them.
+    InputsSaved,
+}
+
+static SUBSCRIBERS: Mutex<Vec<Sender<DeadlinerEvent>>> = Mutex::new(Vec::new());
+
+/// Every event published from now on is received on the returned channel.
+pub fn subscribe() -> Receiver<DeadlinerEvent> {
+    let (sender, receiver) = mpsc::channel();
+
+    SUBSCRIBERS.lock().unwrap().push(sender);
+
+    receiver
+}
+
+pub fn publish(event: DeadlinerEvent) {
+    // Subscribers that dropped their receiver are forgotten
+    SUBSCRIBERS
+        .lock()
+        .unwrap()
+        .retain(|subscriber| subscriber.send(event.clone()).is_ok());
+}
+
+/// The milestones any of the integrations is interested in.
+fn all_milestones(conf: &SanitizedConf) -> Vec<u32> {
+    let mut milestones = vec![0];
+
+    milestones.extend(&conf.notifications.milestones);
+    milestones.extend(&conf.announcements.thresholds);
+    milestones.extend(&conf.sound_alerts.milestones);
+    milestones.extend(&conf.webhooks.milestones);
+
+    milestones
+}
+
+/// Events for the deadlines that reach a milestone this minute.
+pub fn milestone_events(conf: &SanitizedConf) -> Vec<DeadlinerEvent> {
+    let milestones = all_milestones(conf);
+    let mut events = Vec::new();
+
+    for deadline in &conf.deadlines {
+        if let Some(minutes) = deadline.reached_milestone(&milestones) {
+            events.push(DeadlinerEvent::MilestoneCrossed {
+                deadline: deadline.clone(),
+                minutes: minutes as u32,
+            });
+
+            if minutes == 0 {
+                events.push(DeadlinerEvent::DeadlinePassed {
+                    deadline: deadline.clone(),
+                });
+            }
+        }
+    }
+
+    events
+}
@@ -0,0 +1,92 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Pomodoro style focus timer, the wallpaper counts down a work interval
+/// then a break one over and over instead of the deadlines.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct FocusConf {
+    pub enabled: bool,
+    pub work_minutes: u32,
+    pub break_minutes: u32,
+    /// Unix timestamp of when the first work interval started, set when saving.
+    #[serde(default)]
+    pub started_at: Option<i64>,
+}
+
+impl Default for FocusConf {
+    fn default() -> Self {
+        FocusConf {
+            enabled: false,
+            work_minutes: 50,
+            break_minutes: 10,
+            started_at: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FocusPhase {
+    Work,
+    Break,
+}
+
+impl std::fmt::Display for FocusPhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Work => "Focus",
+                Self::Break => "Break",
+            }
+        )
+    }
+}
+
+impl FocusConf {
+    /// Keeps the cycle going from the last save when the intervals didn't change,
+    /// otherwise starts a new work interval now.
+    pub fn sanitized(&self, saved: Option<&FocusConf>) -> Result<FocusConf, String> {
+        if self.enabled && self.work_minutes == 0 {
+            return Err(String::from(
+                "The focus interval must be at least a minute!",
+            ));
+        }
+
+        let started_at = saved
+            .filter(|saved| {
+                saved.enabled
+                    && saved.work_minutes == self.work_minutes
+                    && saved.break_minutes == self.break_minutes
+            })
+            .and_then(|saved| saved.started_at)
+            .or(Some(Utc::now().timestamp()));
+
+        Ok(FocusConf {
+            started_at: started_at.filter(|_| self.enabled),
+            ..*self
+        })
+    }
+
+    /// The interval that's running at `now` and the time left in it.
+    pub fn phase_at(&self, now: DateTime<Utc>) -> (FocusPhase, Duration) {
+        let work = self.work_minutes as i64 * 60;
+        let cycle = work + self.break_minutes as i64 * 60;
+
+        let started_at = self.started_at.unwrap_or_else(|| now.timestamp());
+        let elapsed = (now.timestamp() - started_at).rem_euclid(cycle.max(1));
+
+        if elapsed < work {
+            (FocusPhase::Work, Duration::seconds(work - elapsed))
+        } else {
+            (FocusPhase::Break, Duration::seconds(cycle - elapsed))
+        }
+    }
+
+    /// Ex: "42 min", the minute that's running counts as a whole one.
+    pub fn countdown_at(&self, now: DateTime<Utc>) -> String {
+        let (_, left) = self.phase_at(now);
+
+        format!("{} min", (left.num_seconds() + 59) / 60)
+    }
+}
@@ -0,0 +1,107 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::SystemTime,
+};
+
+use text_to_png::TextRenderer;
+
+use crate::{embedded_font, font_path, trace, unwrap_or_return, Font, SanitizedConf};
+
+/// The font a renderer was parsed from, it's parsed again once the file changes.
+#[derive(PartialEq)]
+struct FontKey {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+/// The schedular renders with the same font every refresh,
+/// so the parsed font is kept around instead of reading the TTF from disk every time.
+static RENDERER: Mutex<Option<(FontKey, TextRenderer)>> = Mutex::new(None);
+
+/// A text renderer for the picked font. The files on disk are preferred so the assets can be
+/// replaced, a bundled font falls back to its compiled in copy and a custom font that's
+/// missing falls back to the default bundled font instead of failing the refresh.
+pub fn font_renderer(conf: &SanitizedConf) -> Result<TextRenderer, String> {
+    let path = font_path(conf);
+
+    if path.is_file() {
+        return cached_text_renderer(&path);
+    }
+
+    let font = match embedded_font(conf.font) {
+        Some(_) => conf.font,
+        None => {
+            trace!(
+                "the custom font {} is missing, using the bundled one",
+                path.display()
+            );
+
+            Font::PoppinsBlack
+        }
+    };
+
+    embedded_text_renderer(font)
+}
+
+fn embedded_text_renderer(font: Font) -> Result<TextRenderer, String> {
+    let font_data = embedded_font(font).unwrap_or_default();
+    let key = FontKey {
+        path: PathBuf::from(format!("<embedded {:?}>", font)),
+        modified: None,
+        len: font_data.len() as u64,
+    };
+
+    let mut cached = RENDERER.lock().unwrap();
+
+    if let Some((cached_key, renderer)) = cached.as_ref() {
+        if *cached_key == key {
+            return Ok(renderer.clone());
+        }
+    }
+
+    trace!("parsing the embedded font {:?}", font);
+
+    let renderer = unwrap_or_return!(
+        TextRenderer::try_new_with_ttf_font_data(font_data),
+        "Couldn't parse the font file!"
+    );
+
+    *cached = Some((key, renderer.clone()));
+
+    Ok(renderer)
+}
+
+/// A text renderer for the font at `path`, parsed only when it's not cached yet.
+pub fn cached_text_renderer(path: &Path) -> Result<TextRenderer, String> {
+    let metadata = unwrap_or_return!(fs::metadata(path), "Couldn't read the font file!");
+    let key = FontKey {
+        path: path.to_path_buf(),
+        modified: metadata.modified().ok(),
+        len: metadata.len(),
+    };
+
+    let mut cached = RENDERER.lock().unwrap();
+
+    if let Some((cached_key, renderer)) = cached.as_ref() {
+        if *cached_key == key {
+            trace!("using the cached font {}", path.display());
+
+            return Ok(renderer.clone());
+        }
+    }
+
+    trace!("parsing the font {}", path.display());
+
+    let font_data = unwrap_or_return!(fs::read(path), "Couldn't read the font file!");
+    let renderer = unwrap_or_return!(
+        TextRenderer::try_new_with_ttf_font_data(font_data),
+        "Couldn't parse the font file!"
+    );
+
+    *cached = Some((key, renderer.clone()));
+
+    Ok(renderer)
+}
@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+
+use crate::{Locale, TimeUnit};
+
+/// How the remaining time is phrased on the wallpaper.
+#[derive(Debug, PartialEq, Clone, Copy, EnumIter, Serialize, Deserialize, Default)]
+pub enum CountdownStyle {
+    /// "2 Weeks, 3 Days, 4 Hours"
+    #[default]
+    Exact,
+    /// "About 3 Weeks", "Just over 1 Day", for people who find exact numbers stressful.
+    Fuzzy,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum FuzzyQualifier {
+    About,
+    JustOver,
+    Almost,
+    LessThan,
+}
+
+/// Phrases `minutes` approximately using the biggest unit that still reads naturally.
+///
+/// Rounding rules:
+/// - less than 15% past a whole unit is "Just over" that unit.
+/// - more than 85% towards the next unit is "Almost" the next unit.
+/// - anything else is "About" the nearest whole unit.
+pub fn fuzzy_countdown(locale: Locale, minutes: i64) -> String {
+    let minutes = minutes.max(0);
+
+    if minutes < 1 {
+        return format!(
+            "{} {}",
+            locale.fuzzy_qualifier(FuzzyQualifier::LessThan),
+            locale.format_unit(TimeUnit::Minute, 1)
+        );
+    }
+
+    let (unit, unit_minutes) = match minutes {
+        m if m < 60 => (TimeUnit::Minute, 1),
+        m if m < 24 * 60 => (TimeUnit::Hour, 60),
+        m if m < 14 * 24 * 60 => (TimeUnit::Day, 24 * 60),
+        m if m < 60 * 24 * 60 => (TimeUnit::Week, 7 * 24 * 60),
+        _ => (TimeUnit::Month, 30 * 24 * 60),
+    };
+
+    let value = minutes as f64 / unit_minutes as f64;
+    let whole = value.floor();
+    let fraction = value - whole;
+
+    let (qualifier, count) = if unit == TimeUnit::Minute {
+        (FuzzyQualifier::About, minutes)
+    } else if fraction < 0.15 {
+        (FuzzyQualifier::JustOver, whole as i64)
+    } else if fraction > 0.85 {
+        (FuzzyQualifier::Almost, whole as i64 + 1)
+    } else {
+        (FuzzyQualifier::About, value.round() as i64)
+    };
+
+    format!(
+        "{} {}",
+        locale.fuzzy_qualifier(qualifier),
+        locale.format_unit(unit, count)
+    )
+}
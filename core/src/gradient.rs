@@ -0,0 +1,51 @@
+use image::{Rgb, RgbImage};
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+
+#[derive(Debug, PartialEq, Clone, Copy, EnumIter, Serialize, Deserialize, Default)]
+pub enum GradientDirection {
+    /// From left to right.
+    Horizontal,
+    /// From top to bottom.
+    #[default]
+    Vertical,
+    /// From the top-left corner to the bottom-right corner.
+    Diagonal,
+    /// From the center to the corners.
+    Radial,
+}
+
+/// Renders a gradient from `start` to `end` at the wallpaper's resolution.
+pub fn render_gradient(
+    width: u32,
+    height: u32,
+    start: [u8; 3],
+    end: [u8; 3],
+    direction: GradientDirection,
+) -> RgbImage {
+    let (w, h) = ((width.max(2) - 1) as f32, (height.max(2) - 1) as f32);
+    let (center_x, center_y) = (w / 2., h / 2.);
+    let radius = (center_x * center_x + center_y * center_y).sqrt();
+
+    RgbImage::from_fn(width, height, |x, y| {
+        let (x, y) = (x as f32, y as f32);
+
+        let t = match direction {
+            GradientDirection::Horizontal => x / w,
+            GradientDirection::Vertical => y / h,
+            GradientDirection::Diagonal => (x / w + y / h) / 2.,
+            GradientDirection::Radial => {
+                ((x - center_x).powi(2) + (y - center_y).powi(2)).sqrt() / radius
+            }
+        };
+
+        let mut color = [0; 3];
+        for (i, channel) in color.iter_mut().enumerate() {
+            let (from, to) = (start[i] as f32, end[i] as f32);
+
+            *channel = (from + (to - from) * t).round() as u8;
+        }
+
+        Rgb(color)
+    })
+}
@@ -0,0 +1,56 @@
+use std::{
+    env,
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use image::DynamicImage;
+use wallpaper::Mode;
+
+use crate::{encode_png, swww_resize, trace, unwrap_or_return, PngCompression};
+
+/// Whether the rendered wallpaper can be handed to the setter without writing it to disk,
+/// only swww's daemon on Wayland takes the image over stdin.
+pub fn in_memory_setter_available() -> bool {
+    cfg!(target_os = "linux")
+        && env::var("WAYLAND_DISPLAY").is_ok()
+        && Command::new("swww")
+            .arg("query")
+            .output()
+            .is_ok_and(|output| output.status.success())
+}
+
+/// Pipes the encoded wallpaper straight into `swww img -`.
+pub fn set_wallpaper_from_memory(
+    wallpaper: &DynamicImage,
+    mode: Mode,
+    compression: PngCompression,
+) -> Result<(), String> {
+    let resize = swww_resize(mode);
+
+    trace!(
+        "handing the wallpaper to swww in memory (resize: {})",
+        resize
+    );
+
+    let mut swww = unwrap_or_return!(
+        Command::new("swww")
+            .args(["img", "--resize", resize, "--transition-type", "none", "-"])
+            .stdin(Stdio::piped())
+            .spawn(),
+        "Couldn't run swww!"
+    );
+
+    let mut png = Vec::new();
+    encode_png(wallpaper, compression, &mut png)?;
+
+    unwrap_or_return!(
+        swww.stdin.take().unwrap().write_all(&png),
+        "Couldn't hand the wallpaper to swww!"
+    );
+
+    match swww.wait() {
+        Ok(status) if status.success() => Ok(()),
+        _ => Err(String::from("swww couldn't set the wallpaper!")),
+    }
+}
@@ -0,0 +1,130 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::PathBuf,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+use crate::{get_cache_dir, http_client, send_with_retry, trace};
+
+/// Holidays are downloaded from https://date.nager.at, a free API without a key.
+const HOLIDAYS_URL: &str = "https://date.nager.at/api/v3/PublicHolidays";
+const HOLIDAYS_TIMEOUT_SECS: u32 = 10;
+
+#[derive(Deserialize)]
+struct NagerHoliday {
+    date: String,
+    /// Holidays of only some of the country's regions aren't global.
+    #[serde(default = "default_global")]
+    global: bool,
+}
+
+fn default_global() -> bool {
+    true
+}
+
+/// A country's ISO code and a year.
+type HolidaysKey = (String, i32);
+
+/// Holidays already read in this run.
+static HOLIDAYS: Mutex<Option<HashMap<HolidaysKey, Vec<NaiveDate>>>> = Mutex::new(None);
+/// When downloading them last failed, Ex: the schedular started before the network was up.
+static FAILED_DOWNLOADS: Mutex<Option<HashMap<HolidaysKey, Instant>>> = Mutex::new(None);
+
+/// A failed download is tried again after this long instead of on every render.
+const RETRY_AFTER: Duration = Duration::from_secs(15 * 60);
+
+fn holidays_path(country: &str, year: i32) -> PathBuf {
+    get_cache_dir()
+        .join("holidays")
+        .join(format!("{}-{}.json", country, year))
+}
+
+/// Nationwide public holidays of `country` (ISO 3166 code, Ex: "DE") in `year`.
+/// They're cached on disk once downloaded, while the download keeps failing
+/// there are no holidays and it's retried every 15 minutes.
+pub fn public_holidays(country: &str, year: i32) -> Vec<NaiveDate> {
+    let key = (country.to_string(), year);
+
+    if let Some(holidays) = HOLIDAYS
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .get(&key)
+    {
+        return holidays.clone();
+    }
+
+    let recently_failed = FAILED_DOWNLOADS
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .get(&key)
+        .is_some_and(|failed_at| failed_at.elapsed() < RETRY_AFTER);
+
+    if recently_failed {
+        return Vec::new();
+    }
+
+    let cached = fs::read_to_string(holidays_path(country, year))
+        .ok()
+        .and_then(|dates| serde_json::from_str::<Vec<String>>(&dates).ok());
+
+    // Not holding the locks while downloading, the renders of other threads would wait on it
+    let dates = match cached {
+        Some(dates) => dates,
+        None => match download_holidays(country, year) {
+            Ok(dates) => dates,
+            Err(msg) => {
+                trace!("no holidays for {} in {} for now: {}", country, year, msg);
+
+                FAILED_DOWNLOADS
+                    .lock()
+                    .unwrap()
+                    .get_or_insert_with(HashMap::new)
+                    .insert(key, Instant::now());
+                return Vec::new();
+            }
+        },
+    };
+
+    let holidays: Vec<NaiveDate> = dates
+        .iter()
+        .filter_map(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+        .collect();
+
+    HOLIDAYS
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .insert(key, holidays.clone());
+
+    holidays
+}
+
+fn download_holidays(country: &str, year: i32) -> Result<Vec<String>, String> {
+    let client = http_client(HOLIDAYS_TIMEOUT_SECS);
+    let url = format!("{}/{}/{}", HOLIDAYS_URL, year, country);
+
+    let response = send_with_retry(|| client.get(&url)).map_err(|e| e.to_string())?;
+    let body = response.text().map_err(|e| e.to_string())?;
+    let holidays: Vec<NagerHoliday> = serde_json::from_str(&body).map_err(|e| e.to_string())?;
+
+    let dates: Vec<String> = holidays
+        .into_iter()
+        .filter(|holiday| holiday.global)
+        .map(|holiday| holiday.date)
+        .collect();
+
+    let path = holidays_path(country, year);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).ok();
+    }
+    fs::write(path, serde_json::to_string(&dates).unwrap()).ok();
+
+    Ok(dates)
+}
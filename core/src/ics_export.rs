@@ -0,0 +1,103 @@
+use std::{fs, path::Path};
+
+use chrono::{DateTime, Utc};
+
+use crate::{unique_hash, unwrap_or_return, SanitizedConf};
+
+const ICS_DATE_TIME: &str = "%Y%m%dT%H%M%SZ";
+
+/// Writes the deadlines as an iCalendar file for Google Calendar, Outlook, ...
+pub fn export_ics(conf: &SanitizedConf, output: &Path) -> Result<(), String> {
+    if conf.deadlines.is_empty() {
+        return Err(String::from("There are no deadlines to export!"));
+    }
+
+    unwrap_or_return!(
+        fs::write(output, deadlines_to_ics(conf, Utc::now())),
+        format!("Couldn't write {}", output.display())
+    );
+
+    Ok(())
+}
+
+/// Every deadline is an event at its instant with a reminder at each notification milestone.
+pub fn deadlines_to_ics(conf: &SanitizedConf, now: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        String::from("BEGIN:VCALENDAR"),
+        String::from("VERSION:2.0"),
+        String::from("PRODID:-//Deadliner//Deadliner//EN"),
+        String::from("CALSCALE:GREGORIAN"),
+    ];
+
+    for deadline in &conf.deadlines {
+        let date = deadline.date().format(ICS_DATE_TIME).to_string();
+        let summary = if deadline.title.is_empty() {
+            String::from("Deadline")
+        } else {
+            deadline.title.clone()
+        };
+
+        lines.extend([
+            String::from("BEGIN:VEVENT"),
+            // Stable so importing the file again updates the events instead of duplicating them
+            format!(
+                "UID:{}@deadliner",
+                unique_hash(&format!("{} {}", deadline.title, date))
+            ),
+            format!("DTSTAMP:{}", now.format(ICS_DATE_TIME)),
+            format!("DTSTART:{}", date),
+            format!("DTEND:{}", date),
+            format!("SUMMARY:{}", escape_text(&summary)),
+        ]);
+
+        if let Some(rule) = deadline.recurrence.ics_rule() {
+            lines.push(format!("RRULE:{}", rule));
+        }
+
+        for minutes in &conf.notifications.milestones {
+            lines.extend([
+                String::from("BEGIN:VALARM"),
+                String::from("ACTION:DISPLAY"),
+                format!("DESCRIPTION:{}", escape_text(&summary)),
+                format!("TRIGGER:-PT{}M", minutes),
+                String::from("END:VALARM"),
+            ]);
+        }
+
+        lines.push(String::from("END:VEVENT"));
+    }
+
+    lines.push(String::from("END:VCALENDAR"));
+
+    lines
+        .iter()
+        .map(|line| fold_line(line))
+        .collect::<Vec<_>>()
+        .join("\r\n")
+        + "\r\n"
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Lines longer than 75 bytes are folded by continuing them on the next line after a space.
+fn fold_line(line: &str) -> String {
+    let mut folded = String::new();
+    let mut line_len = 0;
+
+    for c in line.chars() {
+        if line_len + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            line_len = 1;
+        }
+
+        folded.push(c);
+        line_len += c.len_utf8();
+    }
+
+    folded
+}
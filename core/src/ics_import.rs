@@ -0,0 +1,201 @@
+use std::fs;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+
+use crate::{
+    http_client, parse_timezone, resolve_deadline, send_with_retry, unwrap_or_return, Deadline,
+};
+
+/// A VEVENT read from an iCalendar file, it becomes a deadline at its end.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarEvent {
+    pub summary: String,
+    /// Wall clock time in `timezone`, the same as `end` for events without a duration.
+    pub start: NaiveDateTime,
+    /// Wall clock time in `timezone`.
+    pub end: NaiveDateTime,
+    /// IANA time zone name, "UTC" for times ending with "Z", empty for floating local times.
+    pub timezone: String,
+    /// Ex: "Exams" from "CATEGORIES:Exams,University".
+    pub categories: Vec<String>,
+}
+
+impl CalendarEvent {
+    pub fn start_instant(&self) -> DateTime<Utc> {
+        resolve_deadline(self.start, parse_timezone(&self.timezone).ok().flatten())
+    }
+
+    pub fn end_instant(&self) -> DateTime<Utc> {
+        resolve_deadline(self.end, parse_timezone(&self.timezone).ok().flatten())
+    }
+
+    pub fn to_deadline(&self, use_24_hour: bool) -> Deadline {
+        let mut deadline = Deadline {
+            title: self.summary.clone(),
+            timezone: self.timezone.clone(),
+            tag: self.categories.first().cloned().unwrap_or_default(),
+            ..Deadline::default()
+        };
+
+        deadline.set_date_time(self.end, use_24_hour);
+
+        deadline
+    }
+}
+
+/// Reads the events of an `.ics` file or of a calendar subscription URL
+/// (Ex: "webcal://calendar.example.com/exams.ics").
+pub fn load_calendar(source: &str, timeout_secs: u32) -> Result<Vec<CalendarEvent>, String> {
+    let source = source.trim();
+
+    let ics = if source.starts_with("http://")
+        || source.starts_with("https://")
+        || source.starts_with("webcal://")
+    {
+        let url = source.replacen("webcal://", "https://", 1);
+        let client = http_client(timeout_secs);
+
+        let response = unwrap_or_return!(
+            send_with_retry(|| client.get(&url)),
+            "Couldn't download the calendar!"
+        );
+
+        unwrap_or_return!(response.text(), "Couldn't download the calendar!")
+    } else {
+        unwrap_or_return!(
+            fs::read_to_string(source),
+            "Couldn't read the calendar file!"
+        )
+    };
+
+    parse_ics(&ics)
+}
+
+/// Events ending after `now`, the soonest first.
+pub fn upcoming_events(events: Vec<CalendarEvent>, now: DateTime<Utc>) -> Vec<CalendarEvent> {
+    let mut upcoming: Vec<CalendarEvent> = events
+        .into_iter()
+        .filter(|event| event.end_instant() > now)
+        .collect();
+
+    upcoming.sort_by_key(|event| event.end_instant());
+
+    upcoming
+}
+
+pub fn parse_ics(ics: &str) -> Result<Vec<CalendarEvent>, String> {
+    if !ics.contains("BEGIN:VCALENDAR") {
+        return Err(String::from("Not an iCalendar file!"));
+    }
+
+    let mut events = Vec::new();
+    let mut current: Option<EventFields> = None;
+
+    for line in unfold_lines(ics) {
+        let (name, value) = match line.split_once(':') {
+            Some(property) => property,
+            None => continue,
+        };
+        let (name, params) = name.split_once(';').unwrap_or((name, ""));
+
+        match (name.to_uppercase().as_str(), current.as_mut()) {
+            ("BEGIN", None) if value.eq_ignore_ascii_case("VEVENT") => {
+                current = Some(EventFields::default());
+            }
+            ("END", Some(_)) if value.eq_ignore_ascii_case("VEVENT") => {
+                if let Some(event) = current.take().and_then(EventFields::into_event) {
+                    events.push(event);
+                }
+            }
+            ("SUMMARY", Some(fields)) => fields.summary = unescape_text(value),
+            ("CATEGORIES", Some(fields)) => fields
+                .categories
+                .extend(value.split(',').map(unescape_text)),
+            ("DTEND", Some(fields)) => fields.end = parse_date_time(params, value),
+            ("DTSTART", Some(fields)) => fields.start = parse_date_time(params, value),
+            _ => {}
+        }
+    }
+
+    Ok(events)
+}
+
+#[derive(Default)]
+struct EventFields {
+    summary: String,
+    categories: Vec<String>,
+    start: Option<(NaiveDateTime, String)>,
+    end: Option<(NaiveDateTime, String)>,
+}
+
+impl EventFields {
+    /// Events without an end are instants at their start.
+    fn into_event(self) -> Option<CalendarEvent> {
+        let start = self.start.as_ref().map(|(start, _)| *start);
+        let (end, timezone) = self.end.or(self.start)?;
+
+        Some(CalendarEvent {
+            summary: self.summary,
+            start: start.unwrap_or(end),
+            end,
+            timezone,
+            categories: self.categories,
+        })
+    }
+}
+
+/// Long lines are folded by starting the next line with a space or a tab.
+fn unfold_lines(ics: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+
+    for line in ics.lines() {
+        match (
+            line.strip_prefix(' ').or_else(|| line.strip_prefix('\t')),
+            lines.last_mut(),
+        ) {
+            (Some(continuation), Some(last)) => last.push_str(continuation),
+            _ => lines.push(line.to_string()),
+        }
+    }
+
+    lines
+}
+
+fn unescape_text(value: &str) -> String {
+    value
+        .replace("\\n", " ")
+        .replace("\\N", " ")
+        .replace("\\,", ",")
+        .replace("\\;", ";")
+        .replace("\\\\", "\\")
+        .trim()
+        .to_string()
+}
+
+/// Ex: "20240102T150000Z", "TZID=Europe/Paris" with "20240102T150000",
+/// or "VALUE=DATE" with "20240102" for all-day events that end at that midnight.
+fn parse_date_time(params: &str, value: &str) -> Option<(NaiveDateTime, String)> {
+    let value = value.trim();
+
+    let timezone = params
+        .split(';')
+        .find_map(|param| param.strip_prefix("TZID="))
+        .map(|tzid| tzid.trim_matches('"').to_string())
+        // Some calendars use Windows zone names, those are read as local times
+        .filter(|tzid| matches!(parse_timezone(tzid), Ok(Some(_))))
+        .unwrap_or_default();
+
+    if let Some(utc) = value.strip_suffix('Z') {
+        let at = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+
+        return Some((at, String::from("UTC")));
+    }
+
+    if let Ok(at) = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S") {
+        return Some((at, timezone));
+    }
+
+    let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+
+    Some((date.and_hms(0, 0, 0), timezone))
+}
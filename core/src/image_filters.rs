@@ -0,0 +1,53 @@
+use image::{DynamicImage, GenericImageView};
+use serde::{Deserialize, Serialize};
+
+use crate::trace;
+
+/// Filters applied to background images so the text on top of them stands out.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub struct ImageFilters {
+    /// From 0 (untouched) to 100 (black).
+    pub darken: u8,
+    /// Gaussian blur's sigma in pixels, 0 doesn't blur.
+    pub blur: f32,
+    pub grayscale: bool,
+}
+
+impl ImageFilters {
+    pub fn apply(&self, mut image: DynamicImage) -> DynamicImage {
+        if *self == ImageFilters::default() {
+            return image;
+        }
+
+        trace!(
+            "filtering the {}x{} background: {:?}",
+            image.width(),
+            image.height(),
+            self
+        );
+
+        // Stay in RGB so the colored text can still be drawn on top
+        if self.grayscale {
+            image = DynamicImage::ImageRgb8(image.grayscale().to_rgb8());
+        }
+
+        if self.blur > 0. {
+            image = image.blur(self.blur);
+        }
+
+        if self.darken > 0 {
+            let brightness = 1. - self.darken.min(100) as f32 / 100.;
+
+            let mut rgb = image.to_rgb8();
+            for pixel in rgb.pixels_mut() {
+                for channel in pixel.0.iter_mut() {
+                    *channel = (*channel as f32 * brightness) as u8;
+                }
+            }
+
+            image = DynamicImage::ImageRgb8(rgb);
+        }
+
+        image
+    }
+}
@@ -0,0 +1,84 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+use crate::{get_cache_dir, trace};
+
+pub const SUPPORTED_IMAGE_EXTENSIONS: [&str; 5] = ["png", "gif", "jpg", "jpeg", "bmp"];
+
+/// Images of the folder that weren't used yet in the current round.
+#[derive(Serialize, Deserialize, Default)]
+struct FolderRotation {
+    folder: String,
+    remaining: Vec<PathBuf>,
+}
+
+pub fn is_supported_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| SUPPORTED_IMAGE_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+/// The supported images directly inside `folder`, sorted by name.
+pub fn folder_images(folder: &str) -> Result<Vec<PathBuf>, String> {
+    let entries =
+        fs::read_dir(folder).map_err(|_| format!("Couldn't read the folder {}", folder))?;
+
+    let mut images: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && is_supported_image(path))
+        .collect();
+
+    images.sort();
+
+    Ok(images)
+}
+
+/// Picks the next image of `folder`, no image is repeated until all of them were used.
+/// They're used in a random order when `shuffle` is on and by name otherwise.
+pub fn next_folder_image(folder: &str, shuffle: bool) -> Result<PathBuf, String> {
+    let images = folder_images(folder)?;
+
+    if images.is_empty() {
+        return Err(format!("There are no images in {}", folder));
+    }
+
+    let rotation_path = get_cache_dir().join("folder_rotation.json");
+
+    let mut rotation: FolderRotation = fs::read_to_string(&rotation_path)
+        .ok()
+        .and_then(|rotation| serde_json::from_str(&rotation).ok())
+        .filter(|rotation: &FolderRotation| rotation.folder == folder)
+        .unwrap_or_default();
+
+    // Images could've been removed from the folder since the last pick
+    rotation.remaining.retain(|image| images.contains(image));
+
+    if rotation.remaining.is_empty() {
+        trace!(
+            "starting a new round of the {} images in {}",
+            images.len(),
+            folder
+        );
+
+        rotation = FolderRotation {
+            folder: folder.to_string(),
+            remaining: images,
+        };
+
+        if shuffle {
+            rotation.remaining.shuffle(&mut rand::thread_rng());
+        }
+    }
+
+    let image = rotation.remaining.remove(0);
+
+    fs::write(&rotation_path, serde_json::to_string(&rotation).unwrap()).ok();
+
+    Ok(image)
+}
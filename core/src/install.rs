@@ -0,0 +1,96 @@
+use std::fs;
+
+use crate::{
+    get_cache_dir, get_config_dir, get_config_file, new_path, stop_and_restore_original_wallpaper,
+    trace, unwrap_or_return,
+};
+
+const INSTALLED_VERSION_FILE: &str = "installed_version.txt";
+const LEGACY_CONFIG_FILE: &str = "config.json";
+
+/// How this launch relates to the previous one of the same OS user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Launch {
+    FirstRun,
+    Updated { from: String },
+    Again,
+}
+
+/// Whether deadliner never ran for this OS user, packaged installs share
+/// the binaries but every user gets their own data.
+pub fn is_first_run() -> bool {
+    !get_config_dir().join(INSTALLED_VERSION_FILE).exists()
+}
+
+/// Runs on every GUI launch, moves data left by older versions to where this one
+/// reads it and remembers the running version to tell first runs and updates apart.
+pub fn run_install_hooks() -> Launch {
+    let version_file = get_config_dir().join(INSTALLED_VERSION_FILE);
+    let current = env!("CARGO_PKG_VERSION");
+
+    let launch = match fs::read_to_string(&version_file) {
+        Err(_) => Launch::FirstRun,
+        Ok(previous) if previous.trim() != current => Launch::Updated {
+            from: previous.trim().to_string(),
+        },
+        Ok(_) => Launch::Again,
+    };
+
+    if launch != Launch::Again {
+        trace!("{:?}, migrating the data of this user", launch);
+
+        if let Err(e) = migrate_user_data() {
+            trace!("{}", e);
+        }
+
+        fs::write(&version_file, current).ok();
+    }
+
+    launch
+}
+
+/// Moves the config older versions wrote next to the binaries into the user's config dir,
+/// installers replace (or lock) the install dir so nothing should be kept there.
+pub fn migrate_user_data() -> Result<(), String> {
+    let legacy = new_path(LEGACY_CONFIG_FILE);
+    let conf_file = get_config_file();
+
+    if !legacy.exists() || conf_file.exists() {
+        return Ok(());
+    }
+
+    unwrap_or_return!(
+        fs::copy(&legacy, &conf_file),
+        "Couldn't move the old config to your user's config dir!"
+    );
+
+    // Read-only installs can't remove it, the copy is read first anyway
+    fs::remove_file(&legacy).ok();
+
+    trace!("moved {} to {}", legacy.display(), conf_file.display());
+
+    Ok(())
+}
+
+/// Stops the countdown, sets the original wallpaper back and removes everything
+/// deadliner saved for this user, called by uninstallers with `deadliner uninstall-cleanup`.
+pub fn uninstall_cleanup() -> Result<String, String> {
+    // The original wallpaper is remembered in the cache dir, restore it before removing it
+    let restored = stop_and_restore_original_wallpaper();
+
+    for dir in [get_cache_dir(), get_config_dir()] {
+        if dir.exists() {
+            unwrap_or_return!(
+                fs::remove_dir_all(&dir),
+                format!("Couldn't remove {}!", dir.display())
+            );
+        }
+    }
+
+    Ok(match restored {
+        Ok(_) => {
+            String::from("Restored your original wallpaper and removed all of deadliner's data.")
+        }
+        Err(e) => format!("Removed all of deadliner's data. {}", e),
+    })
+}
@@ -0,0 +1,56 @@
+use std::f32::consts::PI;
+
+use image::{Rgba, RgbaImage};
+use imageproc::drawing::draw_filled_circle_mut;
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+
+/// How the countdown is arranged on the wallpaper.
+#[derive(Debug, PartialEq, Clone, Copy, EnumIter, Serialize, Deserialize, Default)]
+pub enum Layout {
+    #[default]
+    Text,
+    /// The text is drawn inside a ring that drains as the deadline gets closer.
+    ProgressRing,
+}
+
+/// Draws `content` in the middle of a ring, `remaining` (0 to 1) of the ring is filled
+/// clockwise from the top and the rest is a faint track.
+pub fn draw_progress_ring(content: &RgbaImage, remaining: f32, color: [u8; 3]) -> RgbaImage {
+    let (width, height) = content.dimensions();
+    let thickness = (height / 8).max(6);
+
+    // The ring goes around the text's corners
+    let inner_diameter = ((width * width + height * height) as f32).sqrt() as u32;
+    let size = inner_diameter + thickness * 4;
+
+    let mut ring = RgbaImage::new(size, size);
+
+    let center = size as f32 / 2.;
+    let radius = center - thickness as f32;
+    let [r, g, b] = color;
+
+    // Stamping small circles along the arc gives it round caps
+    let steps = (2. * PI * radius) as u32;
+    for step in 0..steps {
+        let progress = step as f32 / steps as f32;
+        let alpha = if progress <= remaining { 255 } else { 40 };
+
+        let angle = progress * 2. * PI - PI / 2.;
+        let x = center + radius * angle.cos();
+        let y = center + radius * angle.sin();
+
+        draw_filled_circle_mut(
+            &mut ring,
+            (x as i32, y as i32),
+            thickness as i32 / 2,
+            Rgba([r, g, b, alpha]),
+        );
+    }
+
+    let x = (size - width) / 2;
+    let y = (size - height) / 2;
+    image::imageops::overlay(&mut ring, content, x, y);
+
+    ring
+}
@@ -0,0 +1,923 @@
+//! Everything deadliner does besides drawing its GUI, shared by the GUI, the schedular
+//! and any other tool (Ex: a status-bar script) that shouldn't link egui.
+//!
+//! - [`DeadlinerConf`] is what the user edits and [`sanitize_inputs`] validates it
+//!   into the [`SanitizedConf`] that everything else runs with.
+//! - [`format_deadline_at`] formats a deadline's countdown, [`break_down`] splits
+//!   the time left into the shown units.
+//! - [`generate_wallpaper`] draws the text lines of [`deadline_lines`] on the background,
+//!   [`update_wallpaper`] renders and sets it as the wallpaper.
+//! - [`save_inputs`] saves the inputs and (re)starts the schedular with them,
+//!   [`load_sanitized_conf`] reads what the schedular runs with.
+//!
+//! ```no_run
+//! use chrono::Utc;
+//! use deadliner_core::{format_deadline_at, load_sanitized_conf};
+//!
+//! let conf = load_sanitized_conf().unwrap();
+//!
+//! for deadline in &conf.deadlines {
+//!     println!("{}", format_deadline_at(&conf, deadline, Utc::now(), false));
+//! }
+//! ```
+
+mod agenda;
+mod animated_export;
+mod announcements;
+mod archive;
+mod backdrop;
+mod backend_test;
+mod background_provider;
+mod branding;
+mod broadcast;
+mod calendar_sync;
+mod check;
+mod cli_output;
+mod config;
+mod config_watcher;
+mod contrast;
+mod control;
+mod csv_import;
+mod deadline_hook;
+mod deadlines_export;
+mod doctor;
+mod download;
+mod embedded_fonts;
+mod error;
+mod events;
+mod focus;
+mod font_cache;
+mod fuzzy;
+mod gradient;
+mod handoff;
+mod holidays;
+mod ics_export;
+mod ics_import;
+mod image_filters;
+mod image_folder;
+mod install;
+mod layout;
+mod locale;
+mod macros;
+mod memory_cap;
+mod migration;
+mod mode_preview;
+mod monitors;
+mod natural_date;
+mod notifications;
+mod online_sources;
+mod original_wallpaper;
+mod output_target;
+mod overdue;
+mod pattern;
+mod pause;
+mod performance;
+mod png_encoding;
+mod profiles;
+mod quick_picks;
+mod rate_limit;
+mod recurrence;
+mod refresh_health;
+mod rotation;
+mod rounding;
+mod safe_mode;
+mod sanity;
+mod seasonal;
+mod settings_bundle;
+mod share_card;
+mod short_hash;
+mod single_instance;
+mod sound_alerts;
+mod stale_warning;
+mod status_bar;
+mod stress_free;
+mod tags;
+mod text_position;
+mod text_variables;
+mod timezone;
+mod unit_format;
+mod update_wallpaper;
+mod urgency;
+mod url_cache;
+mod user_config;
+mod verbose;
+mod wayland;
+mod webhooks;
+mod workdays;
+
+pub use agenda::*;
+pub use animated_export::*;
+pub use announcements::*;
+pub use archive::*;
+pub use backdrop::*;
+pub use backend_test::*;
+pub use background_provider::*;
+pub use branding::*;
+pub use broadcast::*;
+pub use calendar_sync::*;
+pub use check::*;
+pub use cli_output::*;
+pub use config::*;
+pub use config_watcher::*;
+pub use contrast::*;
+pub use control::*;
+pub use csv_import::*;
+pub use deadline_hook::*;
+pub use deadlines_export::*;
+pub use doctor::*;
+pub use download::*;
+pub use embedded_fonts::*;
+pub use error::*;
+pub use events::*;
+pub use focus::*;
+pub use font_cache::*;
+pub use fuzzy::*;
+pub use gradient::*;
+pub use handoff::*;
+pub use holidays::*;
+pub use ics_export::*;
+pub use ics_import::*;
+pub use image_filters::*;
+pub use image_folder::*;
+pub use install::*;
+pub use layout::*;
+pub use locale::*;
+pub use memory_cap::*;
+pub use migration::*;
+pub use mode_preview::*;
+pub use monitors::*;
+pub use natural_date::*;
+pub use notifications::*;
+pub use online_sources::*;
+pub use original_wallpaper::*;
+pub use output_target::*;
+pub use overdue::*;
+pub use pattern::*;
+pub use pause::*;
+pub use performance::*;
+pub use png_encoding::*;
+pub use profiles::*;
+pub use quick_picks::*;
+use rate_limit::*;
+pub use recurrence::*;
+pub use refresh_health::*;
+pub use rotation::*;
+pub use rounding::*;
+pub use safe_mode::*;
+pub use sanity::*;
+pub use seasonal::*;
+use serde::{Deserialize, Serialize};
+pub use settings_bundle::*;
+pub use share_card::*;
+pub use short_hash::*;
+pub use single_instance::*;
+pub use sound_alerts::*;
+pub use stale_warning::*;
+pub use status_bar::*;
+pub use stress_free::*;
+pub use tags::*;
+pub use text_position::*;
+pub use text_variables::*;
+pub use timezone::*;
+pub use unit_format::*;
+pub use update_wallpaper::*;
+pub use urgency::*;
+pub use url_cache::*;
+pub use user_config::*;
+pub use verbose::*;
+pub use wayland::*;
+pub use webhooks::*;
+pub use workdays::*;
+
+use chrono::{DateTime, Local, NaiveDateTime, NaiveTime, Utc};
+use std::error::Error;
+use std::io::BufWriter;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::Duration;
+use std::{env, fs, thread};
+use std::{fs::File, path};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SanitizedConf {
+    /// Configs without one are from before config.json was versioned.
+    #[serde(default)]
+    pub version: u32,
+    pub screen_dimensions: ScreenDimensions,
+    pub monitors: Vec<MonitorConf>,
+
+    pub default_bg: SanitizedBackground,
+    pub bg_mode: WallpaperMode,
+    /// Crop/Fit/Span are applied while rendering and the OS only centers the result.
+    pub render_mode_in_app: bool,
+    pub background_filters: ImageFilters,
+    pub png_compression: PngCompression,
+    pub performance: PerformanceBudget,
+    pub refresh_timeout_secs: u32,
+    pub url_cache_ttl_hours: u32,
+    pub download_timeout_secs: u32,
+    pub memory_cap_mb: u32,
+    pub output_targets: Vec<OutputTargetConf>,
+    // Configs saved before this option existed keep launching on login
+    #[serde(default = "default_launch_on_login")]
+    pub launch_on_login: bool,
+
+    pub show_months: bool,
+    pub show_weeks: bool,
+    pub show_days: bool,
+    pub show_hours: bool,
+    pub show_minutes: bool,
+    pub show_seconds: bool,
+    /// Units smaller than this one are never shown.
+    pub smallest_unit: TimeUnit,
+    pub rounding: RoundingMode,
+    pub unit_format: UnitFormat,
+    pub countdown_style: CountdownStyle,
+    pub stress_free: StressFreeConf,
+    pub focus: FocusConf,
+    pub workdays: WorkdaysConf,
+
+    pub layout: Layout,
+    pub font: Font,
+    pub font_size: u8,
+    pub font_color: String,
+    pub urgency_colors: UrgencyColorsConf,
+    pub final_hours: FinalHoursConf,
+    pub custom_font_location: String,
+    pub title_font_size: u8,
+    pub title_font_color: String,
+    pub text_position: TextPosition,
+    /// Keeps the text readable on any background.
+    pub high_contrast: bool,
+    pub backdrop: BackdropConf,
+    /// IANA names of the time zones shown under every countdown.
+    pub world_clocks: Vec<String>,
+    pub text_variables: Vec<ScriptVariableConf>,
+
+    pub branding_location: String,
+
+    pub deadlines: Vec<SanitizedDeadline>,
+    pub overdue: OverdueBehavior,
+    #[serde(default)]
+    pub rotation: RotationMode,
+
+    pub broadcast: BroadcastConf,
+    pub announcements: AnnouncementsConf,
+    pub sound_alerts: SoundAlertsConf,
+    pub notifications: NotificationsConf,
+    pub deadline_hook: DeadlineHookConf,
+    pub webhooks: WebhooksConf,
+    pub calendar_sync: CalendarSyncConf,
+    pub agenda: AgendaConf,
+    #[serde(default)]
+    pub seasonal: SeasonalConf,
+    #[serde(default)]
+    pub tags: Vec<DeadlineTag>,
+    #[serde(default)]
+    pub stale_warning: StaleWarningConf,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SanitizedDeadline {
+    pub title: String,
+    pub deadline_str: String,
+    /// IANA name of the time zone `deadline_str` is in, `None` is the local time zone.
+    pub timezone: Option<String>,
+    pub locale: Locale,
+    /// Either the deadline's template override or the locale's default template.
+    pub template: String,
+    /// Unix timestamp of when the deadline was first saved, the progress ring starts there.
+    pub started_at: Option<i64>,
+    /// `deadline_str` is the first occurrence of a recurring deadline.
+    #[serde(default)]
+    pub recurrence: Recurrence,
+    /// The time since `deadline_str` is shown, it's never over.
+    #[serde(default)]
+    pub counts_up: bool,
+    /// Name of the deadline's tag, empty when it's not tagged.
+    #[serde(default)]
+    pub tag: String,
+}
+
+impl SanitizedDeadline {
+    pub fn date(&self) -> DateTime<Utc> {
+        self.date_at(Utc::now())
+    }
+
+    /// The occurrence that's counted down to at `now`.
+    pub fn date_at(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let first = NaiveDateTime::parse_from_str(&self.deadline_str, "%Y-%m-%d %I:%M %p").unwrap();
+        let timezone = parse_timezone(self.timezone.as_deref().unwrap_or_default()).unwrap();
+
+        let n = self.recurrence.current_index(first, timezone, now);
+
+        resolve_deadline(self.recurrence.nth(first, n), timezone)
+    }
+
+    /// When the current occurrence's countdown started, the previous occurrence for recurring ones.
+    fn countdown_start(&self) -> Option<i64> {
+        let first = NaiveDateTime::parse_from_str(&self.deadline_str, "%Y-%m-%d %I:%M %p").unwrap();
+        let timezone = parse_timezone(self.timezone.as_deref().unwrap_or_default()).unwrap();
+
+        match self.recurrence.current_index(first, timezone, Utc::now()) {
+            0 => self.started_at,
+            n => {
+                let previous = resolve_deadline(self.recurrence.nth(first, n - 1), timezone);
+                Some(previous.timestamp())
+            }
+        }
+    }
+
+    pub fn time_left(&self) -> chrono::Duration {
+        self.time_left_at(Utc::now())
+    }
+
+    pub fn time_left_at(&self, now: DateTime<Utc>) -> chrono::Duration {
+        self.date_at(now).signed_duration_since(now)
+    }
+
+    pub fn minutes_left(&self) -> i64 {
+        self.time_left().num_minutes()
+    }
+
+    /// Part of the time from when the deadline was saved till the deadline that's left, from 0 to 1.
+    pub fn remaining_fraction(&self) -> f32 {
+        let started_at = match self.countdown_start() {
+            Some(started_at) => started_at,
+            None => return 1.,
+        };
+
+        let total = self.date().timestamp() - started_at;
+        if total <= 0 {
+            return 0.;
+        }
+
+        (self.time_left().num_seconds() as f32 / total as f32).clamp(0., 1.)
+    }
+
+    /// The milestone (in minutes left) that's reached this minute if there's one.
+    pub fn reached_milestone(&self, milestones: &[u32]) -> Option<i64> {
+        if self.counts_up {
+            return None;
+        }
+
+        // The schedular checks at the start of every minute, a few milliseconds late
+        let minutes = RoundingMode::HalfUp.round(self.time_left().num_seconds(), TimeUnit::Minute);
+
+        if minutes >= 0 && milestones.contains(&(minutes as u32)) {
+            Some(minutes)
+        } else {
+            None
+        }
+    }
+}
+
+impl SanitizedConf {
+    /// Whether the wallpaper keeps getting updated after all of the deadlines are over.
+    pub fn keeps_running(&self) -> bool {
+        self.focus.enabled
+            || self.overdue.keeps_running()
+            || self.deadlines.iter().any(|d| d.counts_up)
+    }
+
+    /// The mode the OS should set the rendered wallpaper with.
+    pub fn os_mode(&self) -> WallpaperMode {
+        if self.render_mode_in_app {
+            WallpaperMode::Center
+        } else {
+            self.bg_mode
+        }
+    }
+
+    /// Units picked to be shown, from the biggest to the smallest one.
+    pub fn shown_units(&self) -> Vec<TimeUnit> {
+        [
+            (TimeUnit::Month, self.show_months),
+            (TimeUnit::Week, self.show_weeks),
+            (TimeUnit::Day, self.show_days),
+            (TimeUnit::Hour, self.show_hours),
+            (TimeUnit::Minute, self.show_minutes),
+            (TimeUnit::Second, self.show_seconds),
+        ]
+        .into_iter()
+        .filter(|(unit, shown)| *shown && unit.seconds() >= self.smallest_unit.seconds())
+        .map(|(unit, _)| unit)
+        .collect()
+    }
+
+    /// Minutes left till the last deadline is over.
+    pub fn minutes_left(&self) -> i64 {
+        self.deadlines
+            .iter()
+            .map(|d| d.minutes_left())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Progress of the closest deadline that's not over yet, see `SanitizedDeadline::remaining_fraction`.
+    pub fn closest_remaining_fraction(&self) -> f32 {
+        self.deadlines
+            .iter()
+            .filter(|d| d.minutes_left() > 0)
+            .min_by_key(|d| d.minutes_left())
+            .map_or(0., |d| d.remaining_fraction())
+    }
+
+    /// Minutes left till the closest deadline that's not over yet.
+    pub fn closest_minutes_left(&self) -> i64 {
+        self.deadlines
+            .iter()
+            .map(|d| d.minutes_left())
+            .filter(|minutes| *minutes > 0)
+            .min()
+            .unwrap_or(0)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum SanitizedBackground {
+    Solid {
+        rgb: [u8; 3],
+        hex: String,
+        #[serde(default)]
+        pattern: Pattern,
+    },
+    Gradient {
+        start: [u8; 3],
+        end: [u8; 3],
+        direction: GradientDirection,
+    },
+    FromDisk(String),
+    FromURL(String),
+    FromFolder {
+        path: String,
+        shuffle: bool,
+    },
+    Online {
+        source: OnlineSource,
+        keyword: String,
+    },
+}
+
+impl From<Background> for SanitizedBackground {
+    fn from(b: Background) -> Self {
+        match b {
+            Background::Solid {
+                color: [r, g, b],
+                pattern,
+            } => Self::Solid {
+                rgb: [r, g, b],
+                hex: rgb_to_hex(r, g, b),
+                pattern,
+            },
+            Background::Gradient {
+                start,
+                end,
+                direction,
+            } => Self::Gradient {
+                start,
+                end,
+                direction,
+            },
+            Background::FromDisk { location, .. } => Self::FromDisk(location.trim().to_string()),
+            Background::FromURL { url, .. } => Self::FromURL(url.trim().to_string()),
+            Background::FromFolder { path, shuffle, .. } => Self::FromFolder {
+                path: path.trim().to_string(),
+                shuffle,
+            },
+            Background::Online {
+                source, keyword, ..
+            } => Self::Online {
+                source,
+                keyword: keyword.trim().to_string(),
+            },
+        }
+    }
+}
+
+pub(crate) fn rgb_to_hex(r: u8, g: u8, b: u8) -> String {
+    format!("#{:02X}{:02X}{:02X}", r, g, b)
+}
+
+pub fn hex_to_rgb(hex: &str) -> Option<[u8; 3]> {
+    let hex = hex.trim().trim_start_matches('#');
+
+    if hex.len() != 6 {
+        return None;
+    }
+
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+/// Validates the inputs and turns them into the config the schedular renders from.
+pub fn sanitize_inputs(conf: &DeadlinerConf) -> Result<SanitizedConf, String> {
+    // Settings locked by an admin always win over the ones the user picked
+    let conf = &apply_managed_layer(conf)?;
+
+    sanitize_inputs_with(conf, load_saved_sanitized_conf().ok())
+}
+
+/// Validates the inputs as they are, without the managed settings or the saved config,
+/// `saved_conf` is what the focus sessions and the deadlines' progress carry on from.
+pub fn sanitize_inputs_with(
+    conf: &DeadlinerConf,
+    saved_conf: Option<SanitizedConf>,
+) -> Result<SanitizedConf, String> {
+    if !(conf.show_months
+        || conf.show_weeks
+        || conf.show_days
+        || conf.show_hours
+        || conf.show_minutes
+        || conf.show_seconds)
+        || conf.deadlines.is_empty()
+        || conf.deadlines.iter().any(|d| d.date.trim().is_empty())
+    {
+        return Err(String::from("Not enough Inputs"));
+    }
+
+    let mut sanitized_conf = SanitizedConf {
+        version: CONFIG_VERSION,
+        screen_dimensions: conf.screen_dimensions,
+        monitors: conf.monitors.clone(),
+        default_bg: conf.default_background.clone().into(),
+        bg_mode: conf.default_background.mode(),
+        render_mode_in_app: conf.render_mode_in_app,
+        background_filters: conf.background_filters,
+        png_compression: conf.png_compression,
+        performance: conf.performance,
+        refresh_timeout_secs: conf.refresh_timeout_secs.max(5),
+        url_cache_ttl_hours: conf.url_cache_ttl_hours,
+        download_timeout_secs: conf.download_timeout_secs.max(1),
+        memory_cap_mb: conf.memory_cap_mb,
+        output_targets: sanitize_output_targets(&conf.output_targets)?,
+        launch_on_login: conf.launch_on_login,
+        layout: conf.layout,
+        font: conf.font,
+        font_size: conf.font_size,
+        urgency_colors: conf.urgency_colors,
+        final_hours: conf.final_hours.sanitized(),
+
+        show_months: conf.show_months,
+        show_weeks: conf.show_weeks,
+        show_days: conf.show_days,
+        show_hours: conf.show_hours,
+        show_minutes: conf.show_minutes,
+        show_seconds: conf.show_seconds,
+        smallest_unit: conf.performance.smallest_unit(conf.smallest_unit),
+        rounding: conf.rounding,
+        unit_format: conf.unit_format.clone(),
+        countdown_style: conf.countdown_style,
+        stress_free: conf.stress_free,
+        focus: conf
+            .focus
+            .sanitized(saved_conf.as_ref().map(|saved| &saved.focus))?,
+        workdays: conf.workdays.sanitized()?,
+        custom_font_location: conf.custom_font_location.clone(),
+        title_font_size: conf.title_font_size,
+        title_font_color: {
+            let [r, g, b] = conf.title_font_color;
+            rgb_to_hex(r, g, b)
+        },
+        text_position: conf.text_position,
+        high_contrast: conf.high_contrast,
+        backdrop: conf.backdrop,
+        world_clocks: sanitize_world_clocks(&conf.world_clocks)?,
+        text_variables: conf
+            .text_variables
+            .iter()
+            .map(|variable| variable.sanitized())
+            .collect::<Result<_, _>>()?,
+        branding_location: conf.branding_location.trim().to_string(),
+
+        // Just a placeholder till we convert RGB to HEX
+        font_color: String::new(),
+
+        // Just a placeholder till we parse the dates
+        deadlines: Vec::new(),
+        overdue: conf.overdue.clone(),
+        rotation: conf.rotation,
+        broadcast: conf.broadcast,
+        announcements: conf.announcements.clone(),
+        sound_alerts: conf.sound_alerts.sanitized()?,
+        notifications: conf.notifications.clone(),
+        deadline_hook: conf.deadline_hook.sanitized()?,
+        webhooks: conf.webhooks.sanitized()?,
+        calendar_sync: conf.calendar_sync.sanitized()?,
+        agenda: conf.agenda.sanitized(),
+        seasonal: conf.seasonal.clone(),
+        tags: conf.tags.clone(),
+        stale_warning: conf.stale_warning,
+    };
+
+    // font-color RGB to HEX
+    {
+        let [r, g, b] = conf.font_color;
+
+        sanitized_conf.font_color = rgb_to_hex(r, g, b);
+    }
+
+    // Keep counting the progress from the first save of deadlines that didn't change
+    let saved_deadlines = saved_conf.map(|saved| saved.deadlines).unwrap_or_default();
+
+    for deadline in &conf.deadlines {
+        let mut deadline = sanitize_deadline(deadline, conf.use_24_hour)?;
+
+        deadline.started_at = saved_deadlines
+            .iter()
+            .find(|saved| {
+                saved.deadline_str == deadline.deadline_str && saved.timezone == deadline.timezone
+            })
+            .and_then(|saved| saved.started_at)
+            .or(Some(Utc::now().timestamp()));
+
+        sanitized_conf.deadlines.push(deadline);
+    }
+
+    if sanitized_conf.shown_units().is_empty() {
+        return Err(String::from(
+            "Pick a time unit that's not smaller than the precision!",
+        ));
+    }
+
+    if let OverdueBehavior::Message(msg) = &mut sanitized_conf.overdue {
+        *msg = msg.trim().to_string();
+
+        if msg.is_empty() {
+            return Err(String::from("Overdue message can't be empty!"));
+        }
+    }
+
+    // Monitors pointing at a removed deadline fallback to showing all of them
+    for monitor in &mut sanitized_conf.monitors {
+        if monitor.deadline.is_some_and(|i| i >= conf.deadlines.len()) {
+            monitor.deadline = None;
+        }
+    }
+
+    // Make sure the branding preset is readable before relying on it in the schedular
+    load_branding(&sanitized_conf.branding_location)?;
+
+    Ok(sanitized_conf)
+}
+
+pub fn save_inputs(conf: &DeadlinerConf) -> Result<(), DeadlinerError> {
+    // New inputs are rendered as they are, a crash puts the next launch in safe mode again
+    leave_safe_mode();
+
+    // The managed settings are applied while sanitizing, the inputs are cached as picked
+    let sanitized_conf = sanitize_inputs(conf).map_err(DeadlinerError::Parse)?;
+
+    // Run update_wallpaper once to check for any potential errors before saving this conf.
+    update_wallpaper(&sanitized_conf, true)?;
+
+    update_wallpaper(&sanitized_conf, false)?;
+
+    // If we managed to update the wallpaper successfully, then save the current conf.
+    write_inputs(conf, &sanitized_conf)?;
+
+    stop_schedular();
+
+    // !Here we setup a schedule to update the wallpaper
+    let schedular_exec = format!("deadliner-schedular{}", &get_current_file_ext());
+    Command::new(new_path(&schedular_exec))
+        .arg("skip-update-on-launch")
+        .spawn()
+        .map_err(|e| DeadlinerError::io("Couldn't run the schedular binary!", e))?;
+
+    Ok(())
+}
+
+/// Writes the inputs and the config the schedular runs with, without restarting it.
+pub fn write_inputs(
+    conf: &DeadlinerConf,
+    sanitized_conf: &SanitizedConf,
+) -> Result<(), DeadlinerError> {
+    // Write the config.json in the user's config dir instead of in the cache dir cause this is a very
+    // important file. And it would be bad if it was accidently deleted when the cache was cleared
+    let saving_failed =
+        |e| DeadlinerError::io("Couldn't save your configuration to the filesystem!", e);
+
+    save_calendar_credentials(&sanitized_conf.calendar_sync)
+        .map_err(|msg| DeadlinerError::Io { msg, source: None })?;
+
+    fs::write(
+        get_config_file(),
+        serde_json::to_string_pretty(sanitized_conf).unwrap(),
+    )
+    .map_err(saving_failed)?;
+
+    let cache_conf = get_profile_cache_dir().join("raw_config.json");
+
+    fs::write(cache_conf, serde_json::to_string_pretty(conf).unwrap()).map_err(saving_failed)
+}
+
+/// Shuts down the running instance of the schedular if there's one.
+pub fn stop_schedular() {
+    // Check if there's an already running instance of schedular
+    let port = get_schedular_port();
+    let server_url = format!("http://127.0.0.1:{}", port);
+
+    let res = reqwest::blocking::Client::builder()
+        .timeout(Duration::from_millis(50))
+        .build()
+        .unwrap()
+        .get(&server_url)
+        .send();
+
+    let is_schedular_running = res.is_ok() && res.unwrap().status().as_u16() == 200;
+
+    if is_schedular_running {
+        // Send a request to shutdown the running schedular
+        reqwest::blocking::get(server_url + "/shutdown").unwrap();
+
+        // Give the schedular a bit of time till it shutdown
+        thread::sleep(Duration::from_millis(50));
+    }
+}
+
+pub(crate) fn sanitize_deadline(
+    deadline: &Deadline,
+    use_24_hour: bool,
+) -> Result<SanitizedDeadline, String> {
+    let date = resolve_deadline_date(deadline, use_24_hour)?;
+
+    // The schedular always reads the deadline in the 12-hour format
+    let formatted_date_str = date.format("%Y-%m-%d %I:%M %p").to_string();
+
+    let timezone = parse_timezone(&deadline.timezone)?;
+
+    // Check if deadline was in the future, a recurring one can start in the past
+    let minutes = resolve_deadline(date, timezone)
+        .signed_duration_since(Utc::now())
+        .num_minutes();
+    if deadline.counts_up {
+        if minutes > 0 {
+            return Err(String::from("Counting up needs a past date!"));
+        }
+        if deadline.recurrence != Recurrence::Never {
+            return Err(String::from("A deadline counting up can't repeat!"));
+        }
+    } else if minutes <= 0 && deadline.recurrence == Recurrence::Never {
+        return Err(String::from("Deadline must be a future date!"));
+    }
+
+    let template = if deadline.template.trim().is_empty() && deadline.counts_up {
+        deadline.locale.count_up_template().to_string()
+    } else if deadline.template.trim().is_empty() {
+        deadline.locale.default_template().to_string()
+    } else {
+        deadline.template.trim().to_string()
+    };
+
+    if !template.contains("{countdown}") {
+        return Err(String::from("Template must contain {countdown}!"));
+    }
+
+    Ok(SanitizedDeadline {
+        title: deadline.title.trim().to_string(),
+        deadline_str: formatted_date_str,
+        timezone: timezone.map(|tz| tz.name().to_string()),
+        locale: deadline.locale,
+        template,
+        started_at: None,
+        recurrence: deadline.recurrence,
+        counts_up: deadline.counts_up,
+        tag: deadline.tag.trim().to_string(),
+    })
+}
+
+/// Resolves the typed date, Ex: "2022-08-26" or "next friday 5pm", with the time inputs
+/// which are only needed when the date doesn't include a time.
+pub fn resolve_deadline_date(
+    deadline: &Deadline,
+    use_24_hour: bool,
+) -> Result<NaiveDateTime, String> {
+    let now = Local::now().naive_local();
+
+    let (date, time) = match parse_natural_date(&deadline.date, now) {
+        Some(resolved) => resolved,
+        None => return Err(String::from("Invalid date input!")),
+    };
+
+    let time = match time {
+        Some(time) => time,
+        None if deadline.hours.trim().is_empty() || deadline.minutes.trim().is_empty() => {
+            return Err(String::from("Not enough Inputs"));
+        }
+        None if use_24_hour => {
+            let time_str = format!("{}:{}", deadline.hours.trim(), deadline.minutes.trim());
+
+            unwrap_or_return!(
+                NaiveTime::parse_from_str(&time_str, "%H:%M"),
+                "Invalid date input!"
+            )
+        }
+        None => {
+            let time_str = format!(
+                "{}:{} {:?}",
+                deadline.hours.trim(),
+                deadline.minutes.trim(),
+                deadline.period
+            );
+
+            unwrap_or_return!(
+                NaiveTime::parse_from_str(&time_str, "%I:%M %p"),
+                "Invalid date input!"
+            )
+        }
+    };
+
+    Ok(date.and_time(time))
+}
+
+pub fn is_string_numeric(word: &str) -> bool {
+    for c in word.chars() {
+        if !c.is_numeric() {
+            return false;
+        }
+    }
+    true
+}
+
+pub fn get_file_name_from_path(file_path: &str) -> &str {
+    let location_paths: Vec<&str> = file_path.split(path::MAIN_SEPARATOR).collect();
+
+    location_paths[location_paths.len() - 1]
+}
+
+pub(crate) type DownloadResult<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Today's image of an online source, it's only downloaded once a day.
+pub fn download_daily_image(
+    source: OnlineSource,
+    keyword: &str,
+    dimensions: ScreenDimensions,
+    timeout_secs: u32,
+) -> DownloadResult<String> {
+    let today = Local::now().format("%Y-%m-%d");
+    let cache_key = unique_hash(&format!("{:?}-{}-{}", source, keyword.trim(), today));
+
+    download_cached(&cache_key, timeout_secs, || {
+        Ok(source.image_url(keyword, dimensions, timeout_secs)?)
+    })
+}
+
+/// Downloads the image from the URL `resolve_url` returns unless it was cached as `cache_key`,
+/// the URL isn't resolved at all then.
+fn download_cached(
+    cache_key: &str,
+    timeout_secs: u32,
+    resolve_url: impl FnOnce() -> DownloadResult<String>,
+) -> DownloadResult<String> {
+    let cache_dir = dirs::cache_dir().ok_or("no cache dir")?;
+    let file_path = cache_dir.join(format!("./deadliner/{}.png", cache_key));
+
+    if !file_path.exists() {
+        let url = resolve_url()?;
+        // Download next to the cached image first so an interrupted download isn't cached
+        let part_path = file_path.with_extension("part");
+
+        trace!("downloading {}", url);
+
+        let client = http_client(timeout_secs);
+        let response = send_with_retry(|| client.get(&url))?;
+
+        copy_with_progress(&url, response, BufWriter::new(File::create(&part_path)?))?;
+
+        fs::rename(&part_path, &file_path)?;
+    }
+
+    Ok(file_path.to_str().to_owned().ok_or("no file path")?.into())
+}
+
+pub fn new_path(path: &str) -> PathBuf {
+    let mut exe_location = env::current_exe().unwrap();
+
+    exe_location.pop();
+
+    exe_location.join(path)
+}
+
+pub fn get_cache_dir() -> PathBuf {
+    let cache_dir = dirs::cache_dir().ok_or("no cache dir").unwrap();
+
+    cache_dir.join("deadliner")
+}
+
+pub fn get_current_file_ext() -> String {
+    let curr_exe = std::env::current_exe().unwrap();
+    let splitted_by_dots = curr_exe
+        .as_os_str()
+        .to_str()
+        .unwrap()
+        .split(".")
+        .collect::<Vec<&str>>();
+
+    if splitted_by_dots.len() < 2 {
+        String::new()
+    } else {
+        ".".to_string() + splitted_by_dots[splitted_by_dots.len() - 1]
+    }
+}
@@ -0,0 +1,318 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+
+use crate::FuzzyQualifier;
+
+/// Language used to render a deadline's countdown.
+#[derive(Debug, PartialEq, Clone, Copy, EnumIter, Serialize, Deserialize, Default)]
+pub enum Locale {
+    #[default]
+    English,
+    Arabic,
+    French,
+    German,
+    Spanish,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, EnumIter, Serialize, Deserialize)]
+pub enum TimeUnit {
+    Month,
+    Week,
+    Day,
+    Hour,
+    Minute,
+    Second,
+}
+
+impl TimeUnit {
+    /// Length of the unit, a month is always counted as 30 days.
+    pub fn seconds(&self) -> i64 {
+        match self {
+            Self::Month => 30 * 24 * 60 * 60,
+            Self::Week => 7 * 24 * 60 * 60,
+            Self::Day => 24 * 60 * 60,
+            Self::Hour => 60 * 60,
+            Self::Minute => 60,
+            Self::Second => 1,
+        }
+    }
+}
+
+/// CLDR plural categories, only the ones used by the supported locales.
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+impl Locale {
+    /// Template used when a deadline doesn't override it,
+    /// `{countdown}` is replaced with the remaining time.
+    pub fn default_template(&self) -> &'static str {
+        match self {
+            Self::English => "{countdown} Left.",
+            Self::Arabic => "متبقي {countdown}",
+            Self::French => "Il reste {countdown}.",
+            Self::German => "Noch {countdown}.",
+            Self::Spanish => "Quedan {countdown}.",
+        }
+    }
+
+    /// Template used for deadlines that are over when counting up.
+    pub fn overdue_template(&self) -> &'static str {
+        match self {
+            Self::English => "{countdown} overdue",
+            Self::Arabic => "متأخر {countdown}",
+            Self::French => "En retard de {countdown}",
+            Self::German => "{countdown} überfällig",
+            Self::Spanish => "{countdown} de retraso",
+        }
+    }
+
+    /// Default template of deadlines counting up the time since a past date.
+    pub fn count_up_template(&self) -> &'static str {
+        match self {
+            Self::English => "It's been {countdown}",
+            Self::Arabic => "مضى {countdown}",
+            Self::French => "Cela fait {countdown}",
+            Self::German => "Seit {countdown}",
+            Self::Spanish => "Han pasado {countdown}",
+        }
+    }
+
+    /// Spoken at the announcement thresholds.
+    pub fn announcement_template(&self) -> &'static str {
+        match self {
+            Self::English => "{countdown} remaining",
+            Self::Arabic => "متبقي {countdown}",
+            Self::French => "Il reste {countdown}",
+            Self::German => "Noch {countdown}",
+            Self::Spanish => "Quedan {countdown}",
+        }
+    }
+
+    /// Note after a working days countdown, Ex: "(3 public holidays excluded)".
+    pub fn holidays_excluded(&self, count: usize) -> String {
+        match (self, count) {
+            (Self::English, 1) => String::from("(1 public holiday excluded)"),
+            (Self::English, _) => format!("({} public holidays excluded)", count),
+            (Self::Arabic, _) => format!("(عطلات رسمية مستثناة: {})", count),
+            (Self::French, 1) => String::from("(1 jour férié exclu)"),
+            (Self::French, _) => format!("({} jours fériés exclus)", count),
+            (Self::German, 1) => String::from("(1 Feiertag ausgenommen)"),
+            (Self::German, _) => format!("({} Feiertage ausgenommen)", count),
+            (Self::Spanish, 1) => String::from("(1 festivo excluido)"),
+            (Self::Spanish, _) => format!("({} festivos excluidos)", count),
+        }
+    }
+
+    pub fn deadline_over(&self) -> &'static str {
+        match self {
+            Self::English => "Deadline is Over",
+            Self::Arabic => "انتهى الموعد النهائي",
+            Self::French => "Le délai est écoulé",
+            Self::German => "Die Frist ist abgelaufen",
+            Self::Spanish => "El plazo ha terminado",
+        }
+    }
+
+    pub fn fuzzy_qualifier(&self, qualifier: FuzzyQualifier) -> &'static str {
+        use FuzzyQualifier::*;
+
+        match (self, qualifier) {
+            (Self::English, About) => "About",
+            (Self::English, JustOver) => "Just over",
+            (Self::English, Almost) => "Almost",
+            (Self::English, LessThan) => "Less than",
+            (Self::Arabic, About) => "حوالي",
+            (Self::Arabic, JustOver) => "أكثر بقليل من",
+            (Self::Arabic, Almost) => "تقريبًا",
+            (Self::Arabic, LessThan) => "أقل من",
+            (Self::French, About) => "Environ",
+            (Self::French, JustOver) => "Un peu plus de",
+            (Self::French, Almost) => "Presque",
+            (Self::French, LessThan) => "Moins de",
+            (Self::German, About) => "Etwa",
+            (Self::German, JustOver) => "Knapp über",
+            (Self::German, Almost) => "Fast",
+            (Self::German, LessThan) => "Weniger als",
+            (Self::Spanish, About) => "Unos",
+            (Self::Spanish, JustOver) => "Poco más de",
+            (Self::Spanish, Almost) => "Casi",
+            (Self::Spanish, LessThan) => "Menos de",
+        }
+    }
+
+    /// Joins the formatted time units of a countdown.
+    pub fn separator(&self) -> &'static str {
+        match self {
+            Self::Arabic => " و",
+            _ => ", ",
+        }
+    }
+
+    /// Formats `count` of `unit` with the correct plural form, Ex: "2 Days" or "يومان".
+    pub fn format_unit(&self, unit: TimeUnit, count: i64) -> String {
+        let category = self.plural_category(count);
+
+        match self {
+            Self::English => {
+                let name = match unit {
+                    TimeUnit::Month => "Month",
+                    TimeUnit::Week => "Week",
+                    TimeUnit::Day => "Day",
+                    TimeUnit::Hour => "Hour",
+                    TimeUnit::Minute => "Minute",
+                    TimeUnit::Second => "Second",
+                };
+                let suffix = if category == PluralCategory::One {
+                    ""
+                } else {
+                    "s"
+                };
+
+                format!("{} {}{}", count, name, suffix)
+            }
+            Self::Arabic => {
+                // [one, two, few, many/other]
+                let forms = match unit {
+                    TimeUnit::Month => ["شهر", "شهران", "أشهر", "شهرًا", "شهر"],
+                    TimeUnit::Week => ["أسبوع", "أسبوعان", "أسابيع", "أسبوعًا", "أسبوع"],
+                    TimeUnit::Day => ["يوم", "يومان", "أيام", "يومًا", "يوم"],
+                    TimeUnit::Hour => ["ساعة", "ساعتان", "ساعات", "ساعة", "ساعة"],
+                    TimeUnit::Minute => ["دقيقة", "دقيقتان", "دقائق", "دقيقة", "دقيقة"],
+                    TimeUnit::Second => ["ثانية", "ثانيتان", "ثوانٍ", "ثانية", "ثانية"],
+                };
+
+                // Singular and dual forms are written without the number in Arabic
+                match category {
+                    PluralCategory::One => forms[0].to_string(),
+                    PluralCategory::Two => forms[1].to_string(),
+                    PluralCategory::Few => format!("{} {}", count, forms[2]),
+                    PluralCategory::Many => format!("{} {}", count, forms[3]),
+                    PluralCategory::Zero | PluralCategory::Other => {
+                        format!("{} {}", count, forms[4])
+                    }
+                }
+            }
+            Self::French => {
+                let (singular, plural) = match unit {
+                    TimeUnit::Month => ("mois", "mois"),
+                    TimeUnit::Week => ("semaine", "semaines"),
+                    TimeUnit::Day => ("jour", "jours"),
+                    TimeUnit::Hour => ("heure", "heures"),
+                    TimeUnit::Minute => ("minute", "minutes"),
+                    TimeUnit::Second => ("seconde", "secondes"),
+                };
+
+                Self::pick(count, category, singular, plural)
+            }
+            Self::German => {
+                let (singular, plural) = match unit {
+                    TimeUnit::Month => ("Monat", "Monate"),
+                    TimeUnit::Week => ("Woche", "Wochen"),
+                    TimeUnit::Day => ("Tag", "Tage"),
+                    TimeUnit::Hour => ("Stunde", "Stunden"),
+                    TimeUnit::Minute => ("Minute", "Minuten"),
+                    TimeUnit::Second => ("Sekunde", "Sekunden"),
+                };
+
+                Self::pick(count, category, singular, plural)
+            }
+            Self::Spanish => {
+                let (singular, plural) = match unit {
+                    TimeUnit::Month => ("mes", "meses"),
+                    TimeUnit::Week => ("semana", "semanas"),
+                    TimeUnit::Day => ("día", "días"),
+                    TimeUnit::Hour => ("hora", "horas"),
+                    TimeUnit::Minute => ("minuto", "minutos"),
+                    TimeUnit::Second => ("segundo", "segundos"),
+                };
+
+                Self::pick(count, category, singular, plural)
+            }
+        }
+    }
+
+    /// Short form of `count` of `unit` that doesn't change with the count, Ex: "2d" or "2 Std.".
+    pub fn format_compact_unit(&self, unit: TimeUnit, count: i64) -> String {
+        use TimeUnit::*;
+
+        let abbreviation = match (self, unit) {
+            (Self::English, Month) => "mo",
+            (Self::English, Week) => "w",
+            (Self::English, Day) => "d",
+            (Self::English, Hour) => "h",
+            (Self::English, Minute) => "m",
+            (Self::English, Second) => "s",
+            (Self::Arabic, Month) => "شهر",
+            (Self::Arabic, Week) => "أسبوع",
+            (Self::Arabic, Day) => "يوم",
+            (Self::Arabic, Hour) => "س",
+            (Self::Arabic, Minute) => "د",
+            (Self::Arabic, Second) => "ث",
+            (Self::French, Month) => "mois",
+            (Self::French, Week) => "sem.",
+            (Self::French, Day) => "j",
+            (Self::French, Hour) => "h",
+            (Self::French, Minute) => "min",
+            (Self::French, Second) => "s",
+            (Self::German, Month) => "Mon.",
+            (Self::German, Week) => "Wo.",
+            (Self::German, Day) => "Tg.",
+            (Self::German, Hour) => "Std.",
+            (Self::German, Minute) => "Min.",
+            (Self::German, Second) => "Sek.",
+            (Self::Spanish, Month) => "mes.",
+            (Self::Spanish, Week) => "sem.",
+            (Self::Spanish, Day) => "d",
+            (Self::Spanish, Hour) => "h",
+            (Self::Spanish, Minute) => "min",
+            (Self::Spanish, Second) => "s",
+        };
+
+        // Only English sticks the abbreviation to the number
+        if *self == Self::English {
+            format!("{}{}", count, abbreviation)
+        } else {
+            format!("{} {}", count, abbreviation)
+        }
+    }
+
+    fn pick(count: i64, category: PluralCategory, singular: &str, plural: &str) -> String {
+        if category == PluralCategory::One {
+            format!("{} {}", count, singular)
+        } else {
+            format!("{} {}", count, plural)
+        }
+    }
+
+    // Source: https://unicode-org.github.io/cldr-staging/charts/latest/supplemental/language_plural_rules.html
+    fn plural_category(&self, count: i64) -> PluralCategory {
+        let n = count.abs();
+
+        match self {
+            Self::Arabic => match n % 100 {
+                _ if n == 0 => PluralCategory::Zero,
+                _ if n == 1 => PluralCategory::One,
+                _ if n == 2 => PluralCategory::Two,
+                3..=10 => PluralCategory::Few,
+                11..=99 => PluralCategory::Many,
+                _ => PluralCategory::Other,
+            },
+            // French treats 0 as singular too
+            Self::French if n <= 1 => PluralCategory::One,
+            _ if n == 1 => PluralCategory::One,
+            _ => PluralCategory::Other,
+        }
+    }
+}
+
+/// Replaces the `{countdown}` variable of a template.
+pub fn fill_template(template: &str, countdown: &str) -> String {
+    template.replace("{countdown}", countdown)
+}
@@ -0,0 +1,25 @@
+/// Returns `Err($a)` from the enclosing function when `$e` fails.
+#[macro_export]
+macro_rules! unwrap_or_return {
+    ( $e:expr, $a: expr ) => {
+        match $e {
+            Ok(x) => x,
+            Err(_) => return Err(String::from($a)),
+        }
+    };
+}
+
+/// Logs a step of the rendering pipeline when verbose mode is on, Ex: `deadliner --dry-run -v`.
+/// The step is always remembered so a stuck refresh can tell where it got stuck.
+#[macro_export]
+macro_rules! trace {
+    ( $($arg: tt)* ) => {{
+        let step = format!($($arg)*);
+
+        if $crate::is_verbose() {
+            eprintln!("[trace] {}", step);
+        }
+
+        $crate::record_step(step);
+    }};
+}
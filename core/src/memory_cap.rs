@@ -0,0 +1,73 @@
+use std::{fs::File, io::BufReader, path::Path};
+
+use image::{codecs::jpeg::JpegDecoder, io::Reader, DynamicImage, ImageFormat};
+
+use crate::{trace, DeadlinerError};
+
+/// The pipeline works on RGBA copies of the background.
+const BYTES_PER_PIXEL: u64 = 4;
+
+/// Megabytes a `width`x`height` background takes once it's decoded.
+pub fn decoded_size_mb(width: u32, height: u32) -> u64 {
+    (width as u64 * height as u64 * BYTES_PER_PIXEL) / (1024 * 1024)
+}
+
+/// Decodes the image at `path` without going over `cap_mb` megabytes (0 doesn't cap it).
+/// JPEGs that wouldn't fit (Ex: 8K panoramas) are scaled down while they're decoded,
+/// other formats can only be decoded whole so they're refused instead.
+pub fn open_capped(path: &Path, cap_mb: u32, what: &str) -> Result<DynamicImage, DeadlinerError> {
+    let cant_open = |e| DeadlinerError::io(format!("Couldn't open {}", what.to_lowercase()), e);
+    let cant_decode = |e| DeadlinerError::decode(format!("{} can't be decoded", what), e);
+
+    let reader = Reader::open(path)
+        .and_then(|reader| reader.with_guessed_format())
+        .map_err(cant_open)?;
+    let format = reader.format();
+    let (width, height) = reader.into_dimensions().map_err(cant_decode)?;
+
+    let fits = |divisor: u32| {
+        cap_mb == 0 || decoded_size_mb(width / divisor, height / divisor) <= cap_mb as u64
+    };
+
+    if fits(1) {
+        return Reader::open(path)
+            .and_then(|reader| reader.with_guessed_format())
+            .map_err(cant_open)?
+            .decode()
+            .map_err(cant_decode);
+    }
+
+    // The scale factors JPEG can decode at
+    let divisor = [2, 4, 8].into_iter().find(|divisor| fits(*divisor));
+
+    match (format, divisor) {
+        (Some(ImageFormat::Jpeg), Some(divisor)) => {
+            trace!(
+                "{}x{} background would take {} MB, over the {} MB cap, decoding it at 1/{}",
+                width,
+                height,
+                decoded_size_mb(width, height),
+                cap_mb,
+                divisor
+            );
+
+            let file = File::open(path).map_err(cant_open)?;
+            let mut decoder = JpegDecoder::new(BufReader::new(file)).map_err(cant_decode)?;
+
+            decoder
+                .scale((width / divisor) as u16, (height / divisor) as u16)
+                .map_err(cant_decode)?;
+
+            DynamicImage::from_decoder(decoder).map_err(cant_decode)
+        }
+        _ => Err(DeadlinerError::Render(format!(
+            "{} is {}x{} and would take {} MB decoded, over the {} MB memory cap. \
+             Use a smaller image or raise the cap",
+            what,
+            width,
+            height,
+            decoded_size_mb(width, height),
+            cap_mb
+        ))),
+    }
+}
@@ -0,0 +1,125 @@
+use serde_json::{json, Map, Value};
+
+use crate::{archive_passed_deadlines, sanitize_inputs, DeadlinerConf, SanitizedConf};
+
+/// Bumped whenever a change to `DeadlinerConf` can't be read by a `#[serde(default)]`,
+/// with a migration added to `MIGRATIONS` that turns the previous version into it.
+pub const CONFIG_VERSION: u32 = 3;
+
+/// `MIGRATIONS[i]` turns a version `i + 1` config into a version `i + 2` one.
+const MIGRATIONS: &[fn(&mut Map<String, Value>)] =
+    &[single_to_multiple_deadlines, solid_color_with_pattern];
+
+pub(crate) fn current_config_version() -> u32 {
+    CONFIG_VERSION
+}
+
+/// Reads the inputs saved by any version of deadliner, configs without a version
+/// are from before versioning (version 1).
+pub fn migrate_config(conf_str: &str) -> Result<DeadlinerConf, String> {
+    let mut value: Value = serde_json::from_str(conf_str)
+        .map_err(|e| format!("The saved inputs aren't valid JSON: {}", e))?;
+
+    let conf = match value.as_object_mut() {
+        Some(conf) => conf,
+        None => return Err(String::from("The saved inputs aren't a JSON object")),
+    };
+
+    let version = conf.get("version").and_then(Value::as_u64).unwrap_or(1) as u32;
+
+    if version > CONFIG_VERSION {
+        return Err(format!(
+            "The saved inputs are from a newer deadliner (version {}), update it to read them",
+            version
+        ));
+    }
+
+    for migration in &MIGRATIONS[(version.max(1) - 1) as usize..] {
+        migration(conf);
+    }
+    conf.insert(String::from("version"), json!(CONFIG_VERSION));
+
+    serde_json::from_value(value).map_err(|e| format!("The saved inputs can't be migrated: {}", e))
+}
+
+/// `conf_str` when it's a config.json written by this version.
+pub(crate) fn current_sanitized_conf(conf_str: &str) -> Option<SanitizedConf> {
+    serde_json::from_str::<SanitizedConf>(conf_str)
+        .ok()
+        .filter(|conf| conf.version == CONFIG_VERSION)
+}
+
+/// Reads the config.json saved by any version of deadliner. It's derived from the inputs,
+/// so one from an older version, Ex: with the single `deadline_str` of version 1, is sanitized
+/// again from `raw_conf_str`, the inputs that were saved with it.
+pub fn migrate_sanitized_conf(
+    conf_str: &str,
+    raw_conf_str: Option<&str>,
+) -> Result<SanitizedConf, String> {
+    if let Some(conf) = current_sanitized_conf(conf_str) {
+        return Ok(conf);
+    }
+
+    let version = serde_json::from_str::<Value>(conf_str)
+        .ok()
+        .and_then(|conf| conf.get("version").and_then(Value::as_u64))
+        .unwrap_or(1) as u32;
+
+    if version > CONFIG_VERSION {
+        return Err(format!(
+            "The config is from a newer deadliner (version {}), update it to read it",
+            version
+        ));
+    }
+
+    let raw_conf_str = raw_conf_str.ok_or_else(|| {
+        String::from("The config is from an older deadliner, open it and save the inputs again")
+    })?;
+
+    let mut conf = migrate_config(raw_conf_str)?;
+
+    // Deadlines that went by since the inputs were saved would fail the sanitizing
+    archive_passed_deadlines(&mut conf);
+
+    sanitize_inputs(&conf)
+}
+
+/// Version 1 had a single deadline as top-level fields and no languages or templates.
+fn single_to_multiple_deadlines(conf: &mut Map<String, Value>) {
+    if !conf.contains_key("deadlines") {
+        let mut deadline = Map::new();
+
+        for field in ["date", "hours", "minutes", "period"] {
+            if let Some(value) = conf.remove(field) {
+                deadline.insert(String::from(field), value);
+            }
+        }
+
+        let deadlines = if deadline.is_empty() {
+            vec![]
+        } else {
+            vec![Value::Object(deadline)]
+        };
+        conf.insert(String::from("deadlines"), Value::Array(deadlines));
+    }
+
+    if let Some(Value::Array(deadlines)) = conf.get_mut("deadlines") {
+        for deadline in deadlines.iter_mut().filter_map(Value::as_object_mut) {
+            deadline.entry("locale").or_insert_with(|| json!("English"));
+            deadline.entry("template").or_insert_with(|| json!(""));
+        }
+    }
+}
+
+/// Version 2 had the solid background's color as its only field, before patterns existed.
+fn solid_color_with_pattern(conf: &mut Map<String, Value>) {
+    if let Some(Value::Object(background)) = conf.get_mut("default_background") {
+        if let Some(color) = background
+            .get("Solid")
+            .filter(|color| color.is_array())
+            .cloned()
+        {
+            background.insert(String::from("Solid"), json!({ "color": color }));
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use image::{imageops::FilterType, DynamicImage, GenericImageView, Rgba, RgbaImage};
+
+use crate::{ScreenDimensions, WallpaperMode};
+
+/// What the OS shows on a `desktop` sized screen when `image` is set with `mode`,
+/// scaled down to `preview_width` pixels wide.
+/// Span stretches over every monitor so `desktop` is their combined bounds for it.
+pub fn simulate_wallpaper_mode(
+    image: &DynamicImage,
+    mode: WallpaperMode,
+    desktop: ScreenDimensions,
+    preview_width: u32,
+) -> DynamicImage {
+    let scale = preview_width as f32 / desktop.width.max(1) as f32;
+    let width = preview_width.max(1);
+    let height = ((desktop.height as f32 * scale) as u32).max(1);
+
+    match mode {
+        WallpaperMode::Crop | WallpaperMode::Span => {
+            image.resize_to_fill(width, height, FilterType::Triangle)
+        }
+        WallpaperMode::Fit => centered(
+            &image.resize(width, height, FilterType::Triangle),
+            width,
+            height,
+        ),
+        // Shown at its own size, the desktop's color fills the rest
+        WallpaperMode::Center => {
+            let image_width = ((image.width() as f32 * scale) as u32).max(1);
+            let image_height = ((image.height() as f32 * scale) as u32).max(1);
+
+            centered(
+                &image.resize_exact(image_width, image_height, FilterType::Triangle),
+                width,
+                height,
+            )
+        }
+    }
+}
+
+/// `image` in the middle of a black `width`x`height` canvas, cut where it doesn't fit.
+fn centered(image: &DynamicImage, width: u32, height: u32) -> DynamicImage {
+    let mut canvas = RgbaImage::from_pixel(width, height, Rgba([0, 0, 0, 255]));
+
+    let x = (width as i64 - image.width() as i64) / 2;
+    let y = (height as i64 - image.height() as i64) / 2;
+
+    for (px, py, pixel) in image.pixels() {
+        let (cx, cy) = (x + px as i64, y + py as i64);
+
+        if (0..width as i64).contains(&cx) && (0..height as i64).contains(&cy) {
+            canvas.put_pixel(cx as u32, cy as u32, pixel);
+        }
+    }
+
+    DynamicImage::ImageRgba8(canvas)
+}
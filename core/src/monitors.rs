@@ -0,0 +1,145 @@
+use image::DynamicImage;
+use serde::{Deserialize, Serialize};
+
+use crate::{trace, ScreenDimensions};
+
+/// Per-monitor settings, the position is relative to the virtual desktop
+/// that spans all of the monitors.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct MonitorConf {
+    pub name: String,
+    pub position: [i32; 2],
+    pub dimensions: ScreenDimensions,
+
+    /// Monitors that aren't enabled only get the background without a countdown.
+    pub enabled: bool,
+    /// Index of the only deadline shown on this monitor, `None` shows all of them.
+    pub deadline: Option<usize>,
+
+    /// Corrects a poorly calibrated monitor so the wallpaper looks the same on every screen,
+    /// above 1 brightens the midtones and below 1 darkens them.
+    #[serde(default = "default_gamma")]
+    pub gamma: f32,
+    /// From -100 (black) to 100 (white), added after the gamma.
+    #[serde(default)]
+    pub brightness: i8,
+}
+
+fn default_gamma() -> f32 {
+    1.
+}
+
+impl MonitorConf {
+    pub fn new(name: String, position: [i32; 2], dimensions: ScreenDimensions) -> MonitorConf {
+        MonitorConf {
+            name,
+            position,
+            dimensions,
+            enabled: true,
+            deadline: None,
+            gamma: default_gamma(),
+            brightness: 0,
+        }
+    }
+
+    fn is_calibrated(&self) -> bool {
+        self.gamma != default_gamma() || self.brightness != 0
+    }
+
+    /// Applies the monitor's gamma and brightness to its part of the wallpaper.
+    pub fn calibrate(&self, mut image: DynamicImage) -> DynamicImage {
+        if !self.is_calibrated() {
+            return image;
+        }
+
+        trace!(
+            "calibrating monitor \"{}\": gamma {}, brightness {}",
+            self.name,
+            self.gamma,
+            self.brightness
+        );
+
+        let gamma = self.gamma.clamp(0.1, 10.);
+        let brightness = self.brightness.clamp(-100, 100) as f32 * 2.55;
+
+        let mut lookup = [0u8; 256];
+        for (value, calibrated) in lookup.iter_mut().enumerate() {
+            let corrected = (value as f32 / 255.).powf(1. / gamma) * 255.;
+            *calibrated = (corrected + brightness).round().clamp(0., 255.) as u8;
+        }
+
+        if !matches!(
+            image,
+            DynamicImage::ImageRgb8(_) | DynamicImage::ImageRgba8(_)
+        ) {
+            image = DynamicImage::ImageRgba8(image.to_rgba8());
+        }
+
+        // Only the color channels, the alpha is kept for the spanned overlay
+        match &mut image {
+            DynamicImage::ImageRgb8(rgb) => {
+                for pixel in rgb.pixels_mut() {
+                    pixel.0.iter_mut().for_each(|c| *c = lookup[*c as usize]);
+                }
+            }
+            DynamicImage::ImageRgba8(rgba) => {
+                for pixel in rgba.pixels_mut() {
+                    pixel.0[..3]
+                        .iter_mut()
+                        .for_each(|c| *c = lookup[*c as usize]);
+                }
+            }
+            _ => unreachable!(),
+        }
+
+        image
+    }
+
+    pub fn shows_deadline(&self, index: usize) -> bool {
+        self.enabled && self.deadline.is_none_or(|deadline| deadline == index)
+    }
+}
+
+/// Keeps the settings of the monitors that are still connected,
+/// matched by their name, and adds the newly detected ones with the default settings.
+pub fn merge_monitors(detected: Vec<MonitorConf>, saved: &[MonitorConf]) -> Vec<MonitorConf> {
+    detected
+        .into_iter()
+        .map(
+            |monitor| match saved.iter().find(|s| s.name == monitor.name) {
+                Some(saved) => MonitorConf {
+                    enabled: saved.enabled,
+                    deadline: saved.deadline,
+                    gamma: saved.gamma,
+                    brightness: saved.brightness,
+                    ..monitor
+                },
+                None => monitor,
+            },
+        )
+        .collect()
+}
+
+/// Returns the top-left corner and the dimensions of the virtual desktop spanning all monitors.
+pub fn spanned_bounds(monitors: &[MonitorConf]) -> ([i32; 2], ScreenDimensions) {
+    let min_x = monitors.iter().map(|m| m.position[0]).min().unwrap_or(0);
+    let min_y = monitors.iter().map(|m| m.position[1]).min().unwrap_or(0);
+    let max_x = monitors
+        .iter()
+        .map(|m| m.position[0] + m.dimensions.width as i32)
+        .max()
+        .unwrap_or(0);
+    let max_y = monitors
+        .iter()
+        .map(|m| m.position[1] + m.dimensions.height as i32)
+        .max()
+        .unwrap_or(0);
+
+    (
+        [min_x, min_y],
+        ScreenDimensions {
+            width: (max_x - min_x) as u32,
+            height: (max_y - min_y) as u32,
+        },
+    )
+}
@@ -0,0 +1,147 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+
+/// Parses dates typed like "next friday 5pm", "in 3 weeks", "end of month" or "2022-08-26 17:30",
+/// relative to `now`. The time is `None` when the input only names a day.
+pub fn parse_natural_date(
+    input: &str,
+    now: NaiveDateTime,
+) -> Option<(NaiveDate, Option<NaiveTime>)> {
+    let input = input.trim().to_lowercase();
+
+    // "5 pm" reads the same as "5pm"
+    let mut words: Vec<String> = Vec::new();
+    for word in input.split_whitespace() {
+        match (word, words.last_mut()) {
+            ("am" | "pm", Some(last)) => last.push_str(word),
+            ("at" | "by" | "on" | "the", _) => (),
+            _ => words.push(word.to_string()),
+        }
+    }
+
+    // The time is always at the end, Ex: "tomorrow 9am"
+    let time = words.last().and_then(|word| parse_time(word));
+    if time.is_some() {
+        words.pop();
+    }
+
+    let words: Vec<&str> = words.iter().map(|word| word.as_str()).collect();
+    let today = now.date();
+
+    let date = match words.as_slice() {
+        [] if time.is_some() => today,
+        ["today"] => today,
+        ["tomorrow"] => today + Duration::days(1),
+        [date] if NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok() => {
+            NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?
+        }
+        ["in", count, unit] => {
+            let count: i64 = count.parse().ok()?;
+
+            match unit.trim_end_matches('s') {
+                // Hours and minutes from now already have a time
+                "minute" | "min" => {
+                    let date = now + Duration::minutes(count);
+                    return Some((date.date(), Some(date.time())));
+                }
+                "hour" => {
+                    let date = now + Duration::hours(count);
+                    return Some((date.date(), Some(date.time())));
+                }
+                "day" => today + Duration::days(count),
+                "week" => today + Duration::weeks(count),
+                "month" => add_months(today, count as i32)?,
+                "year" => add_months(today, count as i32 * 12)?,
+                _ => return None,
+            }
+        }
+        ["next", "week"] => today + Duration::weeks(1),
+        ["next", "month"] => add_months(today, 1)?,
+        ["next", "year"] => add_months(today, 12)?,
+        ["end", "of", period] => match *period {
+            "week" => {
+                let days_till_sunday = 6 - today.weekday().num_days_from_monday() as i64;
+                today + Duration::days(days_till_sunday)
+            }
+            "month" => add_months(today.with_day(1)?, 1)? - Duration::days(1),
+            "year" => NaiveDate::from_ymd_opt(today.year(), 12, 31)?,
+            _ => return None,
+        },
+        ["next", weekday] => next_weekday(today, parse_weekday(weekday)?, false),
+        ["this", weekday] | [weekday] => next_weekday(today, parse_weekday(weekday)?, true),
+        _ => return None,
+    };
+
+    Some((date, time))
+}
+
+fn parse_time(word: &str) -> Option<NaiveTime> {
+    match word {
+        "noon" => return NaiveTime::from_hms_opt(12, 0, 0),
+        "midnight" => return NaiveTime::from_hms_opt(23, 59, 0),
+        _ => (),
+    }
+
+    let (clock, period) = if let Some(clock) = word.strip_suffix("am") {
+        (clock, Some(false))
+    } else if let Some(clock) = word.strip_suffix("pm") {
+        (clock, Some(true))
+    } else {
+        (word, None)
+    };
+
+    let (hours, minutes) = match clock.split_once(':') {
+        Some((hours, minutes)) => (hours.parse::<u32>().ok()?, minutes.parse::<u32>().ok()?),
+        // A lone number is only a time with a period, Ex: "5pm" but not "5"
+        None if period.is_some() => (clock.parse::<u32>().ok()?, 0),
+        None => return None,
+    };
+
+    let hours = match period {
+        Some(is_pm) if (1..=12).contains(&hours) => hours % 12 + if is_pm { 12 } else { 0 },
+        Some(_) => return None,
+        None => hours,
+    };
+
+    NaiveTime::from_hms_opt(hours, minutes, 0)
+}
+
+fn parse_weekday(word: &str) -> Option<Weekday> {
+    let weekdays = [
+        ("monday", Weekday::Mon),
+        ("tuesday", Weekday::Tue),
+        ("wednesday", Weekday::Wed),
+        ("thursday", Weekday::Thu),
+        ("friday", Weekday::Fri),
+        ("saturday", Weekday::Sat),
+        ("sunday", Weekday::Sun),
+    ];
+
+    // Accept abbreviations like "fri" or "thurs"
+    weekdays
+        .iter()
+        .find(|(name, _)| word.len() >= 3 && name.starts_with(word))
+        .map(|(_, weekday)| *weekday)
+}
+
+/// The closest `weekday` from `today`, including today only when `include_today` is set.
+fn next_weekday(today: NaiveDate, weekday: Weekday, include_today: bool) -> NaiveDate {
+    let current = today.weekday().num_days_from_monday() as i64;
+    let target = weekday.num_days_from_monday() as i64;
+
+    let mut days = (target - current).rem_euclid(7);
+    if days == 0 && !include_today {
+        days = 7;
+    }
+
+    today + Duration::days(days)
+}
+
+/// Adds `months` keeping the day of the month, clamped to the last day of shorter months.
+fn add_months(date: NaiveDate, months: i32) -> Option<NaiveDate> {
+    let total = date.year() * 12 + date.month0() as i32 + months;
+    let (year, month) = (total.div_euclid(12), total.rem_euclid(12) as u32 + 1);
+
+    (0..4)
+        .map(|i| date.day().saturating_sub(i))
+        .find_map(|day| NaiveDate::from_ymd_opt(year, month, day))
+}
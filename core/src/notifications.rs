@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{announcement, SanitizedConf, SanitizedDeadline};
+
+/// Desktop notifications shown when a deadline gets close, whether or not the wallpaper
+/// is refreshed at that moment.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct NotificationsConf {
+    pub enabled: bool,
+    /// Minutes left at which a notification is shown, 0 is when the deadline is over.
+    pub milestones: Vec<u32>,
+}
+
+impl Default for NotificationsConf {
+    fn default() -> Self {
+        NotificationsConf {
+            enabled: false,
+            milestones: vec![7 * 24 * 60, 24 * 60, 60, 0],
+        }
+    }
+}
+
+/// Summary and body of the notification shown when `deadline` reaches `minutes` left
+/// if that's one of the milestones.
+pub fn milestone_notification(
+    conf: &SanitizedConf,
+    deadline: &SanitizedDeadline,
+    minutes: u32,
+) -> Option<(String, String)> {
+    if !conf.notifications.enabled || !conf.notifications.milestones.contains(&minutes) {
+        return None;
+    }
+
+    let summary = if deadline.title.is_empty() {
+        String::from("⌚ Deadliner")
+    } else {
+        format!("⌚ {}", deadline.title)
+    };
+
+    let body = if minutes == 0 {
+        deadline.locale.deadline_over().to_string()
+    } else {
+        announcement(deadline.locale, minutes as i64)
+    };
+
+    Some((summary, body))
+}
+
+impl NotificationsConf {
+    /// Whether a notification is already shown when the deadline is over.
+    pub fn notifies_when_over(&self) -> bool {
+        self.enabled && self.milestones.contains(&0)
+    }
+}
@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use strum_macros::EnumIter;
+
+use crate::{http_client, send_with_retry, ScreenDimensions, SendError};
+
+/// Built-in sources of a fresh background photo every day.
+#[derive(Debug, PartialEq, Clone, Copy, EnumIter, Serialize, Deserialize, Default)]
+pub enum OnlineSource {
+    /// A random photo matching a keyword.
+    #[default]
+    Unsplash,
+    /// Bing's image of the day.
+    Bing,
+    /// NASA's astronomy picture of the day.
+    Nasa,
+}
+
+impl OnlineSource {
+    /// URL of the source's current image, Bing and NASA are asked for it first.
+    pub fn image_url(
+        &self,
+        keyword: &str,
+        dimensions: ScreenDimensions,
+        timeout_secs: u32,
+    ) -> Result<String, String> {
+        match self {
+            OnlineSource::Unsplash => Ok(format!(
+                "https://source.unsplash.com/random/{}x{}/?{}",
+                dimensions.width,
+                dimensions.height,
+                keyword.trim().replace(' ', ",")
+            )),
+            OnlineSource::Bing => {
+                let archive = fetch_json(
+                    "https://www.bing.com/HPImageArchive.aspx?format=js&idx=0&n=1&mkt=en-US",
+                    timeout_secs,
+                )?;
+
+                match archive["images"][0]["url"].as_str() {
+                    Some(path) => Ok(format!("https://www.bing.com{}", path)),
+                    None => Err(String::from("Bing didn't share an image of the day!")),
+                }
+            }
+            OnlineSource::Nasa => {
+                let apod = fetch_json(
+                    "https://api.nasa.gov/planetary/apod?api_key=DEMO_KEY",
+                    timeout_secs,
+                )?;
+
+                // Some days the picture of the day is a video
+                if apod["media_type"].as_str() != Some("image") {
+                    return Err(String::from(
+                        "NASA's picture of the day isn't an image today!",
+                    ));
+                }
+
+                match apod["hdurl"].as_str().or(apod["url"].as_str()) {
+                    Some(url) => Ok(url.to_string()),
+                    None => Err(String::from("NASA didn't share a picture of the day!")),
+                }
+            }
+        }
+    }
+}
+
+fn fetch_json(url: &str, timeout_secs: u32) -> Result<Value, String> {
+    let client = http_client(timeout_secs);
+
+    let body = match send_with_retry(|| client.get(url)) {
+        Ok(response) => response
+            .text()
+            .map_err(|_| format!("Couldn't reach {}", url))?,
+        Err(SendError::RateLimited(msg)) => return Err(msg),
+        Err(_) => return Err(format!("Couldn't reach {}", url)),
+    };
+
+    serde_json::from_str(&body).map_err(|_| format!("{} didn't respond with JSON", url))
+}
@@ -0,0 +1,90 @@
+use std::{fs, path::Path};
+
+use wallpaper::Mode;
+
+use crate::{
+    get_cache_dir, get_desktop_wallpaper, get_desktop_wallpaper_mode, remove_sanitized_conf,
+    set_desktop_wallpaper, stop_schedular, unwrap_or_return,
+};
+
+/// The path on the first line and the mode on the second,
+/// files saved before the mode was kept only have the path.
+const ORIGINAL_WALLPAPER_FILE: &str = "original_wallpaper.txt";
+
+fn mode_name(mode: &Mode) -> &'static str {
+    match mode {
+        Mode::Center => "center",
+        Mode::Crop => "crop",
+        Mode::Fit => "fit",
+        Mode::Span => "span",
+        Mode::Stretch => "stretch",
+        Mode::Tile => "tile",
+    }
+}
+
+fn parse_mode(name: &str) -> Option<Mode> {
+    match name.trim() {
+        "center" => Some(Mode::Center),
+        "crop" => Some(Mode::Crop),
+        "fit" => Some(Mode::Fit),
+        "span" => Some(Mode::Span),
+        "stretch" => Some(Mode::Stretch),
+        "tile" => Some(Mode::Tile),
+        _ => None,
+    }
+}
+
+/// Remembers the user's wallpaper before deadliner replaces it for the first time.
+pub fn save_original_wallpaper() {
+    let saved = get_cache_dir().join(ORIGINAL_WALLPAPER_FILE);
+
+    if saved.exists() {
+        return;
+    }
+
+    if let Ok(current) = get_desktop_wallpaper() {
+        // Never remember one of our own renders as the original wallpaper
+        if current.is_empty() || Path::new(&current).starts_with(get_cache_dir()) {
+            return;
+        }
+
+        // Desktops whose mode can't be read crop, like most of them do by default
+        let mode = get_desktop_wallpaper_mode().unwrap_or(Mode::Crop);
+
+        fs::write(saved, format!("{}\n{}", current, mode_name(&mode))).ok();
+    }
+}
+
+/// Sets the remembered wallpaper back, the next wallpaper update remembers it again.
+pub fn restore_original_wallpaper() -> Result<(), String> {
+    let saved = get_cache_dir().join(ORIGINAL_WALLPAPER_FILE);
+
+    let original = unwrap_or_return!(
+        fs::read_to_string(&saved),
+        "There's no original wallpaper to restore!"
+    );
+
+    let mut lines = original.lines();
+    let path = lines.next().unwrap_or_default().trim();
+    let mode = lines.next().and_then(parse_mode).unwrap_or(Mode::Crop);
+
+    unwrap_or_return!(
+        set_desktop_wallpaper(path, mode),
+        "Couldn't restore the original wallpaper!"
+    );
+
+    fs::remove_file(saved).ok();
+
+    Ok(())
+}
+
+/// Stops the countdown for good and puts the user's own wallpaper back,
+/// used by the GUI and `deadliner restore-wallpaper` (Ex: before uninstalling).
+pub fn stop_and_restore_original_wallpaper() -> Result<(), String> {
+    stop_schedular();
+
+    // Without a config the schedular doesn't render the countdown again on the next launch
+    remove_sanitized_conf();
+
+    restore_original_wallpaper()
+}
@@ -0,0 +1,232 @@
+use std::{
+    fs::{self, File},
+    io::BufWriter,
+    path::Path,
+    process::Command,
+};
+
+use image::DynamicImage;
+use reqwest::header::CONTENT_TYPE;
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+use wallpaper::Mode;
+
+use crate::{
+    encode_png, get_cache_dir, http_client, in_memory_setter_available, save_original_wallpaper,
+    save_wallpaper, send_with_retry, set_desktop_wallpaper, set_wallpaper_from_memory, trace,
+    unwrap_or_return, SanitizedConf,
+};
+
+/// Where a rendered wallpaper goes, several of them can be picked at once.
+#[derive(Debug, PartialEq, Clone, EnumIter, Serialize, Deserialize, Default)]
+pub enum OutputTargetConf {
+    #[default]
+    Desktop,
+    LockScreen,
+    /// Saves the render as a PNG, Ex: for another app to pick it up.
+    File(String),
+    /// POSTs the render as a PNG to the URL.
+    Http(String),
+}
+
+impl std::fmt::Display for OutputTargetConf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Desktop => "Desktop",
+                Self::LockScreen => "Lock Screen",
+                Self::File(_) => "File",
+                Self::Http(_) => "HTTP Endpoint",
+            }
+        )
+    }
+}
+
+impl OutputTargetConf {
+    pub fn sanitized(&self) -> Result<OutputTargetConf, String> {
+        Ok(match self {
+            Self::File(path) if path.trim().is_empty() => {
+                return Err(String::from("Pick a file to export the wallpaper to!"))
+            }
+            Self::Http(url) if !url.trim().starts_with("http") => {
+                return Err(String::from(
+                    "The HTTP output's URL should start with http:// or https://",
+                ))
+            }
+            Self::File(path) => Self::File(path.trim().to_string()),
+            Self::Http(url) => Self::Http(url.trim().to_string()),
+            target => target.clone(),
+        })
+    }
+
+    fn target(&self) -> Box<dyn OutputTarget> {
+        match self {
+            Self::Desktop => Box::new(DesktopTarget),
+            Self::LockScreen => Box::new(LockScreenTarget),
+            Self::File(path) => Box::new(FileTarget { path: path.clone() }),
+            Self::Http(url) => Box::new(HttpTarget { url: url.clone() }),
+        }
+    }
+}
+
+pub fn sanitize_output_targets(
+    targets: &[OutputTargetConf],
+) -> Result<Vec<OutputTargetConf>, String> {
+    if targets.is_empty() {
+        return Err(String::from("Pick at least one output for the wallpaper!"));
+    }
+
+    targets.iter().map(|target| target.sanitized()).collect()
+}
+
+/// Somewhere a rendered wallpaper can be delivered to.
+pub trait OutputTarget {
+    fn deliver(
+        &self,
+        wallpaper: &DynamicImage,
+        mode: Mode,
+        conf: &SanitizedConf,
+    ) -> Result<(), String>;
+}
+
+/// Hands the wallpaper to every picked target, one failing target doesn't stop the others.
+pub fn deliver_to_targets(
+    wallpaper: &DynamicImage,
+    mode: Mode,
+    conf: &SanitizedConf,
+) -> Result<(), String> {
+    let errors: Vec<String> = conf
+        .output_targets
+        .iter()
+        .filter_map(|target| {
+            trace!("delivering the wallpaper to {}", target);
+
+            target
+                .target()
+                .deliver(wallpaper, mode.clone(), conf)
+                .err()
+                .map(|e| format!("{}: {}", target, e))
+        })
+        .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("\n"))
+    }
+}
+
+fn save_png(wallpaper: &DynamicImage, conf: &SanitizedConf, path: &Path) -> Result<(), String> {
+    let file = unwrap_or_return!(
+        File::create(path),
+        format!("Couldn't save {}", path.display())
+    );
+
+    encode_png(wallpaper, conf.png_compression, BufWriter::new(file))
+}
+
+struct DesktopTarget;
+
+impl OutputTarget for DesktopTarget {
+    fn deliver(
+        &self,
+        wallpaper: &DynamicImage,
+        mode: Mode,
+        conf: &SanitizedConf,
+    ) -> Result<(), String> {
+        save_original_wallpaper();
+
+        if in_memory_setter_available() {
+            return set_wallpaper_from_memory(wallpaper, mode, conf.png_compression);
+        }
+
+        let file_path = save_wallpaper(wallpaper, conf)?;
+
+        set_desktop_wallpaper(&file_path, mode)
+    }
+}
+
+struct LockScreenTarget;
+
+impl OutputTarget for LockScreenTarget {
+    fn deliver(
+        &self,
+        wallpaper: &DynamicImage,
+        _: Mode,
+        conf: &SanitizedConf,
+    ) -> Result<(), String> {
+        if !cfg!(target_os = "linux") {
+            return Err(String::from(
+                "Setting the lock screen is only supported on GNOME for now",
+            ));
+        }
+
+        let path = get_cache_dir().join("lock_screen.png");
+        save_png(wallpaper, conf, &path)?;
+
+        let status = Command::new("gsettings")
+            .args([
+                "set",
+                "org.gnome.desktop.screensaver",
+                "picture-uri",
+                &format!("file://{}", path.display()),
+            ])
+            .status();
+
+        match status {
+            Ok(status) if status.success() => Ok(()),
+            _ => Err(String::from("Couldn't set the GNOME lock screen")),
+        }
+    }
+}
+
+struct FileTarget {
+    path: String,
+}
+
+impl OutputTarget for FileTarget {
+    fn deliver(
+        &self,
+        wallpaper: &DynamicImage,
+        _: Mode,
+        conf: &SanitizedConf,
+    ) -> Result<(), String> {
+        let path = Path::new(&self.path);
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).ok();
+        }
+
+        save_png(wallpaper, conf, path)
+    }
+}
+
+struct HttpTarget {
+    url: String,
+}
+
+impl OutputTarget for HttpTarget {
+    fn deliver(
+        &self,
+        wallpaper: &DynamicImage,
+        _: Mode,
+        conf: &SanitizedConf,
+    ) -> Result<(), String> {
+        let mut png = Vec::new();
+        encode_png(wallpaper, conf.png_compression, &mut png)?;
+
+        let client = http_client(conf.download_timeout_secs);
+
+        unwrap_or_return!(
+            send_with_retry(|| client
+                .post(&self.url)
+                .header(CONTENT_TYPE, "image/png")
+                .body(png.clone())),
+            format!("Couldn't send the wallpaper to {}", self.url)
+        );
+
+        Ok(())
+    }
+}
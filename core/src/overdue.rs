@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+
+/// What happens to the wallpaper once a deadline is over.
+#[derive(Debug, PartialEq, Clone, EnumIter, Serialize, Deserialize, Default)]
+pub enum OverdueBehavior {
+    /// Shows "Deadline is Over" between party poppers.
+    #[default]
+    Celebrate,
+    /// Keeps counting up, Ex: "3 Days overdue".
+    CountUp,
+    /// Shows a custom message instead of the countdown, Ex: "Done!".
+    Message(String),
+    /// Reverts to the wallpaper the user had before using deadliner.
+    RestoreOriginal,
+    /// Keeps the background but clears the countdown text entirely.
+    ClearText,
+}
+
+impl std::fmt::Display for OverdueBehavior {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Celebrate => "Celebrate",
+                Self::CountUp => "Count Up",
+                Self::Message(_) => "Custom Message",
+                Self::RestoreOriginal => "Restore Wallpaper",
+                Self::ClearText => "Clear Text",
+            }
+        )
+    }
+}
+
+impl OverdueBehavior {
+    /// Whether the wallpaper keeps getting updated after all of the deadlines are over.
+    pub fn keeps_running(&self) -> bool {
+        matches!(self, Self::CountUp | Self::Message(_))
+    }
+}
@@ -0,0 +1,60 @@
+use image::{Rgb, RgbImage};
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+
+/// A subtle texture drawn over a solid color background.
+#[derive(Debug, PartialEq, Clone, Copy, EnumIter, Serialize, Deserialize, Default)]
+pub enum Pattern {
+    #[default]
+    None,
+    /// Grain like printed paper.
+    Noise,
+    DiagonalLines,
+    Dots,
+}
+
+/// How much a pattern's pixel differs from the picked color, kept low so the text stays readable.
+const PATTERN_STRENGTH: i16 = 10;
+
+/// Renders `rgb` at the wallpaper's resolution with `pattern` over it,
+/// the pattern is the same on every render so it doesn't flicker between updates.
+pub fn render_pattern(width: u32, height: u32, rgb: [u8; 3], pattern: Pattern) -> RgbImage {
+    // Spacing scales with the screen so the pattern looks the same on every resolution
+    let spacing = (height / 50).max(8);
+    let thickness = (spacing / 8).max(1);
+
+    // Light colors get darker patterns, dark ones get lighter ones
+    let luminance = rgb.iter().map(|c| *c as u32).sum::<u32>() / 3;
+    let direction = if luminance > 127 { -1 } else { 1 };
+
+    RgbImage::from_fn(width, height, |x, y| {
+        let offset = match pattern {
+            Pattern::None => 0,
+            Pattern::Noise => (noise(x, y) % (PATTERN_STRENGTH as u32 + 1)) as i16,
+            Pattern::DiagonalLines if (x + y) % spacing < thickness => PATTERN_STRENGTH,
+            Pattern::Dots => {
+                let (dx, dy) = (
+                    (x % spacing) as i32 - spacing as i32 / 2,
+                    (y % spacing) as i32 - spacing as i32 / 2,
+                );
+
+                if dx * dx + dy * dy <= (thickness * thickness) as i32 {
+                    PATTERN_STRENGTH
+                } else {
+                    0
+                }
+            }
+            Pattern::DiagonalLines => 0,
+        };
+
+        Rgb(rgb.map(|c| (c as i16 + direction * offset).clamp(0, 255) as u8))
+    })
+}
+
+/// A cheap hash of the pixel's position, random looking but stable across renders.
+fn noise(x: u32, y: u32) -> u32 {
+    let mut hash = x.wrapping_mul(374_761_393) ^ y.wrapping_mul(668_265_263);
+    hash = (hash ^ (hash >> 13)).wrapping_mul(1_274_126_177);
+
+    hash ^ (hash >> 16)
+}
@@ -0,0 +1,29 @@
+use std::fs;
+
+use crate::{get_config_dir, trace, unwrap_or_return};
+
+/// Exists while updates are paused, shared by the GUI, the tray and the schedular.
+const PAUSED_FILE: &str = "paused";
+
+/// Whether the user paused the wallpaper updates, Ex: while presenting their screen.
+pub fn is_paused() -> bool {
+    get_config_dir().join(PAUSED_FILE).exists()
+}
+
+/// Pauses or resumes the wallpaper updates, the saved inputs are kept either way.
+pub fn set_paused(paused: bool) -> Result<(), String> {
+    let paused_file = get_config_dir().join(PAUSED_FILE);
+
+    trace!(
+        "{} the wallpaper updates",
+        if paused { "pausing" } else { "resuming" }
+    );
+
+    if paused {
+        unwrap_or_return!(fs::write(paused_file, ""), "Couldn't pause the updates!");
+    } else if paused_file.exists() {
+        unwrap_or_return!(fs::remove_file(paused_file), "Couldn't resume the updates!");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use image::imageops::FilterType;
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+
+use crate::{ImageFilters, TimeUnit};
+
+/// How much CPU a refresh may take, old hardware can stick to a basic countdown
+/// while the rest get smoother text and scaling.
+#[derive(Debug, PartialEq, Clone, Copy, EnumIter, Serialize, Deserialize, Default)]
+pub enum PerformanceBudget {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+impl PerformanceBudget {
+    /// Used to scale the background to the screen.
+    pub fn resize_filter(&self) -> FilterType {
+        match self {
+            Self::Low => FilterType::Nearest,
+            Self::Normal => FilterType::Triangle,
+            Self::High => FilterType::Lanczos3,
+        }
+    }
+
+    /// The blur is skipped on a low budget, it's by far the slowest filter on big backgrounds.
+    pub fn background_filters(&self, filters: ImageFilters) -> ImageFilters {
+        match self {
+            Self::Low => ImageFilters {
+                blur: 0.,
+                ..filters
+            },
+            _ => filters,
+        }
+    }
+
+    /// The text is rendered this many times bigger and scaled down for smoother edges.
+    pub fn supersampling(&self) -> u8 {
+        match self {
+            Self::High => 2,
+            _ => 1,
+        }
+    }
+
+    /// A low budget refreshes at most every minute, so seconds aren't shown.
+    pub fn smallest_unit(&self, unit: TimeUnit) -> TimeUnit {
+        match self {
+            Self::Low if unit.seconds() < TimeUnit::Minute.seconds() => TimeUnit::Minute,
+            _ => unit,
+        }
+    }
+}
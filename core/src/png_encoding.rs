@@ -0,0 +1,47 @@
+use std::io::Write;
+
+use image::{
+    codecs::png::{CompressionType, FilterType, PngEncoder},
+    ColorType, DynamicImage,
+};
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+
+use crate::unwrap_or_return;
+
+/// Trades the size of the rendered wallpaper for how long it takes to encode,
+/// encoding dominates the refresh time of big (Ex: 4K) wallpapers.
+#[derive(Debug, PartialEq, Clone, Copy, EnumIter, Serialize, Deserialize, Default)]
+pub enum PngCompression {
+    #[default]
+    Fast,
+    Balanced,
+    Smallest,
+}
+
+/// Encodes `image` as an RGB PNG, wallpapers don't need an alpha channel.
+pub fn encode_png<W: Write>(
+    image: &DynamicImage,
+    compression: PngCompression,
+    writer: W,
+) -> Result<(), String> {
+    let (compression, filter) = match compression {
+        PngCompression::Fast => (CompressionType::Fast, FilterType::NoFilter),
+        PngCompression::Balanced => (CompressionType::Default, FilterType::Sub),
+        PngCompression::Smallest => (CompressionType::Best, FilterType::Paeth),
+    };
+
+    let rgb = image.to_rgb8();
+
+    unwrap_or_return!(
+        PngEncoder::new_with_quality(writer, compression, filter).encode(
+            &rgb,
+            rgb.width(),
+            rgb.height(),
+            ColorType::Rgb8
+        ),
+        "Couldn't encode the wallpaper!"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use std::{fs, path::PathBuf};
+
+use crate::{get_cache_dir, get_config_dir, unwrap_or_return};
+
+const ACTIVE_PROFILE_FILE: &str = "active_profile.txt";
+
+/// The profile every install starts with, it keeps using the cache dir itself
+/// so inputs saved before profiles existed stay where they were.
+pub const DEFAULT_PROFILE: &str = "Default";
+
+/// Name of the profile being edited and run by the schedular, Ex: "Work".
+pub fn active_profile() -> String {
+    fs::read_to_string(get_config_dir().join(ACTIVE_PROFILE_FILE))
+        .ok()
+        .map(|name| name.trim().to_string())
+        .filter(|name| sanitize_profile_name(name).is_ok())
+        .unwrap_or_else(|| String::from(DEFAULT_PROFILE))
+}
+
+pub fn set_active_profile(name: &str) -> Result<(), String> {
+    let name = sanitize_profile_name(name)?;
+
+    fs::create_dir_all(profile_cache_dir(&name)).ok();
+
+    unwrap_or_return!(
+        fs::write(get_config_dir().join(ACTIVE_PROFILE_FILE), name),
+        "Couldn't switch the profile!"
+    );
+
+    Ok(())
+}
+
+/// Profile names become directory names, so they're kept to what's safe in a path.
+pub fn sanitize_profile_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+
+    if name.is_empty() {
+        return Err(String::from("Profile name can't be empty!"));
+    }
+
+    if !name
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == ' ' || c == '-' || c == '_')
+    {
+        return Err(String::from(
+            "Profile names can only have letters, numbers, spaces, - and _",
+        ));
+    }
+
+    Ok(name.to_string())
+}
+
+/// Where a profile's inputs and its rendered wallpaper are cached.
+pub fn profile_cache_dir(name: &str) -> PathBuf {
+    if name == DEFAULT_PROFILE {
+        get_cache_dir()
+    } else {
+        get_cache_dir().join("profiles").join(name)
+    }
+}
+
+pub fn get_profile_cache_dir() -> PathBuf {
+    profile_cache_dir(&active_profile())
+}
+
+/// Every profile, the default one first and the others sorted by name.
+pub fn list_profiles() -> Vec<String> {
+    let mut profiles: Vec<String> = fs::read_dir(get_cache_dir().join("profiles"))
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().is_dir())
+                .filter_map(|entry| entry.file_name().into_string().ok())
+                .filter(|name| sanitize_profile_name(name).is_ok() && name != DEFAULT_PROFILE)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    profiles.sort();
+    profiles.insert(0, String::from(DEFAULT_PROFILE));
+
+    profiles
+}
+
+/// Removes a profile's cached inputs and wallpaper, the default one can't be deleted.
+pub fn delete_profile(name: &str) -> Result<(), String> {
+    if name == DEFAULT_PROFILE {
+        return Err(String::from("The default profile can't be deleted!"));
+    }
+
+    unwrap_or_return!(
+        fs::remove_dir_all(profile_cache_dir(&sanitize_profile_name(name)?)),
+        "Couldn't delete the profile!"
+    );
+
+    if active_profile() == name {
+        set_active_profile(DEFAULT_PROFILE)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime};
+use strum_macros::EnumIter;
+
+/// Common deadlines relative to now, filled in with a click.
+#[derive(Debug, PartialEq, Clone, Copy, EnumIter)]
+pub enum QuickPick {
+    Tonight,
+    EndOfWeek,
+    EndOfMonth,
+    InAWeek,
+}
+
+impl QuickPick {
+    pub fn label(&self) -> &'static str {
+        match self {
+            QuickPick::Tonight => "Tonight 23:59",
+            QuickPick::EndOfWeek => "End of week",
+            QuickPick::EndOfMonth => "End of month",
+            QuickPick::InAWeek => "+7 days",
+        }
+    }
+
+    pub fn resolve(&self, now: NaiveDateTime) -> NaiveDateTime {
+        let today = now.date();
+        let end_of = |date: NaiveDate| date.and_time(NaiveTime::from_hms(23, 59, 0));
+
+        match self {
+            QuickPick::Tonight => end_of(today),
+            // Weeks end on Sunday
+            QuickPick::EndOfWeek => {
+                let days_left = 6 - today.weekday().num_days_from_monday();
+                end_of(today + Duration::days(days_left as i64))
+            }
+            QuickPick::EndOfMonth => {
+                let (year, month) = match today.month() {
+                    12 => (today.year() + 1, 1),
+                    month => (today.year(), month + 1),
+                };
+
+                end_of(NaiveDate::from_ymd(year, month, 1).pred())
+            }
+            QuickPick::InAWeek => now + Duration::days(7),
+        }
+    }
+}
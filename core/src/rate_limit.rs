@@ -0,0 +1,232 @@
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    path::PathBuf,
+    sync::Mutex,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use chrono::{Local, TimeZone, Utc};
+use reqwest::{blocking::Response, header::RETRY_AFTER, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+
+use crate::{get_cache_dir, trace};
+
+/// How many requests a provider gets per window, matched by the end of the URL's host.
+/// Picked below the providers' documented limits, the rest of the hosts only get backoffs.
+const BUDGETS: &[(&str, u32, i64)] = &[
+    // The demo apps' limit
+    ("unsplash.com", 50, 3600),
+    // Unauthenticated clients get 10 requests a minute
+    ("reddit.com", 10, 60),
+    ("bing.com", 30, 3600),
+    // NASA's DEMO_KEY allows 30 requests an hour per IP
+    ("api.nasa.gov", 30, 3600),
+    ("date.nager.at", 60, 3600),
+    ("googleusercontent.com", 60, 3600),
+    ("icloud.com", 60, 3600),
+];
+
+/// A provider answering "too many requests" without a `Retry-After` is left alone
+/// for 2^n minutes after its n-th time in a row, up to 6 hours.
+const MAX_BACKOFF_SECS: i64 = 6 * 3600;
+
+/// The requests sent to a provider within its window and how long it asked to be left alone.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ProviderState {
+    /// Unix timestamps.
+    requests: Vec<i64>,
+    backoff_until: i64,
+    /// Times in a row it answered "too many requests".
+    backoffs: u32,
+}
+
+/// Shared by the GUI, the schedular and the CLI so together they stay within the budgets.
+fn rate_limits_path() -> PathBuf {
+    get_cache_dir().join("rate_limits.json")
+}
+
+/// Serializes the threads of this process, `RateLimitsLock` the processes.
+static RATE_LIMITS: Mutex<()> = Mutex::new(());
+
+/// A lock file is older than this only when its process died while holding it.
+const STALE_LOCK: Duration = Duration::from_secs(10);
+
+/// Held while rate_limits.json is read and written back, so the GUI, the schedular
+/// and the CLI don't overwrite each other's requests. Removed when it's dropped.
+struct RateLimitsLock {
+    path: PathBuf,
+}
+
+impl RateLimitsLock {
+    /// Waits up to 2s for the other processes, the budgets are only a courtesy
+    /// so it goes ahead without the lock rather than failing the download.
+    fn acquire() -> Option<RateLimitsLock> {
+        let path = get_cache_dir().join("rate_limits.lock");
+
+        for _ in 0..100 {
+            if OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+                .is_ok()
+            {
+                return Some(RateLimitsLock { path });
+            }
+
+            let is_stale = fs::metadata(&path)
+                .and_then(|lock| lock.modified())
+                .ok()
+                .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+                .is_some_and(|age| age > STALE_LOCK);
+
+            if is_stale {
+                fs::remove_file(&path).ok();
+            } else {
+                thread::sleep(Duration::from_millis(20));
+            }
+        }
+
+        trace!("rate_limits.json stayed locked, going ahead without the lock");
+
+        None
+    }
+}
+
+impl Drop for RateLimitsLock {
+    fn drop(&mut self) {
+        fs::remove_file(&self.path).ok();
+    }
+}
+
+fn load_rate_limits() -> HashMap<String, ProviderState> {
+    fs::read_to_string(rate_limits_path())
+        .ok()
+        .and_then(|limits| serde_json::from_str(&limits).ok())
+        .unwrap_or_default()
+}
+
+fn save_rate_limits(limits: &HashMap<String, ProviderState>) {
+    fs::write(rate_limits_path(), serde_json::to_string(limits).unwrap()).ok();
+}
+
+/// The provider a URL belongs to, Ex: "unsplash.com" for "https://source.unsplash.com/…",
+/// with its budget if it has one.
+fn provider(url: &Url) -> Option<(String, Option<(u32, i64)>)> {
+    let host = url.host_str()?.to_lowercase();
+
+    let budget = BUDGETS
+        .iter()
+        .find(|(domain, _, _)| host == *domain || host.ends_with(&format!(".{}", domain)));
+
+    Some(match budget {
+        Some((domain, requests, window)) => (domain.to_string(), Some((*requests, *window))),
+        None => (host, None),
+    })
+}
+
+fn local_time(timestamp: i64) -> String {
+    Local.timestamp(timestamp, 0).format("%H:%M").to_string()
+}
+
+/// Takes one request from the provider's budget, refused while it's used up
+/// or while the provider asked to be left alone.
+pub(crate) fn take_request(url: &Url) -> Result<(), String> {
+    let (provider, budget) = match provider(url) {
+        Some(provider) => provider,
+        None => return Ok(()),
+    };
+
+    let _lock = RATE_LIMITS.lock().unwrap();
+    let _file_lock = RateLimitsLock::acquire();
+    let mut limits = load_rate_limits();
+    let now = Utc::now().timestamp();
+
+    let state = limits.entry(provider.clone()).or_default();
+
+    if state.backoff_until > now {
+        return Err(format!(
+            "{} asked for fewer requests, holding off till {}",
+            provider,
+            local_time(state.backoff_until)
+        ));
+    }
+
+    let (requests, window) = match budget {
+        Some(budget) => budget,
+        None => return Ok(()),
+    };
+
+    state.requests.retain(|at| now - at < window);
+
+    if state.requests.len() >= requests as usize {
+        let available_at = state.requests[0] + window;
+
+        trace!("{}'s budget of {} requests is used up", provider, requests);
+
+        return Err(format!(
+            "Used up the {} requests {} allows per {} minutes, the next one can go at {}",
+            requests,
+            provider,
+            window / 60,
+            local_time(available_at)
+        ));
+    }
+
+    state.requests.push(now);
+
+    save_rate_limits(&limits);
+
+    Ok(())
+}
+
+/// Backs off from a provider that answered "too many requests" or "unavailable",
+/// for as long as its `Retry-After` says when it sends one.
+pub(crate) fn record_response(url: &Url, response: &Response) {
+    let provider = match provider(url) {
+        Some((provider, _)) => provider,
+        None => return,
+    };
+
+    let status = response.status();
+    let is_throttled = status == StatusCode::TOO_MANY_REQUESTS
+        || (status == StatusCode::SERVICE_UNAVAILABLE
+            && response.headers().contains_key(RETRY_AFTER));
+
+    let _lock = RATE_LIMITS.lock().unwrap();
+    let _file_lock = RateLimitsLock::acquire();
+    let mut limits = load_rate_limits();
+
+    let state = limits.entry(provider.clone()).or_default();
+
+    if !is_throttled {
+        if state.backoffs == 0 {
+            return;
+        }
+        state.backoffs = 0;
+    } else {
+        // Only the seconds form, dates are rare and the exponential backoff covers them
+        let retry_after = response
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<i64>().ok());
+
+        let backoff = retry_after
+            .unwrap_or_else(|| 60 * 2i64.pow(state.backoffs.min(10)))
+            .min(MAX_BACKOFF_SECS);
+
+        trace!(
+            "{} answered {}, backing off for {}s",
+            provider,
+            status,
+            backoff
+        );
+
+        state.backoffs += 1;
+        state.backoff_until = Utc::now().timestamp() + backoff;
+    }
+
+    save_rate_limits(&limits);
+}
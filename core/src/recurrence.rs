@@ -0,0 +1,110 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+
+use crate::resolve_deadline;
+
+/// How a deadline repeats, Ex: a weekly assignment due on Fridays at 23:59.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, EnumIter, Serialize, Deserialize, Default)]
+pub enum Recurrence {
+    #[default]
+    Never,
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl std::fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Never => "Doesn't repeat",
+                Self::Daily => "Every day",
+                Self::Weekly => "Every week",
+                Self::Monthly => "Every month",
+                Self::Yearly => "Every year",
+            }
+        )
+    }
+}
+
+/// A passed occurrence is still shown for a minute, so the schedular's
+/// slightly late tick still sees the deadline being reached.
+const ROLLOVER_GRACE_SECS: i64 = 60;
+
+impl Recurrence {
+    /// The `n`th occurrence starting from `first` (the 0th one).
+    /// Monthly and yearly ones land on the last day of shorter months, Ex: Jan 31 -> Feb 28.
+    pub fn nth(self, first: NaiveDateTime, n: u32) -> NaiveDateTime {
+        match self {
+            Self::Never => first,
+            Self::Daily => first + Duration::days(n as i64),
+            Self::Weekly => first + Duration::weeks(n as i64),
+            Self::Monthly => add_months(first, n),
+            Self::Yearly => add_months(first, n * 12),
+        }
+    }
+
+    /// Index of the occurrence that's counted down to at `now`, the first one that isn't over.
+    pub fn current_index(
+        self,
+        first: NaiveDateTime,
+        timezone: Option<Tz>,
+        now: DateTime<Utc>,
+    ) -> u32 {
+        if self == Self::Never {
+            return 0;
+        }
+
+        let is_over = |n| {
+            now.signed_duration_since(resolve_deadline(self.nth(first, n), timezone))
+                .num_seconds()
+                > ROLLOVER_GRACE_SECS
+        };
+
+        // Skip close to the current occurrence right away instead of stepping through years of them
+        let elapsed_days = now.naive_utc().signed_duration_since(first).num_days();
+        let days_per_occurrence = match self {
+            Self::Never | Self::Daily => 1,
+            Self::Weekly => 7,
+            Self::Monthly => 31,
+            Self::Yearly => 366,
+        };
+        let mut n = (elapsed_days / days_per_occurrence - 1).max(0) as u32;
+
+        while is_over(n) {
+            n += 1;
+        }
+
+        n
+    }
+
+    /// The `RRULE` of an iCalendar event repeating the same way.
+    pub fn ics_rule(self) -> Option<&'static str> {
+        match self {
+            Self::Never => None,
+            Self::Daily => Some("FREQ=DAILY"),
+            Self::Weekly => Some("FREQ=WEEKLY"),
+            Self::Monthly => Some("FREQ=MONTHLY"),
+            Self::Yearly => Some("FREQ=YEARLY"),
+        }
+    }
+}
+
+fn add_months(date: NaiveDateTime, months: u32) -> NaiveDateTime {
+    let months = date.month0() + months;
+    let year = date.year() + (months / 12) as i32;
+    let month = months % 12 + 1;
+
+    // The last day of the month when it's shorter than the original one
+    let day = (1..=date.day())
+        .rev()
+        .find_map(|day| NaiveDate::from_ymd_opt(year, month, day))
+        .unwrap();
+
+    day.and_time(date.time())
+}
@@ -0,0 +1,131 @@
+use std::fs;
+
+use chrono::{Local, TimeZone};
+use serde::{Deserialize, Serialize};
+
+use crate::{get_cache_dir, load_sanitized_conf, update_wallpaper, with_synced_deadlines};
+
+/// Only the latest refreshes matter for how healthy updates are now.
+const KEPT_REFRESHES: usize = 20;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefreshRecord {
+    /// Unix timestamp of when the refresh ended.
+    pub at: i64,
+    pub error: Option<String>,
+}
+
+/// Outcomes of the recent background refreshes, kept on disk only.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct RefreshHealth {
+    pub recent: Vec<RefreshRecord>,
+    /// Unix timestamps kept past the recent refreshes, which only cover the last few minutes.
+    #[serde(default)]
+    pub last_success_at: Option<i64>,
+    /// When the refreshes started failing, `None` while the last one went fine.
+    #[serde(default)]
+    pub failing_since: Option<i64>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum HealthStatus {
+    /// No refresh happened yet.
+    Unknown,
+    Healthy,
+    /// The last refresh went fine but some recent ones failed.
+    Flaky,
+    /// The last refresh failed.
+    Failing,
+}
+
+impl HealthStatus {
+    pub fn color(&self) -> [u8; 3] {
+        match self {
+            HealthStatus::Unknown => [150, 150, 150],
+            HealthStatus::Healthy => [67, 181, 96],
+            HealthStatus::Flaky => [254, 216, 67],
+            HealthStatus::Failing => [255, 48, 48],
+        }
+    }
+}
+
+impl RefreshHealth {
+    pub fn load() -> RefreshHealth {
+        fs::read_to_string(get_cache_dir().join("refresh_health.json"))
+            .ok()
+            .and_then(|health| serde_json::from_str(&health).ok())
+            .unwrap_or_default()
+    }
+
+    /// Adds the outcome of a refresh that just ended.
+    pub fn record(result: &Result<(), String>) {
+        let mut health = RefreshHealth::load();
+        let now = Local::now().timestamp();
+
+        health.recent.push(RefreshRecord {
+            at: now,
+            error: result.clone().err(),
+        });
+
+        if result.is_ok() {
+            health.last_success_at = Some(now);
+            health.failing_since = None;
+        } else {
+            health.failing_since.get_or_insert(now);
+        }
+
+        let overflow = health.recent.len().saturating_sub(KEPT_REFRESHES);
+        health.recent.drain(..overflow);
+
+        fs::write(
+            get_cache_dir().join("refresh_health.json"),
+            serde_json::to_string(&health).unwrap(),
+        )
+        .ok();
+    }
+
+    pub fn status(&self) -> HealthStatus {
+        match self.recent.last() {
+            None => HealthStatus::Unknown,
+            Some(last) if last.error.is_some() => HealthStatus::Failing,
+            Some(_) if self.recent.iter().any(|r| r.error.is_some()) => HealthStatus::Flaky,
+            Some(_) => HealthStatus::Healthy,
+        }
+    }
+
+    pub fn last_success(&self) -> Option<&RefreshRecord> {
+        self.recent.iter().rev().find(|r| r.error.is_none())
+    }
+
+    pub fn last_failure(&self) -> Option<&RefreshRecord> {
+        self.recent.iter().rev().find(|r| r.error.is_some())
+    }
+
+    /// One line summary, Ex: "Failing, last error at 2022-05-01 14:00: Couldn't download...".
+    pub fn summary(&self) -> String {
+        let status = self.status();
+
+        match (status, self.last_failure()) {
+            (HealthStatus::Unknown, _) => "No refreshes yet".to_string(),
+            (_, Some(failure)) => format!(
+                "{:?}, last error at {}: {}",
+                status,
+                Local.timestamp(failure.at, 0).format("%Y-%m-%d %H:%M"),
+                failure.error.as_deref().unwrap_or_default()
+            ),
+            (_, None) => format!("{:?}", status),
+        }
+    }
+}
+
+/// Updates the wallpaper with the saved inputs right away and records how it went,
+/// Ex: after the network is back without changing any setting.
+pub fn refresh_now() -> Result<(), String> {
+    let result = load_sanitized_conf().and_then(|conf| {
+        update_wallpaper(&with_synced_deadlines(&conf), false).map_err(String::from)
+    });
+
+    RefreshHealth::record(&result);
+
+    result
+}
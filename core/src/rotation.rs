@@ -0,0 +1,128 @@
+use std::{cmp::Ordering, fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+
+use crate::{get_profile_cache_dir, trace, SanitizedConf};
+
+/// Which of several deadlines the wallpaper shows, all of them stacked or one per refresh.
+#[derive(Debug, PartialEq, Clone, Copy, EnumIter, Serialize, Deserialize, Default)]
+pub enum RotationMode {
+    /// Every deadline is shown, one under the other.
+    #[default]
+    Stacked,
+    /// One deadline per refresh, in turns.
+    RoundRobin,
+    /// One deadline per refresh, closer deadlines get more turns.
+    Urgency,
+}
+
+/// The deadline on the wallpaper and each deadline's turn credits, see `advance_rotation`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RotationState {
+    shown: usize,
+    credits: Vec<f64>,
+}
+
+fn rotation_path() -> PathBuf {
+    get_profile_cache_dir().join("rotation.json")
+}
+
+fn load_rotation() -> RotationState {
+    fs::read_to_string(rotation_path())
+        .ok()
+        .and_then(|state| serde_json::from_str(&state).ok())
+        .unwrap_or_default()
+}
+
+/// Indexes of the deadlines that can take a turn, the ones that are over are skipped.
+fn rotating_deadlines(conf: &SanitizedConf) -> Vec<usize> {
+    conf.deadlines
+        .iter()
+        .enumerate()
+        .filter(|(_, deadline)| deadline.counts_up || deadline.minutes_left() > 0)
+        .map(|(i, _)| i)
+        .collect()
+}
+
+fn rotates(conf: &SanitizedConf) -> bool {
+    conf.rotation != RotationMode::Stacked && !conf.focus.enabled && conf.deadlines.len() > 1
+}
+
+/// Gives the next deadline its turn on the wallpaper, called once per refresh
+/// so previews and test renders keep showing the same one.
+pub fn advance_rotation(conf: &SanitizedConf) {
+    if !rotates(conf) {
+        return;
+    }
+
+    let candidates = rotating_deadlines(conf);
+    if candidates.is_empty() {
+        return;
+    }
+
+    let mut state = load_rotation();
+
+    // The deadlines changed since the last turn
+    if state.credits.len() != conf.deadlines.len() {
+        state = RotationState {
+            shown: candidates[candidates.len() - 1],
+            credits: vec![0.; conf.deadlines.len()],
+        };
+    }
+
+    state.shown = match conf.rotation {
+        RotationMode::RoundRobin => *candidates
+            .iter()
+            .find(|i| **i > state.shown)
+            .unwrap_or(&candidates[0]),
+        // Smooth weighted round-robin, every deadline still gets a turn now and then
+        _ => {
+            let weights: Vec<f64> = candidates
+                .iter()
+                .map(|i| {
+                    let days_left = conf.deadlines[*i].minutes_left().max(0) as f64 / 1440.;
+
+                    1. / (1. + days_left)
+                })
+                .collect();
+            let total: f64 = weights.iter().sum();
+
+            for (i, weight) in candidates.iter().zip(&weights) {
+                state.credits[*i] += weight;
+            }
+
+            let shown = *candidates
+                .iter()
+                .max_by(|a, b| {
+                    state.credits[**a]
+                        .partial_cmp(&state.credits[**b])
+                        .unwrap_or(Ordering::Equal)
+                })
+                .unwrap();
+            state.credits[shown] -= total;
+
+            shown
+        }
+    };
+
+    trace!("rotating to deadline #{}", state.shown);
+
+    fs::write(rotation_path(), serde_json::to_string(&state).unwrap()).ok();
+}
+
+/// The deadline whose turn it is, `None` when every deadline is shown.
+pub fn rotated_deadline(conf: &SanitizedConf) -> Option<usize> {
+    if !rotates(conf) {
+        return None;
+    }
+
+    let candidates = rotating_deadlines(conf);
+    let shown = load_rotation().shown;
+
+    if candidates.contains(&shown) {
+        Some(shown)
+    } else {
+        candidates.first().copied()
+    }
+}
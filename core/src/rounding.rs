@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+
+use crate::TimeUnit;
+
+/// How the time left is rounded to the smallest unit that's displayed,
+/// Ex: 1 hour and 31 minutes is "2 Hours" when rounding half up.
+#[derive(Debug, PartialEq, Clone, Copy, EnumIter, Serialize, Deserialize, Default)]
+pub enum RoundingMode {
+    #[default]
+    HalfUp,
+    /// Never shows less time than what's left.
+    Up,
+    /// Never shows more time than what's left.
+    Down,
+}
+
+impl RoundingMode {
+    /// Rounds `seconds` to a whole number of `unit`s.
+    pub fn round(&self, seconds: i64, unit: TimeUnit) -> i64 {
+        let unit_seconds = unit.seconds();
+
+        match self {
+            Self::HalfUp => (seconds + unit_seconds / 2).div_euclid(unit_seconds),
+            Self::Up => (seconds + unit_seconds - 1).div_euclid(unit_seconds),
+            Self::Down => seconds.div_euclid(unit_seconds),
+        }
+    }
+}
+
+/// Breaks `seconds` down into `units`, from the biggest to the smallest one,
+/// the smallest unit gets the rounded remainder.
+pub fn break_down(
+    seconds: i64,
+    units: &[TimeUnit],
+    rounding: RoundingMode,
+) -> Vec<(TimeUnit, i64)> {
+    let smallest = match units.last() {
+        Some(unit) => *unit,
+        None => return Vec::new(),
+    };
+
+    let mut rest = rounding.round(seconds, smallest) * smallest.seconds();
+
+    units
+        .iter()
+        .map(|unit| {
+            let value = rest / unit.seconds();
+            rest -= value * unit.seconds();
+
+            (*unit, value)
+        })
+        .collect()
+}
@@ -0,0 +1,136 @@
+use std::{
+    fs,
+    path::PathBuf,
+    process::{self, Command, Stdio},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::{
+    get_cache_dir, rgb_to_hex, trace, AgendaConf, BackdropConf, DeadlinerConf, FinalHoursConf,
+    ImageFilters, Pattern, SanitizedBackground, SanitizedConf, SeasonalConf, UrgencyColorsConf,
+    WallpaperMode,
+};
+
+/// Holds the PID of the process rendering, written before every render and removed after it.
+/// One that's still there on startup while its process is gone means the last render
+/// took the process down with it.
+const RENDER_SENTINEL: &str = "rendering.sentinel";
+
+static SAFE_MODE: AtomicBool = AtomicBool::new(false);
+/// Set once the process was asked to quit, the renders it's killed in the middle of aren't crashes.
+static SHUTTING_DOWN: AtomicBool = AtomicBool::new(false);
+
+fn sentinel_path() -> PathBuf {
+    get_cache_dir().join(RENDER_SENTINEL)
+}
+
+/// Starts in safe mode when the last run crashed while rendering, so a corrupt image
+/// or an enormous font can't crash every launch. Returns whether safe mode is on.
+pub fn enter_safe_mode_if_crashed() -> bool {
+    let pid = fs::read_to_string(sentinel_path())
+        .ok()
+        .and_then(|pid| pid.trim().parse::<u32>().ok());
+
+    match pid {
+        // Ex: the GUI saving new inputs while the schedular restarts
+        Some(pid) if pid != process::id() && is_running(pid) => {}
+        Some(_) => {
+            trace!("the last render crashed, starting in safe mode");
+
+            SAFE_MODE.store(true, Ordering::SeqCst);
+        }
+        None => {}
+    }
+
+    is_safe_mode()
+}
+
+fn is_running(pid: u32) -> bool {
+    if cfg!(target_os = "windows") {
+        Command::new("tasklist")
+            .args(["/FI", &format!("PID eq {}", pid), "/NH"])
+            .output()
+            .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).contains(&pid.to_string()))
+    } else {
+        Command::new("kill")
+            .args(["-0", &pid.to_string()])
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+    }
+}
+
+pub fn is_safe_mode() -> bool {
+    SAFE_MODE.load(Ordering::SeqCst)
+}
+
+/// Renders with the user's styling again, Ex: after they saved new inputs.
+pub fn leave_safe_mode() {
+    SAFE_MODE.store(false, Ordering::SeqCst);
+}
+
+pub(crate) fn mark_render_started() {
+    if SHUTTING_DOWN.load(Ordering::SeqCst) {
+        return;
+    }
+
+    fs::write(sentinel_path(), process::id().to_string()).ok();
+}
+
+/// Only called once the render returned, a panic keeps the sentinel for the next launch.
+pub(crate) fn mark_render_finished() {
+    fs::remove_file(sentinel_path()).ok();
+}
+
+/// Called when the process is asked to quit, Ex: by the GUI restarting the schedular
+/// with new inputs, so a render it's stopped in the middle of doesn't look like a crash.
+pub fn mark_shutting_down() {
+    SHUTTING_DOWN.store(true, Ordering::SeqCst);
+
+    let is_ours = fs::read_to_string(sentinel_path())
+        .is_ok_and(|pid| pid.trim() == process::id().to_string());
+
+    if is_ours {
+        fs::remove_file(sentinel_path()).ok();
+    }
+}
+
+/// `conf` with a solid background and the default styling while in safe mode,
+/// the deadlines themselves are kept.
+pub fn with_safe_mode(conf: &SanitizedConf) -> SanitizedConf {
+    let mut conf = conf.clone();
+
+    if !is_safe_mode() {
+        return conf;
+    }
+
+    let defaults = DeadlinerConf::new(conf.screen_dimensions, vec![]);
+    let rgb_hex = |[r, g, b]: [u8; 3]| rgb_to_hex(r, g, b);
+
+    conf.default_bg = SanitizedBackground::Solid {
+        rgb: [0; 3],
+        hex: rgb_hex([0; 3]),
+        pattern: Pattern::None,
+    };
+    conf.bg_mode = WallpaperMode::Center;
+    conf.render_mode_in_app = false;
+    conf.background_filters = ImageFilters::default();
+    conf.layout = defaults.layout;
+    conf.font = defaults.font;
+    conf.font_size = defaults.font_size;
+    conf.font_color = rgb_hex(defaults.font_color);
+    conf.title_font_size = defaults.title_font_size;
+    conf.title_font_color = rgb_hex(defaults.title_font_color);
+    conf.custom_font_location = String::new();
+    conf.text_position = defaults.text_position;
+    conf.high_contrast = false;
+    conf.backdrop = BackdropConf::default();
+    conf.urgency_colors = UrgencyColorsConf::default();
+    conf.final_hours = FinalHoursConf::default();
+    conf.text_variables = Vec::new();
+    conf.branding_location = String::new();
+    conf.agenda = AgendaConf::default();
+    conf.seasonal = SeasonalConf::default();
+
+    conf
+}
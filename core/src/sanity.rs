@@ -0,0 +1,48 @@
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{parse_timezone, resolve_deadline, resolve_deadline_date, DeadlinerConf};
+
+/// Deadlines further than this are most likely a typo in the year.
+const MAX_YEARS_AWAY: i64 = 5;
+
+/// Things that are allowed but are most likely a mistake, they're confirmed before saving.
+pub fn sanity_warnings(conf: &DeadlinerConf) -> Vec<String> {
+    let mut warnings = vec![];
+    let mut resolved: Vec<(usize, DateTime<Utc>)> = vec![];
+
+    let now = Utc::now();
+
+    for (i, deadline) in conf.deadlines.iter().enumerate() {
+        // Invalid inputs and past deadlines are reported as errors while saving
+        let date = match resolve_deadline_date(deadline, conf.use_24_hour) {
+            Ok(date) => date,
+            Err(_) => continue,
+        };
+        let timezone = match parse_timezone(&deadline.timezone) {
+            Ok(timezone) => timezone,
+            Err(_) => continue,
+        };
+
+        let at = resolve_deadline(date, timezone);
+
+        if at - now > Duration::days(365 * MAX_YEARS_AWAY) {
+            warnings.push(format!(
+                "Deadline {} is more than {} years away",
+                i + 1,
+                MAX_YEARS_AWAY
+            ));
+        }
+
+        if let Some((same, _)) = resolved.iter().find(|(_, other)| *other == at) {
+            warnings.push(format!(
+                "Deadlines {} and {} are at the same time",
+                same + 1,
+                i + 1
+            ));
+        }
+
+        resolved.push((i, at));
+    }
+
+    warnings
+}
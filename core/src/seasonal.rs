@@ -0,0 +1,138 @@
+use chrono::{Datelike, Local, NaiveDate};
+use image::{DynamicImage, GenericImageView, Rgba};
+use imageproc::drawing::draw_filled_circle_mut;
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+
+use crate::{rgb_to_hex, trace, SanitizedConf};
+
+/// A `(month, day)` of the year, Ex: `(12, 31)`.
+type MonthDay = (u32, u32);
+
+/// Known times of the year the wallpaper can be themed for.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, EnumIter)]
+pub enum Season {
+    /// Dec 31 to Jan 2, gold titles with confetti.
+    NewYear,
+    /// Mid May to June and the first weeks of December, calm blue titles.
+    ExamSeason,
+    /// The last week of October, orange titles with a few purple and orange dots.
+    Halloween,
+    /// Dec 20 to Dec 30, red titles with snow.
+    WinterHolidays,
+}
+
+impl Season {
+    /// Inclusive ranges the season is on.
+    fn ranges(&self) -> &'static [(MonthDay, MonthDay)] {
+        match self {
+            Season::NewYear => &[((12, 31), (12, 31)), ((1, 1), (1, 2))],
+            Season::ExamSeason => &[((5, 15), (6, 30)), ((12, 1), (12, 19))],
+            Season::Halloween => &[((10, 25), (10, 31))],
+            Season::WinterHolidays => &[((12, 20), (12, 30))],
+        }
+    }
+
+    pub fn is_active(&self, date: NaiveDate) -> bool {
+        let day = (date.month(), date.day());
+
+        self.ranges()
+            .iter()
+            .any(|(start, end)| *start <= day && day <= *end)
+    }
+
+    pub fn accent(&self) -> [u8; 3] {
+        match self {
+            Season::NewYear => [255, 205, 70],
+            Season::ExamSeason => [130, 190, 255],
+            Season::Halloween => [255, 140, 30],
+            Season::WinterHolidays => [235, 70, 70],
+        }
+    }
+
+    /// Colors of the dots sprinkled along the top of the wallpaper, none draws no layer.
+    fn decoration(&self) -> &'static [[u8; 3]] {
+        match self {
+            Season::NewYear => &[
+                [255, 205, 70],
+                [255, 90, 120],
+                [90, 200, 255],
+                [140, 255, 140],
+            ],
+            Season::ExamSeason => &[],
+            Season::Halloween => &[[255, 140, 30], [150, 80, 200]],
+            Season::WinterHolidays => &[[255, 255, 255]],
+        }
+    }
+}
+
+/// Seasons the wallpaper is automatically themed for, each one is picked separately.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SeasonalConf {
+    pub seasons: Vec<Season>,
+}
+
+impl SeasonalConf {
+    /// The first picked season that's on at `date`.
+    pub fn active_at(&self, date: NaiveDate) -> Option<Season> {
+        self.seasons
+            .iter()
+            .copied()
+            .find(|season| season.is_active(date))
+    }
+
+    fn active_today(&self) -> Option<Season> {
+        self.active_at(Local::today().naive_local())
+    }
+}
+
+/// `conf` with the titles in the accent color of the season that's on today.
+pub fn with_seasonal_accents(conf: &SanitizedConf) -> SanitizedConf {
+    let mut conf = conf.clone();
+
+    if let Some(season) = conf.seasonal.active_today() {
+        trace!("{:?} is on, using its accent color", season);
+
+        let [r, g, b] = season.accent();
+        conf.title_font_color = rgb_to_hex(r, g, b);
+    }
+
+    conf
+}
+
+/// Sprinkles the season's dots along the top of the wallpaper, they're in the same
+/// places on every render so the wallpaper doesn't jump between updates.
+pub(crate) fn draw_seasonal_layer(background: &mut DynamicImage, conf: &SanitizedConf) {
+    let colors = match conf.seasonal.active_today() {
+        Some(season) => season.decoration(),
+        None => return,
+    };
+
+    if colors.is_empty() {
+        return;
+    }
+
+    let (width, height) = (background.width(), background.height());
+    let band = (height / 8).max(1);
+    let radius = (height / 300).max(2) as i32;
+
+    let mut seed: u32 = 0x9e37_79b9;
+    let mut next = || {
+        seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+        seed >> 8
+    };
+
+    for i in 0..(width / 40).max(1) {
+        let x = next() % width.max(1);
+        // Denser at the top edge and fading out towards the countdown
+        let y = (next() % band).min(next() % band);
+        let [r, g, b] = colors[i as usize % colors.len()];
+
+        draw_filled_circle_mut(
+            background,
+            (x as i32, y as i32),
+            radius,
+            Rgba([r, g, b, 255]),
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    get_config_dir, merge_monitors, migrate_config, unwrap_or_return, Background, DeadlinerConf,
+    Font,
+};
+
+/// Extension of the files written by "Export settings…".
+pub const SETTINGS_EXTENSION: &str = "deadliner";
+
+/// Every input of the GUI in one file that can be shared, Ex: with the rest of a class.
+#[derive(Serialize, Deserialize)]
+struct SettingsBundle {
+    /// Kept as plain JSON so bundles from older versions go through `migrate_config`.
+    conf: Value,
+    #[serde(default)]
+    files: Vec<BundledFile>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BundledFile {
+    role: BundledRole,
+    /// File name it had on the exporting machine, Ex: "exam-week.png".
+    name: String,
+    /// Base64 of its contents.
+    data: String,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy)]
+enum BundledRole {
+    Font,
+    Background,
+}
+
+/// Copies the settings of `from` that run commands, send things somewhere or hold credentials.
+/// They never leave this machine and are never taken from a bundle since it could come from anyone.
+fn keep_private_settings(conf: &mut DeadlinerConf, from: &DeadlinerConf) {
+    conf.text_variables = from.text_variables.clone();
+    conf.deadline_hook = from.deadline_hook.clone();
+    conf.webhooks = from.webhooks.clone();
+    conf.calendar_sync = from.calendar_sync.clone();
+    conf.output_targets = from.output_targets.clone();
+}
+
+/// Writes `conf` to `output`, with `bundle_files` the custom font and the background image
+/// are put in it too so they don't have to be sent separately.
+/// The private settings are left out, see `keep_private_settings`.
+pub fn export_settings(
+    conf: &DeadlinerConf,
+    output: &Path,
+    bundle_files: bool,
+) -> Result<(), String> {
+    let mut files = Vec::new();
+
+    if bundle_files {
+        if conf.font == Font::ChooseFromDisk {
+            files.push(bundle_file(BundledRole::Font, &conf.custom_font_location)?);
+        }
+
+        if let Background::FromDisk { location, .. } = &conf.default_background {
+            files.push(bundle_file(BundledRole::Background, location)?);
+        }
+    }
+
+    let mut shared = conf.clone();
+    keep_private_settings(
+        &mut shared,
+        &DeadlinerConf::new(conf.screen_dimensions, Vec::new()),
+    );
+
+    let bundle = SettingsBundle {
+        conf: serde_json::to_value(&shared).unwrap(),
+        files,
+    };
+
+    unwrap_or_return!(
+        fs::write(output, serde_json::to_string_pretty(&bundle).unwrap()),
+        format!("Couldn't write {}", output.display())
+    );
+
+    Ok(())
+}
+
+fn bundle_file(role: BundledRole, location: &str) -> Result<BundledFile, String> {
+    let location = Path::new(location.trim());
+
+    let contents = unwrap_or_return!(
+        fs::read(location),
+        format!("Couldn't read {} to bundle it", location.display())
+    );
+
+    Ok(BundledFile {
+        role,
+        name: location
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        data: base64::encode(contents),
+    })
+}
+
+/// Reads settings exported by `export_settings`, the bundled files are extracted next to
+/// the config and the screen, the monitors and the private settings of `local` are kept
+/// since they belong to this machine.
+pub fn import_settings(input: &Path, local: &DeadlinerConf) -> Result<DeadlinerConf, String> {
+    let bundle_str = unwrap_or_return!(
+        fs::read_to_string(input),
+        format!("Couldn't read {}", input.display())
+    );
+    let bundle: SettingsBundle = unwrap_or_return!(
+        serde_json::from_str(&bundle_str),
+        "The file isn't a settings export of deadliner!"
+    );
+
+    let mut conf = migrate_config(&bundle.conf.to_string())?;
+
+    conf.screen_dimensions = local.screen_dimensions;
+    conf.monitors = merge_monitors(local.monitors.clone(), &conf.monitors);
+    keep_private_settings(&mut conf, local);
+
+    for file in &bundle.files {
+        let location = extract_file(file)?.to_string_lossy().to_string();
+
+        match file.role {
+            BundledRole::Font => conf.custom_font_location = location,
+            BundledRole::Background => {
+                if let Background::FromDisk {
+                    location: bundled, ..
+                } = &mut conf.default_background
+                {
+                    *bundled = location;
+                }
+            }
+        }
+    }
+
+    Ok(conf)
+}
+
+fn extract_file(file: &BundledFile) -> Result<PathBuf, String> {
+    let contents = unwrap_or_return!(
+        base64::decode(&file.data),
+        format!("The bundled {} is corrupted!", file.name)
+    );
+
+    // Only the file name is used so a bundle can't write outside of the imported dir
+    let name = Path::new(&file.name)
+        .file_name()
+        .map(|name| name.to_os_string())
+        .unwrap_or_else(|| "bundled".into());
+
+    let imported_dir = get_config_dir().join("imported");
+    fs::create_dir_all(&imported_dir).ok();
+
+    let path = imported_dir.join(name);
+
+    unwrap_or_return!(
+        fs::write(&path, contents),
+        format!("Couldn't extract {}", file.name)
+    );
+
+    Ok(path)
+}
@@ -0,0 +1,70 @@
+use std::path::Path;
+
+use image::imageops::FilterType;
+
+use crate::{
+    compose_wallpaper, deadline_lines, load_background, load_branding, unwrap_or_return,
+    SanitizedConf, ScreenDimensions, TextLine,
+};
+
+/// Share cards follow the sizes social media previews expect.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ShareCardSize {
+    /// 1200x630, used by link previews on most platforms.
+    Landscape,
+    /// 1080x1080, used by feeds showing square posts.
+    Square,
+}
+
+impl ShareCardSize {
+    pub fn dimensions(&self) -> ScreenDimensions {
+        match self {
+            Self::Landscape => ScreenDimensions {
+                width: 1200,
+                height: 630,
+            },
+            Self::Square => ScreenDimensions {
+                width: 1080,
+                height: 1080,
+            },
+        }
+    }
+}
+
+/// Renders the deadlines' titles and countdowns styled like the wallpaper into a PNG card.
+pub fn export_share_card(
+    conf: &SanitizedConf,
+    size: ShareCardSize,
+    output: &Path,
+) -> Result<(), String> {
+    let ScreenDimensions { width, height } = size.dimensions();
+
+    let lines: Vec<TextLine> = deadline_lines(conf, false)
+        .into_iter()
+        .map(|(_, line)| line)
+        .collect();
+
+    if lines.is_empty() {
+        return Err(String::from("There's no countdown to share!"));
+    }
+
+    // Keep the text as big relative to the card as it's on the wallpaper
+    let scale = width.min(height) as f32
+        / conf
+            .screen_dimensions
+            .width
+            .min(conf.screen_dimensions.height)
+            .max(1) as f32;
+    let lines: Vec<TextLine> = lines.iter().map(|line| line.scaled(scale)).collect();
+
+    let branding = load_branding(&conf.branding_location)?;
+
+    let background =
+        load_background(conf, &branding)?.resize_to_fill(width, height, FilterType::Triangle);
+
+    let card = compose_wallpaper(background, &lines, conf, &branding)?;
+
+    unwrap_or_return!(card.save(output), "Couldn't save the share card!");
+
+    Ok(())
+}
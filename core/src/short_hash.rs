@@ -13,7 +13,7 @@ pub fn unique_hash(text: &str) -> String {
 
 fn bitwise(string: &str) -> i32 {
     let mut hash = 0;
-    if string.len() == 0 {
+    if string.is_empty() {
         return hash;
     };
 
@@ -45,7 +45,7 @@ fn binary_transfer(mut integer: i32, binary: i32) -> String {
 
     while integer >= binary {
         num = integer % binary;
-        integer = (integer / binary) as i32;
+        integer /= binary;
         stack.push(table(num));
     }
 
@@ -53,8 +53,8 @@ fn binary_transfer(mut integer: i32, binary: i32) -> String {
         stack.push(table(integer));
     }
 
-    for i in 0..stack.len() {
-        result.push(stack[i]);
+    for c in stack {
+        result.push(c);
     }
 
     sign.to_string() + &result
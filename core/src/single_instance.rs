@@ -0,0 +1,99 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{Ipv4Addr, TcpListener, TcpStream},
+    sync::mpsc::{self, Receiver},
+    thread,
+    time::Duration,
+};
+
+use crate::{get_schedular_port, trace};
+
+const FOCUS_REQUEST: &str = "focus";
+/// Answered to a focus request so another app listening on the port isn't mistaken for us.
+const FOCUS_REPLY: &str = "deadliner";
+
+/// Shifted by 1000 to stay clear of the schedular's port, it was checked to be free too
+/// when the schedular's port was picked.
+fn instance_port() -> u16 {
+    get_schedular_port().wrapping_add(1000)
+}
+
+/// Held by the only GUI of this OS user, the OS frees the port when the process exits
+/// (even when it crashes) so there's no stale lock to clean up.
+pub struct InstanceLock {
+    listener: TcpListener,
+}
+
+pub enum SingleInstance {
+    Locked(InstanceLock),
+    /// The running instance was asked to show its window.
+    AlreadyRunning,
+    /// Another app took the port, running twice beats not running at all.
+    Unavailable,
+}
+
+/// Takes the single-instance lock or hands off to the instance that already holds it.
+pub fn lock_single_instance() -> SingleInstance {
+    match TcpListener::bind((Ipv4Addr::LOCALHOST, instance_port())) {
+        Ok(listener) => SingleInstance::Locked(InstanceLock { listener }),
+        Err(_) if request_focus() => SingleInstance::AlreadyRunning,
+        Err(_) => {
+            trace!(
+                "port {} is taken by another app, running without the single-instance lock",
+                instance_port()
+            );
+
+            SingleInstance::Unavailable
+        }
+    }
+}
+
+/// Whether a running deadliner got the request to show its window.
+fn request_focus() -> bool {
+    let addr = (Ipv4Addr::LOCALHOST, instance_port()).into();
+
+    let stream = match TcpStream::connect_timeout(&addr, Duration::from_secs(1)) {
+        Ok(stream) => stream,
+        Err(_) => return false,
+    };
+    stream.set_read_timeout(Some(Duration::from_secs(1))).ok();
+
+    if writeln!(&stream, "{}", FOCUS_REQUEST).is_err() {
+        return false;
+    }
+
+    let mut reply = String::new();
+    BufReader::new(&stream).read_line(&mut reply).ok();
+
+    reply.trim() == FOCUS_REPLY
+}
+
+impl InstanceLock {
+    /// Listens for later launches asking to show this instance's window,
+    /// `on_request` is called on every request (Ex: to wake up the GUI).
+    pub fn on_focus_request(self, on_request: impl Fn() + Send + 'static) -> Receiver<()> {
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            for stream in self.listener.incoming().flatten() {
+                let mut request = String::new();
+                BufReader::new(&stream).read_line(&mut request).ok();
+
+                if request.trim() != FOCUS_REQUEST {
+                    continue;
+                }
+
+                trace!("deadliner was launched again, showing this window instead");
+
+                writeln!(&stream, "{}", FOCUS_REPLY).ok();
+
+                if sender.send(()).is_err() {
+                    return;
+                }
+                on_request();
+            }
+        });
+
+        receiver
+    }
+}
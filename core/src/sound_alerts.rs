@@ -0,0 +1,73 @@
+use std::path::Path;
+
+use chrono::{Local, Timelike};
+use serde::{Deserialize, Serialize};
+
+use crate::SanitizedConf;
+
+/// Plays a sound file when a deadline reaches one of the milestones.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SoundAlertsConf {
+    pub enabled: bool,
+    pub sound_location: String,
+    /// From 0 to 100.
+    pub volume: u8,
+    /// Minutes left at which the sound is played.
+    pub milestones: Vec<u32>,
+    /// No sounds are played from `quiet_from` till `quiet_till`, hours from 0 to 23.
+    pub quiet_hours: bool,
+    pub quiet_from: u32,
+    pub quiet_till: u32,
+}
+
+impl Default for SoundAlertsConf {
+    fn default() -> Self {
+        SoundAlertsConf {
+            enabled: false,
+            sound_location: String::new(),
+            volume: 80,
+            milestones: vec![24 * 60, 60, 10],
+            quiet_hours: true,
+            quiet_from: 22,
+            quiet_till: 8,
+        }
+    }
+}
+
+impl SoundAlertsConf {
+    pub fn is_quiet_hour(&self, hour: u32) -> bool {
+        if !self.quiet_hours {
+            return false;
+        }
+
+        // Quiet hours usually wrap around midnight, Ex: from 22 till 8
+        if self.quiet_from <= self.quiet_till {
+            (self.quiet_from..self.quiet_till).contains(&hour)
+        } else {
+            hour >= self.quiet_from || hour < self.quiet_till
+        }
+    }
+
+    pub fn sanitized(&self) -> Result<SoundAlertsConf, String> {
+        let sound_location = self.sound_location.trim().to_string();
+
+        if self.enabled && !Path::new(&sound_location).is_file() {
+            return Err(String::from("Couldn't find the alert sound file!"));
+        }
+
+        Ok(SoundAlertsConf {
+            sound_location,
+            volume: self.volume.min(100),
+            ..self.clone()
+        })
+    }
+}
+
+/// Whether the alert sound should be played when a deadline reaches `minutes` left.
+pub fn sound_alert_due(conf: &SanitizedConf, minutes: u32) -> bool {
+    let alerts = &conf.sound_alerts;
+
+    alerts.enabled
+        && alerts.milestones.contains(&minutes)
+        && !alerts.is_quiet_hour(Local::now().hour())
+}
@@ -0,0 +1,71 @@
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+
+use crate::{trace, RefreshHealth, SanitizedConf, TextLine};
+
+/// Index the stale warning's line is tagged with instead of a deadline's,
+/// every enabled monitor shows it.
+pub const STALE_WARNING_LINE: usize = usize::MAX;
+
+const WARNING_COLOR: &str = "#FFB020";
+
+/// Warns on the wallpaper when the refreshes kept failing for a while,
+/// so a countdown that stopped moving isn't trusted.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct StaleWarningConf {
+    pub enabled: bool,
+    /// The warning shows once refreshes have been failing for this many hours.
+    pub after_hours: u32,
+}
+
+impl Default for StaleWarningConf {
+    fn default() -> Self {
+        StaleWarningConf {
+            enabled: true,
+            after_hours: 3,
+        }
+    }
+}
+
+/// Ex: "9h" or "2d 4h".
+fn format_age(seconds: i64) -> String {
+    let hours = seconds / 3600;
+
+    match hours {
+        0 => format!("{}m", seconds / 60),
+        1..=47 => format!("{}h", hours),
+        _ => format!("{}d {}h", hours / 24, hours % 24),
+    }
+}
+
+/// "Last updated 9h ago" when the refreshes have been failing for longer than the configured period.
+/// Only failed refreshes count, a computer that was off or asleep didn't miss any updates.
+pub fn stale_warning_line(conf: &SanitizedConf) -> Option<TextLine> {
+    if !conf.stale_warning.enabled {
+        return None;
+    }
+
+    let health = RefreshHealth::load();
+    let failing_since = health.failing_since?;
+    let now = Local::now().timestamp();
+
+    if now - failing_since < conf.stale_warning.after_hours as i64 * 3600 {
+        return None;
+    }
+
+    let text = match health.last_success_at {
+        Some(at) => format!("⚠ Last updated {} ago", format_age(now - at)),
+        None => format!(
+            "⚠ Updates have been failing for {}",
+            format_age(now - failing_since)
+        ),
+    };
+
+    trace!("the wallpaper is stale: {}", text);
+
+    let mut line = TextLine::title(conf, &text);
+    line.font_size = (conf.title_font_size / 2).max(12);
+    line.font_color = WARNING_COLOR.to_string();
+
+    Some(line)
+}
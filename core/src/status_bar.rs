@@ -0,0 +1,107 @@
+use chrono::Duration;
+use serde_json::json;
+
+use crate::{format_deadline, is_paused, trace, SanitizedConf, SanitizedDeadline};
+
+/// What `deadliner status` prints for status bars.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum StatusFormat {
+    /// Waybar's custom module JSON: text, tooltip, class, alt and percentage.
+    Json,
+    /// One line of text for polybar's and i3blocks' script modules.
+    Plain,
+}
+
+impl StatusFormat {
+    pub fn parse(format: &str) -> Result<StatusFormat, String> {
+        match format {
+            "json" => Ok(StatusFormat::Json),
+            "plain" => Ok(StatusFormat::Plain),
+            _ => Err(format!(
+                "Unknown status format `{}`, use json or plain",
+                format
+            )),
+        }
+    }
+}
+
+/// How close the countdown in the bar is, bars style each class differently.
+/// The thresholds are the urgency colors' ones so the bar agrees with the wallpaper.
+fn urgency_class(conf: &SanitizedConf, deadline: &SanitizedDeadline) -> &'static str {
+    let time_left = deadline.time_left();
+
+    if deadline.counts_up {
+        "counting-up"
+    } else if time_left.num_seconds() < 0 {
+        "overdue"
+    } else if time_left <= Duration::hours(conf.urgency_colors.end_hours as i64) {
+        "critical"
+    } else if time_left <= Duration::days(conf.urgency_colors.start_days as i64) {
+        "warning"
+    } else {
+        "normal"
+    }
+}
+
+/// The deadline shown in the bar, the closest one that's not over yet
+/// or the last one to be over when they all are.
+fn bar_deadline(conf: &SanitizedConf) -> Option<&SanitizedDeadline> {
+    let upcoming = conf
+        .deadlines
+        .iter()
+        .filter(|d| d.counts_up || d.minutes_left() > 0)
+        .min_by_key(|d| d.minutes_left());
+
+    upcoming.or_else(|| conf.deadlines.iter().max_by_key(|d| d.minutes_left()))
+}
+
+/// The current countdown formatted for a status bar.
+pub fn status_line(conf: &SanitizedConf, format: StatusFormat) -> String {
+    let deadline = match bar_deadline(conf) {
+        Some(deadline) => deadline,
+        None => {
+            return match format {
+                StatusFormat::Json => {
+                    json!({ "text": "", "class": "none", "alt": "none" }).to_string()
+                }
+                StatusFormat::Plain => String::new(),
+            }
+        }
+    };
+
+    let countdown = format_deadline(conf, deadline, false);
+    let text = if deadline.title.is_empty() {
+        countdown
+    } else {
+        format!("{}: {}", deadline.title, countdown)
+    };
+
+    let class = if is_paused() {
+        "paused"
+    } else {
+        urgency_class(conf, deadline)
+    };
+
+    trace!("status: \"{}\" ({})", text, class);
+
+    match format {
+        StatusFormat::Plain => text,
+        StatusFormat::Json => {
+            let tooltip: Vec<String> = conf
+                .deadlines
+                .iter()
+                .map(|d| format!("{}: {}", d.title, format_deadline(conf, d, false)))
+                .collect();
+
+            json!({
+                "text": text,
+                "tooltip": tooltip.join("\n"),
+                "class": class,
+                "alt": class,
+                "percentage": (deadline.remaining_fraction() * 100.).round() as u8,
+                "minutes_left": deadline.minutes_left(),
+            })
+            .to_string()
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+/// Stress-free mode hides the small units while the deadline is still far away
+/// and only becomes precise near the end.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct StressFreeConf {
+    pub enabled: bool,
+    /// Hours are hidden while more than this many days are left.
+    pub hours_threshold_days: u32,
+    /// Days are hidden while more than this many weeks are left.
+    pub days_threshold_weeks: u32,
+}
+
+impl Default for StressFreeConf {
+    fn default() -> Self {
+        StressFreeConf {
+            enabled: false,
+            hours_threshold_days: 3,
+            days_threshold_weeks: 4,
+        }
+    }
+}
+
+impl StressFreeConf {
+    pub fn hides_hours(&self, remaining_days: i64) -> bool {
+        self.enabled && remaining_days > self.hours_threshold_days as i64
+    }
+
+    pub fn hides_days(&self, remaining_days: i64) -> bool {
+        self.enabled && remaining_days > self.days_threshold_weeks as i64 * 7
+    }
+}
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{rgb_to_hex, SanitizedConf, SanitizedDeadline};
+
+/// A category deadlines can be tagged with, Ex: "uni" or "work",
+/// their lines on the wallpaper are drawn in its color.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DeadlineTag {
+    pub name: String,
+    pub color: [u8; 3],
+}
+
+/// Colors given to new tags in turns, distinct enough to tell the categories apart at a glance.
+const TAG_PALETTE: [[u8; 3]; 6] = [
+    [52, 152, 219],
+    [46, 204, 113],
+    [241, 196, 15],
+    [155, 89, 182],
+    [230, 126, 34],
+    [26, 188, 156],
+];
+
+impl DeadlineTag {
+    /// A new tag with the next color of the palette.
+    pub fn new(name: &str, tags: &[DeadlineTag]) -> DeadlineTag {
+        DeadlineTag {
+            name: name.trim().to_string(),
+            color: TAG_PALETTE[tags.len() % TAG_PALETTE.len()],
+        }
+    }
+}
+
+/// The tag named `name`, tags are matched regardless of the case.
+pub fn find_tag<'a>(tags: &'a [DeadlineTag], name: &str) -> Option<&'a DeadlineTag> {
+    let name = name.trim();
+
+    if name.is_empty() {
+        return None;
+    }
+
+    tags.iter().find(|tag| tag.name.eq_ignore_ascii_case(name))
+}
+
+impl SanitizedConf {
+    /// Hex color of the deadline's tag, `None` for untagged deadlines.
+    pub fn tag_color(&self, deadline: &SanitizedDeadline) -> Option<String> {
+        find_tag(&self.tags, &deadline.tag).map(|tag| {
+            let [r, g, b] = tag.color;
+            rgb_to_hex(r, g, b)
+        })
+    }
+}
@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+
+/// Where the countdown text is placed on the wallpaper.
+#[derive(Debug, PartialEq, Clone, Copy, EnumIter, Serialize, Deserialize, Default)]
+pub enum TextPosition {
+    #[default]
+    Center,
+    TopLeft,
+    TopCenter,
+    TopRight,
+    BottomLeft,
+    BottomCenter,
+    BottomRight,
+    /// `x` and `y` are percentages (0-100) of the free space around the text,
+    /// the offsets are added afterwards in pixels.
+    Custom {
+        x: f32,
+        y: f32,
+        offset_x: i32,
+        offset_y: i32,
+    },
+}
+
+impl std::fmt::Display for TextPosition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Center => "Center",
+                Self::TopLeft => "Top Left",
+                Self::TopCenter => "Top Center",
+                Self::TopRight => "Top Right",
+                Self::BottomLeft => "Bottom Left",
+                Self::BottomCenter => "Bottom Center",
+                Self::BottomRight => "Bottom Right",
+                Self::Custom { .. } => "Custom",
+            }
+        )
+    }
+}
+
+impl TextPosition {
+    /// Returns the top-left coordinates of a `text_width`x`text_height` box placed on a
+    /// `bg_width`x`bg_height` wallpaper, the box is always kept inside the wallpaper.
+    pub fn coords(
+        &self,
+        bg_width: u32,
+        bg_height: u32,
+        text_width: u32,
+        text_height: u32,
+    ) -> (u32, u32) {
+        let free_x = bg_width.saturating_sub(text_width) as f32;
+        let free_y = bg_height.saturating_sub(text_height) as f32;
+
+        // Keep the text away from the screen edges by 5% of the free space
+        // so it doesn't get hidden behind taskbars or desktop icons.
+        let (x_percent, y_percent, offset_x, offset_y) = match *self {
+            Self::Center => (50., 50., 0, 0),
+            Self::TopLeft => (5., 5., 0, 0),
+            Self::TopCenter => (50., 5., 0, 0),
+            Self::TopRight => (95., 5., 0, 0),
+            Self::BottomLeft => (5., 95., 0, 0),
+            Self::BottomCenter => (50., 95., 0, 0),
+            Self::BottomRight => (95., 95., 0, 0),
+            Self::Custom {
+                x,
+                y,
+                offset_x,
+                offset_y,
+            } => (x, y, offset_x, offset_y),
+        };
+
+        let x = (free_x * x_percent.clamp(0., 100.) / 100.) as i64 + offset_x as i64;
+        let y = (free_y * y_percent.clamp(0., 100.) / 100.) as i64 + offset_y as i64;
+
+        (
+            x.clamp(0, free_x as i64) as u32,
+            y.clamp(0, free_y as i64) as u32,
+        )
+    }
+}
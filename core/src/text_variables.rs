@@ -0,0 +1,124 @@
+use std::{process::Command, sync::Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{trace, SanitizedConf, SanitizedDeadline};
+
+/// A value computed at render time and written as `{name}` in templates and titles,
+/// Ex: `{word_count}` of a thesis or `{open_issues}` of a milestone.
+pub trait TextVariable: Sync {
+    /// Ex: "word_count", without the braces.
+    fn name(&self) -> &str;
+
+    fn value(&self, deadline: &SanitizedDeadline, conf: &SanitizedConf) -> Result<String, String>;
+}
+
+static REGISTERED_VARIABLES: Mutex<Vec<&'static dyn TextVariable>> = Mutex::new(Vec::new());
+
+/// Adds a variable that's filled in every deadline's text, the last registered one
+/// with a name wins over the earlier ones and the scripted ones.
+pub fn register_text_variable(variable: &'static dyn TextVariable) {
+    REGISTERED_VARIABLES.lock().unwrap().push(variable);
+}
+
+/// A variable whose value is the first line a command prints, Ex: `wc -w thesis.md`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct ScriptVariableConf {
+    pub name: String,
+    pub command: String,
+    pub args: Vec<String>,
+}
+
+impl ScriptVariableConf {
+    pub fn sanitized(&self) -> Result<ScriptVariableConf, String> {
+        let name = self
+            .name
+            .trim()
+            .trim_start_matches('{')
+            .trim_end_matches('}')
+            .to_string();
+
+        if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            return Err(format!(
+                "\"{}\" isn't a valid variable name, use letters, digits and _",
+                self.name
+            ));
+        }
+        if name == "countdown" {
+            return Err(String::from("{countdown} is already a variable!"));
+        }
+
+        let command = self.command.trim().to_string();
+        if command.is_empty() {
+            return Err(format!("The command of {{{}}} is empty!", name));
+        }
+
+        Ok(ScriptVariableConf {
+            name,
+            command,
+            args: self.args.clone(),
+        })
+    }
+
+    fn value(&self, deadline: &SanitizedDeadline) -> Result<String, String> {
+        let output = Command::new(&self.command)
+            .args(&self.args)
+            .env("DEADLINER_TITLE", &deadline.title)
+            .output()
+            .map_err(|e| format!("couldn't run \"{}\": {}", self.command, e))?;
+
+        if !output.status.success() {
+            return Err(format!(
+                "\"{}\" failed with {}",
+                self.command, output.status
+            ));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_string())
+    }
+}
+
+/// Replaces the registered and the scripted variables used in `text`,
+/// variables that fail are left as they're written.
+pub fn fill_text_variables(
+    text: &str,
+    deadline: &SanitizedDeadline,
+    conf: &SanitizedConf,
+) -> String {
+    // Only compute what's actually used, scripts can be slow
+    if !text.contains('{') {
+        return text.to_string();
+    }
+
+    let mut filled = text.to_string();
+    let registered = REGISTERED_VARIABLES.lock().unwrap();
+
+    for variable in registered.iter().rev() {
+        let placeholder = format!("{{{}}}", variable.name());
+
+        if filled.contains(&placeholder) {
+            match variable.value(deadline, conf) {
+                Ok(value) => filled = filled.replace(&placeholder, &value),
+                Err(e) => trace!("{} couldn't be filled: {}", placeholder, e),
+            }
+        }
+    }
+
+    for variable in &conf.text_variables {
+        let placeholder = format!("{{{}}}", variable.name);
+
+        if filled.contains(&placeholder) {
+            match variable.value(deadline) {
+                Ok(value) => filled = filled.replace(&placeholder, &value),
+                Err(e) => trace!("{} couldn't be filled: {}", placeholder, e),
+            }
+        }
+    }
+
+    filled
+}
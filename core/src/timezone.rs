@@ -0,0 +1,61 @@
+use chrono::{DateTime, Duration, Local, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+/// Parses an IANA time zone name like "Europe/Berlin", an empty name means the local time zone.
+pub fn parse_timezone(name: &str) -> Result<Option<Tz>, String> {
+    let name = name.trim();
+
+    if name.is_empty() {
+        return Ok(None);
+    }
+
+    name.parse::<Tz>()
+        .map(Some)
+        .map_err(|_| format!("Unknown time zone \"{}\"!", name))
+}
+
+/// Resolves a wall clock time in `timezone` (or the local one) to an exact instant,
+/// so the countdown stays correct across DST changes.
+pub fn resolve_deadline(date: NaiveDateTime, timezone: Option<Tz>) -> DateTime<Utc> {
+    match timezone {
+        Some(tz) => resolve_in(&tz, date),
+        None => resolve_in(&Local, date),
+    }
+}
+
+/// Times skipped by a DST change don't exist, they resolve to the first time after the gap,
+/// Ex: 02:30 on the night the clocks jump from 02:00 to 03:00 is 03:00.
+fn resolve_in<T: TimeZone>(tz: &T, date: NaiveDateTime) -> DateTime<Utc> {
+    // Gaps are an hour at most, a whole day when a zone skipped one (Ex: Samoa in 2011)
+    (0..=2 * 24 * 60)
+        .find_map(|minutes| {
+            tz.from_local_datetime(&(date + Duration::minutes(minutes)))
+                .earliest()
+        })
+        .map(|date| date.with_timezone(&Utc))
+        .unwrap_or_else(|| Utc.from_utc_datetime(&date))
+}
+
+/// Trims the world clocks' time zone names and drops the empty ones.
+pub fn sanitize_world_clocks(names: &[String]) -> Result<Vec<String>, String> {
+    let mut sanitized = Vec::new();
+
+    for name in names {
+        if let Some(tz) = parse_timezone(name)? {
+            sanitized.push(tz.name().to_string());
+        }
+    }
+
+    Ok(sanitized)
+}
+
+/// The deadline's wall clock time in each of the time zones,
+/// Ex: "17:00 CET / 11:00 EST / 08:00 PST".
+pub fn world_clock_line(deadline: DateTime<Utc>, timezones: &[String]) -> String {
+    timezones
+        .iter()
+        .filter_map(|name| name.parse::<Tz>().ok())
+        .map(|tz| deadline.with_timezone(&tz).format("%H:%M %Z").to_string())
+        .collect::<Vec<_>>()
+        .join(" / ")
+}
@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Locale, TimeUnit};
+
+/// How the units of an exact countdown are written and joined,
+/// Ex: "2 Days, 4 Hours" or "2d 4h" when compact.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UnitFormat {
+    pub compact: bool,
+    /// Joins the units, the locale's separator is used when it's empty.
+    pub separator: String,
+    /// Drops units that are zero, Ex: "1 Week, 2 Hours" instead of "1 Week, 0 Days, 2 Hours".
+    pub hide_zero_units: bool,
+}
+
+impl Default for UnitFormat {
+    fn default() -> Self {
+        UnitFormat {
+            compact: false,
+            separator: String::new(),
+            hide_zero_units: true,
+        }
+    }
+}
+
+impl UnitFormat {
+    /// Formats the broken down time left from the biggest unit to the smallest one.
+    /// Zero units before the first one that's not zero are never shown.
+    pub fn format(&self, locale: Locale, units: &[(TimeUnit, i64)], keep_zeros: bool) -> String {
+        let units: Vec<String> = units
+            .iter()
+            .skip_while(|(_, value)| *value == 0 && !keep_zeros)
+            .filter(|(_, value)| *value != 0 || !self.hide_zero_units || keep_zeros)
+            .map(|(unit, value)| self.format_unit(locale, *unit, *value))
+            .collect();
+
+        units.join(self.separator(locale))
+    }
+
+    pub fn format_unit(&self, locale: Locale, unit: TimeUnit, count: i64) -> String {
+        if self.compact {
+            locale.format_compact_unit(unit, count)
+        } else {
+            locale.format_unit(unit, count)
+        }
+    }
+
+    fn separator(&self, locale: Locale) -> &str {
+        match (self.separator.is_empty(), self.compact) {
+            (false, _) => &self.separator,
+            (true, true) => " ",
+            (true, false) => locale.separator(),
+        }
+    }
+}
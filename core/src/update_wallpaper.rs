@@ -0,0 +1,805 @@
+use std::{
+    fs::{self, File},
+    io::BufWriter,
+    path::PathBuf,
+};
+
+use chrono::{DateTime, Local, Utc};
+
+use crate::{
+    advance_rotation, apply_branding, background_provider, break_down, deliver_to_targets,
+    draw_agenda, draw_backdrop_box, draw_progress_ring, draw_seasonal_layer, encode_png,
+    enforce_contrast, fill_template, fill_text_variables, font_renderer, fuzzy_countdown,
+    get_profile_cache_dir, hex_to_rgb, is_paused, load_branding, mark_render_finished,
+    mark_render_started, new_path, progress, publish, rgb_to_hex, rotated_deadline,
+    simulate_wallpaper_mode, spanned_bounds, stale_warning_line, trace, unwrap_or_return,
+    with_safe_mode, with_seasonal_accents, world_clock_line, Branding, CountdownStyle,
+    DeadlinerError, DeadlinerEvent, Font, Layout, MonitorConf, OverdueBehavior, SanitizedConf,
+    SanitizedDeadline, ScreenDimensions, TextPosition, TimeUnit, STALE_WARNING_LINE,
+};
+use image::{imageops::FilterType, DynamicImage, RgbaImage};
+use imageproc::drawing::Canvas;
+use wallpaper::Mode;
+
+/// A single line of text drawn on the wallpaper with its own style.
+#[derive(Debug, Clone)]
+pub struct TextLine {
+    pub text: String,
+    pub font_size: u8,
+    pub font_color: String,
+}
+
+impl TextLine {
+    pub fn countdown(conf: &SanitizedConf, text: &str) -> TextLine {
+        TextLine {
+            text: text.to_string(),
+            font_size: conf.font_size,
+            font_color: conf.font_color.clone(),
+        }
+    }
+
+    pub fn title(conf: &SanitizedConf, text: &str) -> TextLine {
+        TextLine {
+            text: text.to_string(),
+            font_size: conf.title_font_size,
+            font_color: conf.title_font_color.clone(),
+        }
+    }
+
+    /// Same line with its font size scaled for a smaller or bigger image.
+    pub fn scaled(&self, factor: f32) -> TextLine {
+        TextLine {
+            font_size: (self.font_size as f32 * factor).clamp(8., 255.) as u8,
+            ..self.clone()
+        }
+    }
+}
+
+pub fn update_wallpaper(
+    conf: &SanitizedConf,
+    test_text_dimensions: bool,
+) -> Result<(), DeadlinerError> {
+    if test_text_dimensions {
+        render_wallpaper(conf, true)?;
+
+        return Ok(());
+    }
+
+    update_wallpaper_unless(conf, || false)
+}
+
+/// Refreshes the wallpaper unless `is_abandoned` says it was given up on by the time it's rendered,
+/// a late render isn't set since a newer one could already be the wallpaper.
+pub fn update_wallpaper_unless(
+    conf: &SanitizedConf,
+    is_abandoned: impl Fn() -> bool,
+) -> Result<(), DeadlinerError> {
+    if is_paused() {
+        trace!("updates are paused, the wallpaper is left as it is");
+
+        return Ok(());
+    }
+
+    publish(DeadlinerEvent::RefreshStarted);
+    advance_rotation(conf);
+
+    let result = render_wallpaper_image(conf, false).and_then(|(wallpaper, mode)| {
+        if is_abandoned() {
+            trace!("the refresh was abandoned, its render is dropped");
+
+            return Ok(());
+        }
+
+        publish(DeadlinerEvent::RenderCompleted);
+
+        deliver_to_targets(&wallpaper, mode, conf).map_err(DeadlinerError::WallpaperBackend)
+    });
+
+    if let Err(e) = &result {
+        publish(DeadlinerEvent::RefreshFailed(e.to_string()));
+    }
+
+    result
+}
+
+/// Renders the wallpaper to the cache dir without setting it,
+/// returns its path and the mode it should be set with.
+pub fn render_wallpaper(
+    conf: &SanitizedConf,
+    test_text_dimensions: bool,
+) -> Result<(String, Mode), DeadlinerError> {
+    let (wallpaper, mode) = render_wallpaper_image(conf, test_text_dimensions)?;
+
+    let file_path = save_wallpaper(&wallpaper, conf)?;
+
+    trace!("rendered {} to be set with {:?} mode", file_path, mode);
+
+    Ok((file_path, mode))
+}
+
+/// Renders the wallpaper in memory, returns it with the mode it should be set with.
+pub fn render_wallpaper_image(
+    conf: &SanitizedConf,
+    test_text_dimensions: bool,
+) -> Result<(DynamicImage, Mode), DeadlinerError> {
+    progress("Rendering the wallpaper…");
+    mark_render_started();
+
+    let rendered = render_styled_wallpaper(&with_safe_mode(conf), test_text_dimensions);
+
+    mark_render_finished();
+
+    rendered
+}
+
+fn render_styled_wallpaper(
+    conf: &SanitizedConf,
+    test_text_dimensions: bool,
+) -> Result<(DynamicImage, Mode), DeadlinerError> {
+    let conf = &with_final_hours(&with_seasonal_accents(conf));
+    let mut lines = deadline_lines(conf, test_text_dimensions);
+
+    if let Some(shown) = rotated_deadline(conf) {
+        lines.retain(|(i, _)| *i == shown);
+    }
+
+    if let Some(warning) = stale_warning_line(conf) {
+        lines.push((STALE_WARNING_LINE, warning));
+    }
+
+    for (i, line) in &lines {
+        trace!(
+            "deadline #{}: \"{}\" (size {}, color {})",
+            i,
+            line.text,
+            line.font_size,
+            line.font_color
+        );
+    }
+
+    let is_spanned = conf.monitors.len() > 1;
+
+    // TODO: Prevent blocking the main thread cause it freezes the UI.
+    let wallpaper = if is_spanned {
+        trace!(
+            "spanning the wallpaper across {} monitors",
+            conf.monitors.len()
+        );
+
+        let monitors_lines: Vec<(MonitorConf, Vec<TextLine>)> = conf
+            .monitors
+            .iter()
+            .map(|monitor| {
+                let monitor_lines = lines
+                    .iter()
+                    // Every monitor shows the focus timer and the stale warning
+                    .filter(|(i, _)| {
+                        conf.focus.enabled
+                            || (*i == STALE_WARNING_LINE && monitor.enabled)
+                            || monitor.shows_deadline(*i)
+                    })
+                    .map(|(_, line)| line.clone())
+                    .collect();
+
+                (monitor.clone(), monitor_lines)
+            })
+            .collect();
+
+        generate_spanned_wallpaper(&monitors_lines, conf)?
+    } else {
+        let lines: Vec<TextLine> = lines.into_iter().map(|(_, line)| line).collect();
+
+        let wallpaper = generate_wallpaper(&lines, conf)?;
+
+        match conf.monitors.first() {
+            Some(monitor) => monitor.calibrate(wallpaper),
+            None => wallpaper,
+        }
+    };
+
+    // A spanned image is already composed for every monitor.
+    let mode = if is_spanned {
+        Mode::Span
+    } else {
+        conf.os_mode().into()
+    };
+
+    Ok((wallpaper, mode))
+}
+
+/// `conf` restyled to be more prominent when the closest deadline is in its final hours.
+pub fn with_final_hours(conf: &SanitizedConf) -> SanitizedConf {
+    let mut conf = conf.clone();
+
+    if !conf.final_hours.is_active(conf.closest_minutes_left()) {
+        return conf;
+    }
+
+    let final_hours = conf.final_hours;
+    let scaled = |size: u8| (size as f32 * final_hours.font_scale).min(255.) as u8;
+
+    trace!(
+        "final hours, scaling the text by {}",
+        final_hours.font_scale
+    );
+
+    conf.font_size = scaled(conf.font_size);
+    conf.title_font_size = scaled(conf.title_font_size);
+    conf.text_position = TextPosition::Center;
+
+    let [r, g, b] = final_hours.color;
+    conf.font_color = rgb_to_hex(r, g, b);
+    conf.urgency_colors.enabled = false;
+
+    conf
+}
+
+/// Lines of every deadline that's still shown, tagged with the deadline's index.
+pub fn deadline_lines(conf: &SanitizedConf, test_text_dimensions: bool) -> Vec<(usize, TextLine)> {
+    let mut lines = Vec::new();
+
+    if conf.focus.enabled {
+        let now = Utc::now();
+        let (phase, _) = conf.focus.phase_at(now);
+
+        lines.push((0, TextLine::title(conf, &phase.to_string())));
+        lines.push((0, TextLine::countdown(conf, &conf.focus.countdown_at(now))));
+
+        return lines;
+    }
+
+    for (i, deadline) in conf.deadlines.iter().enumerate() {
+        let is_over = deadline.minutes_left() <= 0 && !deadline.counts_up && !test_text_dimensions;
+
+        let countdown = match &conf.overdue {
+            _ if !is_over => format_deadline(conf, deadline, test_text_dimensions),
+            OverdueBehavior::CountUp => format_deadline(conf, deadline, test_text_dimensions),
+            OverdueBehavior::Message(msg) => msg.clone(),
+            // Other deadlines that are over are dropped from the wallpaper,
+            // the schedular takes care of the rest once all of them are over.
+            _ => continue,
+        };
+
+        // Every line of a tagged deadline is in its tag's color
+        let tag_color = conf.tag_color(deadline);
+        let tagged = |mut line: TextLine| {
+            if let Some(color) = &tag_color {
+                line.font_color = color.clone();
+            }
+            line
+        };
+
+        if !deadline.title.is_empty() {
+            let title = fill_text_variables(&deadline.title, deadline, conf);
+            lines.push((i, tagged(TextLine::title(conf, &title))));
+        }
+
+        let mut countdown_line = tagged(TextLine::countdown(conf, &countdown));
+
+        if conf.urgency_colors.enabled && !deadline.counts_up {
+            let [r, g, b] = conf.urgency_colors.color_at(deadline.time_left());
+            countdown_line.font_color = rgb_to_hex(r, g, b);
+        }
+
+        lines.push((i, countdown_line));
+
+        if !is_over && !conf.world_clocks.is_empty() {
+            let times = world_clock_line(deadline.date(), &conf.world_clocks);
+            lines.push((i, tagged(TextLine::title(conf, &times))));
+        }
+    }
+
+    lines
+}
+
+/// Formats the time left till `deadline` in its locale and fills its template with it.
+pub fn format_deadline(
+    conf: &SanitizedConf,
+    deadline: &SanitizedDeadline,
+    test_text_dimensions: bool,
+) -> String {
+    format_deadline_at(conf, deadline, Utc::now(), test_text_dimensions)
+}
+
+/// The countdown as it's shown at `now`.
+pub fn format_deadline_at(
+    conf: &SanitizedConf,
+    deadline: &SanitizedDeadline,
+    now: DateTime<Utc>,
+    test_text_dimensions: bool,
+) -> String {
+    let mut diff = deadline.time_left_at(now);
+
+    // Count up the time since the deadline once it's over
+    let is_overdue = diff.num_minutes() < 0 && !deadline.counts_up;
+
+    let mut holidays_note = String::new();
+
+    if conf.workdays.enabled && !is_overdue && !deadline.counts_up {
+        let now = now.with_timezone(&Local).naive_local();
+        let end = deadline.date().with_timezone(&Local).naive_local();
+
+        diff = conf.workdays.working_time(now, end);
+
+        let holidays = conf.workdays.holidays_between(now, end);
+        if holidays > 0 {
+            holidays_note = format!(" {}", deadline.locale.holidays_excluded(holidays));
+        }
+    }
+
+    let (diff, template) = if deadline.counts_up {
+        (-diff, deadline.template.as_str())
+    } else if is_overdue {
+        (-diff, deadline.locale.overdue_template())
+    } else {
+        (diff, deadline.template.as_str())
+    };
+
+    if conf.countdown_style == CountdownStyle::Fuzzy {
+        let countdown = fuzzy_countdown(deadline.locale, diff.num_minutes());
+
+        let text = fill_template(template, &countdown) + &holidays_note;
+
+        return fill_text_variables(&text, deadline, conf);
+    }
+
+    let remaining_days = diff.num_days();
+    let seconds = diff.num_seconds();
+    let mut shown_units = conf.shown_units();
+
+    // Weeks and months don't have a fixed number of working days, count them in days instead
+    if conf.workdays.enabled
+        && shown_units
+            .iter()
+            .any(|unit| unit.seconds() > TimeUnit::Day.seconds())
+    {
+        shown_units.retain(|unit| unit.seconds() < TimeUnit::Day.seconds());
+        shown_units.insert(0, TimeUnit::Day);
+    }
+
+    // Stress-free mode hides the small units while the deadline is still far away,
+    // but only if there's a bigger unit left to show.
+    let is_hidden = |unit: TimeUnit| match unit {
+        TimeUnit::Day => conf.stress_free.hides_days(remaining_days),
+        TimeUnit::Hour | TimeUnit::Minute | TimeUnit::Second => {
+            conf.stress_free.hides_hours(remaining_days)
+        }
+        _ => false,
+    };
+    let has_bigger_unit = |unit: TimeUnit| {
+        shown_units
+            .iter()
+            .any(|bigger| bigger.seconds() > unit.seconds() && seconds >= bigger.seconds())
+    };
+
+    let displayed_units: Vec<TimeUnit> = shown_units
+        .iter()
+        .copied()
+        .filter(|unit| !(is_hidden(*unit) && has_bigger_unit(*unit)))
+        .collect();
+
+    let locale = deadline.locale;
+    let mut countdown = String::new();
+
+    // The time left is rounded to the smallest displayed unit
+    if let Some(smallest) = displayed_units.last() {
+        if seconds >= smallest.seconds() || test_text_dimensions {
+            let units = break_down(seconds, &displayed_units, conf.rounding);
+
+            // Show every unit while testing the text dimensions to get the widest text
+            countdown = conf
+                .unit_format
+                .format(locale, &units, test_text_dimensions);
+        }
+    }
+
+    // If the deadline is close to its ending, show the time left in the biggest
+    // unit that's not zero yet without going below the precision.
+    if countdown.is_empty() {
+        let unit = [TimeUnit::Hour, TimeUnit::Minute, TimeUnit::Second]
+            .into_iter()
+            .filter(|unit| unit.seconds() >= conf.smallest_unit.seconds())
+            .find(|unit| seconds >= unit.seconds())
+            .unwrap_or(conf.smallest_unit);
+
+        countdown = conf
+            .unit_format
+            .format_unit(locale, unit, conf.rounding.round(seconds, unit));
+    }
+
+    let text = fill_template(template, &countdown) + &holidays_note;
+
+    fill_text_variables(&text, deadline, conf)
+}
+
+pub fn generate_wallpaper(
+    lines: &[TextLine],
+    conf: &SanitizedConf,
+) -> Result<DynamicImage, DeadlinerError> {
+    let (branding, background) = load_branding_and_background(conf)?;
+
+    let background = screen_sized_background(background, conf);
+
+    compose_wallpaper(background, lines, conf, &branding).map_err(DeadlinerError::Render)
+}
+
+fn load_branding_and_background(
+    conf: &SanitizedConf,
+) -> Result<(Option<Branding>, DynamicImage), DeadlinerError> {
+    let branding = load_branding(&conf.branding_location).map_err(DeadlinerError::Parse)?;
+
+    let background = load_background(conf, &branding)?;
+
+    Ok((branding, background))
+}
+
+/// The background as the OS would show it with `conf.bg_mode` when it's rendered in the app,
+/// so the text is placed on what's actually visible.
+fn screen_sized_background(background: DynamicImage, conf: &SanitizedConf) -> DynamicImage {
+    if !conf.render_mode_in_app {
+        return background;
+    }
+
+    let screen = conf.screen_dimensions;
+
+    trace!(
+        "applying {:?} mode for a {}x{} screen",
+        conf.bg_mode,
+        screen.width,
+        screen.height
+    );
+
+    simulate_wallpaper_mode(&background, conf.bg_mode, screen, screen.width)
+}
+
+/// Renders a separately sized wallpaper for each monitor with its own lines
+/// and composes them into one image spanning all of the monitors.
+pub fn generate_spanned_wallpaper(
+    monitors_lines: &[(MonitorConf, Vec<TextLine>)],
+    conf: &SanitizedConf,
+) -> Result<DynamicImage, DeadlinerError> {
+    let (branding, background) = load_branding_and_background(conf)?;
+
+    let monitors: Vec<MonitorConf> = monitors_lines.iter().map(|(m, _)| m.clone()).collect();
+    let ([min_x, min_y], ScreenDimensions { width, height }) = spanned_bounds(&monitors);
+
+    let mut spanned = DynamicImage::ImageRgba8(RgbaImage::new(width, height));
+
+    trace!(
+        "virtual desktop: {}x{} starting at ({}, {})",
+        width,
+        height,
+        min_x,
+        min_y
+    );
+
+    for (monitor, lines) in monitors_lines {
+        let ScreenDimensions { width, height } = monitor.dimensions;
+
+        trace!(
+            "monitor \"{}\": {}x{} at ({}, {}) with {} lines",
+            monitor.name,
+            width,
+            height,
+            monitor.position[0],
+            monitor.position[1],
+            lines.len()
+        );
+
+        let monitor_background =
+            background.resize_to_fill(width, height, conf.performance.resize_filter());
+        let wallpaper = compose_wallpaper(monitor_background, lines, conf, &branding)
+            .map_err(DeadlinerError::Render)?;
+        let wallpaper = monitor.calibrate(wallpaper);
+
+        image::imageops::overlay(
+            &mut spanned,
+            &wallpaper,
+            (monitor.position[0] - min_x) as u32,
+            (monitor.position[1] - min_y) as u32,
+        );
+    }
+
+    Ok(spanned)
+}
+
+/// Renders every deadline at the broadcast resolution and encodes it as a PNG.
+pub fn render_broadcast(conf: &SanitizedConf) -> Result<Vec<u8>, String> {
+    let lines: Vec<TextLine> = deadline_lines(conf, false)
+        .into_iter()
+        .map(|(_, line)| line)
+        .collect();
+
+    let branding = load_branding(&conf.branding_location)?;
+
+    let ScreenDimensions { width, height } = conf.broadcast.dimensions;
+    let background = load_background(conf, &branding)?.resize_to_fill(
+        width,
+        height,
+        conf.performance.resize_filter(),
+    );
+
+    let broadcast = compose_wallpaper(background, &lines, conf, &branding)?;
+
+    let mut png = Vec::new();
+    encode_png(&broadcast, conf.png_compression, &mut png)?;
+
+    Ok(png)
+}
+
+/// Draws the text lines and the branding on top of the background.
+pub(crate) fn compose_wallpaper(
+    mut background: DynamicImage,
+    lines: &[TextLine],
+    conf: &SanitizedConf,
+    branding: &Option<Branding>,
+) -> Result<DynamicImage, String> {
+    draw_seasonal_layer(&mut background, conf);
+
+    if !lines.is_empty() {
+        let mut text_image = render_text_block(lines, conf, branding)?;
+
+        if conf.layout == Layout::ProgressRing {
+            let color = branding
+                .as_ref()
+                .and_then(|b| b.font_color_rgb())
+                .or_else(|| hex_to_rgb(&conf.font_color))
+                .unwrap_or([255; 3]);
+            let remaining = conf.closest_remaining_fraction();
+
+            trace!("drawing the progress ring, {:.1}% left", remaining * 100.);
+
+            text_image = draw_progress_ring(&text_image, remaining, color);
+        }
+
+        if background.width() <= text_image.width() || background.height() <= text_image.height() {
+            return Err(String::from(
+                "Font size is bigger than wallpaper's dimensions!",
+            ));
+        }
+
+        let (x, y) = conf.text_position.coords(
+            background.width(),
+            background.height(),
+            text_image.width(),
+            text_image.height(),
+        );
+
+        trace!(
+            "placing {}x{} text on {}x{} background at ({}, {}) for {:?}",
+            text_image.width(),
+            text_image.height(),
+            background.width(),
+            background.height(),
+            x,
+            y,
+            conf.text_position
+        );
+
+        if conf.backdrop.enabled {
+            let area = (x, y, text_image.width(), text_image.height());
+            draw_backdrop_box(&mut background, area, &conf.backdrop);
+        }
+
+        if conf.high_contrast {
+            enforce_contrast(&mut background, &mut text_image, x, y);
+        }
+
+        image::imageops::overlay(&mut background, &text_image, x, y);
+    }
+
+    draw_agenda(&mut background, conf, branding)?;
+
+    if let Some(branding) = branding {
+        trace!(
+            "applying branding, watermark in {:?}",
+            branding.watermark_corner
+        );
+
+        apply_branding(&mut background, branding)?;
+    }
+
+    Ok(background)
+}
+
+pub(crate) fn save_wallpaper(
+    wallpaper: &DynamicImage,
+    conf: &SanitizedConf,
+) -> Result<String, DeadlinerError> {
+    let file_path = get_profile_cache_dir().join("result.png");
+    let temp_path = get_profile_cache_dir().join("result.png.tmp");
+    let cant_save = |e| DeadlinerError::io("Couldn't save result.png", e);
+
+    // Encode into a temp file and rename it over the last render, so a crash mid-encode
+    // never leaves the desktop pointing at a truncated image.
+    let temp_file = File::create(&temp_path).map_err(cant_save)?;
+    encode_png(wallpaper, conf.png_compression, BufWriter::new(temp_file))
+        .map_err(|msg| DeadlinerError::Io { msg, source: None })?;
+
+    trace!(
+        "encoded result.png with {:?} compression",
+        conf.png_compression
+    );
+
+    fs::rename(&temp_path, &file_path).map_err(cant_save)?;
+
+    Ok(file_path.to_string_lossy().into_owned())
+}
+
+pub fn generate_deadline_over_wallpaper(
+    deadline_str: &str,
+    conf: &SanitizedConf,
+) -> Result<String, String> {
+    let branding = load_branding(&conf.branding_location)?;
+
+    let mut text_image =
+        render_text_block(&[TextLine::countdown(conf, deadline_str)], conf, &branding)?;
+
+    let mut background = screen_sized_background(load_background(conf, &branding)?, conf);
+
+    if background.width() <= text_image.width() || background.height() <= text_image.height() {
+        return Err(String::from(
+            "Font size is bigger than wallpaper's dimensions!",
+        ));
+    }
+
+    let (x, y) = conf.text_position.coords(
+        background.width(),
+        background.height(),
+        text_image.width(),
+        text_image.height(),
+    );
+
+    let party_popper = unwrap_or_return!(
+        image::open(new_path("assets/party-popper.png")),
+        "Couldn't read the party popper image!"
+    );
+    let party_popper_size = {
+        let size = (background.width() as f64 * 0.1) as u32;
+        if size > party_popper.width() {
+            party_popper.width()
+        } else {
+            size
+        }
+    };
+
+    let party_popper = image::imageops::resize(
+        &party_popper,
+        party_popper_size,
+        party_popper_size,
+        FilterType::Nearest,
+    );
+
+    if conf.backdrop.enabled {
+        let area = (x, y, text_image.width(), text_image.height());
+        draw_backdrop_box(&mut background, area, &conf.backdrop);
+    }
+
+    if conf.high_contrast {
+        enforce_contrast(&mut background, &mut text_image, x, y);
+    }
+
+    image::imageops::overlay(&mut background, &text_image, x, y);
+
+    // Add party poppers left and right.
+    let offset = 30;
+    let right = background.width() - party_popper.width() - offset;
+    let bottom = background.height() - party_popper.height() - offset;
+
+    image::imageops::overlay(&mut background, &party_popper, offset, bottom);
+    image::imageops::overlay(
+        &mut background,
+        &image::imageops::flip_horizontal(&party_popper),
+        right,
+        bottom,
+    );
+
+    if let Some(branding) = &branding {
+        apply_branding(&mut background, branding)?;
+    }
+
+    Ok(save_wallpaper(&background, conf)?)
+}
+
+/// Location of either the custom font or one of the bundled ones.
+pub fn font_path(conf: &SanitizedConf) -> PathBuf {
+    if conf.font == Font::ChooseFromDisk {
+        PathBuf::from(&conf.custom_font_location)
+    } else {
+        new_path(&format!("assets/fonts/{:?}.ttf", conf.font))
+    }
+}
+
+/// Renders every line on its own and stacks them vertically
+/// into one transparent image with the lines centered horizontally.
+pub(crate) fn render_text_block(
+    lines: &[TextLine],
+    conf: &SanitizedConf,
+    branding: &Option<Branding>,
+) -> Result<RgbaImage, String> {
+    let brand_color = branding
+        .as_ref()
+        .and_then(|b| b.font_color_rgb())
+        .map(|[r, g, b]| rgb_to_hex(r, g, b));
+
+    let renderer = font_renderer(conf)?;
+
+    // Font sizes are bytes, lines that are already big enough aren't supersampled
+    let scale = conf.performance.supersampling();
+    let scale = if lines
+        .iter()
+        .all(|line| line.font_size.checked_mul(scale).is_some())
+    {
+        scale
+    } else {
+        1
+    };
+
+    let mut line_images = Vec::new();
+    for line in lines {
+        let font_color = brand_color.as_ref().unwrap_or(&line.font_color);
+        let font_size = line.font_size * scale;
+
+        let text_png = unwrap_or_return!(
+            renderer.render_text_to_png_data(&line.text, font_size, font_color.as_str()),
+            format!("Couldn't render \"{}\"", line.text)
+        );
+        let line_image = unwrap_or_return!(
+            image::load_from_memory(&text_png.data),
+            format!("Couldn't render \"{}\"", line.text)
+        );
+
+        line_images.push(line_image.to_rgba8());
+    }
+
+    let width = line_images.iter().map(|l| l.width()).max().unwrap_or(0);
+    let height = line_images.iter().map(|l| l.height()).sum();
+
+    let mut block = RgbaImage::new(width, height);
+
+    let mut y = 0;
+    for line_image in line_images {
+        let x = (width - line_image.width()) / 2;
+        image::imageops::overlay(&mut block, &line_image, x, y);
+
+        y += line_image.height();
+    }
+
+    if scale > 1 {
+        let scale = scale as u32;
+
+        trace!("scaling the text down from {}x supersampling", scale);
+
+        block = image::imageops::resize(
+            &block,
+            (width / scale).max(1),
+            (height / scale).max(1),
+            FilterType::Lanczos3,
+        );
+    }
+
+    Ok(block)
+}
+
+pub(crate) fn load_background(
+    conf: &SanitizedConf,
+    branding: &Option<Branding>,
+) -> Result<DynamicImage, DeadlinerError> {
+    let provider = background_provider(&conf.default_bg).map_err(DeadlinerError::Render)?;
+    let background = provider.load(&conf.default_bg, conf, branding)?;
+
+    trace!(
+        "loaded {:?} background: {}x{}",
+        conf.default_bg,
+        background.width(),
+        background.height()
+    );
+
+    if !provider.filterable() {
+        return Ok(background);
+    }
+
+    let filters = conf.performance.background_filters(conf.background_filters);
+
+    Ok(filters.apply(background))
+}
@@ -0,0 +1,90 @@
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+
+/// Blends the countdown's color from a calm color to an urgent one as the deadline gets closer.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct UrgencyColorsConf {
+    pub enabled: bool,
+    pub calm_color: [u8; 3],
+    pub urgent_color: [u8; 3],
+    /// The color starts changing when this many days are left.
+    pub start_days: u32,
+    /// The color is fully urgent when this many hours are left.
+    pub end_hours: u32,
+}
+
+impl Default for UrgencyColorsConf {
+    fn default() -> Self {
+        UrgencyColorsConf {
+            enabled: false,
+            calm_color: [46, 204, 113],
+            urgent_color: [231, 76, 60],
+            start_days: 7,
+            end_hours: 12,
+        }
+    }
+}
+
+impl UrgencyColorsConf {
+    /// Color of the countdown with `time_left` till the deadline.
+    pub fn color_at(&self, time_left: Duration) -> [u8; 3] {
+        let start = Duration::days(self.start_days as i64).num_seconds() as f32;
+        let end = Duration::hours(self.end_hours as i64).num_seconds() as f32;
+        let left = time_left.num_seconds() as f32;
+
+        let urgency = if start <= end {
+            if left <= end {
+                1.
+            } else {
+                0.
+            }
+        } else {
+            ((start - left) / (start - end)).clamp(0., 1.)
+        };
+
+        let mut color = [0; 3];
+        for (i, channel) in color.iter_mut().enumerate() {
+            let (calm, urgent) = (self.calm_color[i] as f32, self.urgent_color[i] as f32);
+
+            *channel = (calm + (urgent - calm) * urgency).round() as u8;
+        }
+
+        color
+    }
+}
+
+/// Makes the countdown impossible to miss in the last hours before a deadline,
+/// whatever the usual styling is.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct FinalHoursConf {
+    pub enabled: bool,
+    /// Kicks in when the closest deadline has less than this many hours left.
+    pub hours: u32,
+    /// The countdown's and the title's font sizes are multiplied by it.
+    pub font_scale: f32,
+    pub color: [u8; 3],
+}
+
+impl Default for FinalHoursConf {
+    fn default() -> Self {
+        FinalHoursConf {
+            enabled: false,
+            hours: 3,
+            font_scale: 1.5,
+            color: [231, 76, 60],
+        }
+    }
+}
+
+impl FinalHoursConf {
+    pub fn sanitized(&self) -> FinalHoursConf {
+        FinalHoursConf {
+            font_scale: self.font_scale.clamp(1., 3.),
+            ..*self
+        }
+    }
+
+    pub fn is_active(&self, closest_minutes_left: i64) -> bool {
+        self.enabled && closest_minutes_left > 0 && closest_minutes_left < self.hours as i64 * 60
+    }
+}
@@ -0,0 +1,110 @@
+use std::{
+    fs::{self, File},
+    io::BufWriter,
+    path::PathBuf,
+};
+
+use chrono::Local;
+use reqwest::{header, StatusCode};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    copy_with_progress, get_cache_dir, http_client, send_with_retry, trace, unique_hash,
+    DownloadResult,
+};
+
+/// What's known about a downloaded URL background, stored next to the image.
+#[derive(Serialize, Deserialize, Default)]
+struct CachedDownload {
+    etag: Option<String>,
+    /// Unix timestamp of the last time the server was asked for the image.
+    fetched_at: i64,
+}
+
+/// Downloads the image at `url`, the cached one is reused for `ttl_hours` and after that
+/// for as long as the server says it didn't change.
+pub fn download_with_ttl(url: &str, ttl_hours: u32, timeout_secs: u32) -> DownloadResult<String> {
+    let (image_path, meta_path) = cache_paths(url);
+
+    let cached: CachedDownload = fs::read_to_string(&meta_path)
+        .ok()
+        .and_then(|meta| serde_json::from_str(&meta).ok())
+        .unwrap_or_default();
+
+    let now = Local::now().timestamp();
+    let is_cached = image_path.exists();
+
+    if is_cached && now - cached.fetched_at < ttl_hours as i64 * 3600 {
+        return path_string(image_path);
+    }
+
+    trace!("downloading {}", url);
+
+    let client = http_client(timeout_secs);
+    let request = || {
+        let request = client.get(url);
+
+        match (is_cached, &cached.etag) {
+            (true, Some(etag)) => request.header(header::IF_NONE_MATCH, etag),
+            _ => request,
+        }
+    };
+
+    let response = match send_with_retry(request) {
+        Ok(response) => response,
+        // An outdated image is better than none while offline
+        Err(_) if is_cached => {
+            trace!("{} isn't reachable, using the cached image", url);
+            return path_string(image_path);
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let etag = response
+        .headers()
+        .get(header::ETAG)
+        .and_then(|etag| etag.to_str().ok())
+        .map(String::from);
+
+    if response.status() == StatusCode::NOT_MODIFIED && is_cached {
+        trace!("{} didn't change since it was cached", url);
+    } else {
+        // Download next to the cached image first so an interrupted download isn't cached
+        let part_path = image_path.with_extension("part");
+
+        let file = File::create(&part_path)?;
+        copy_with_progress(url, response, BufWriter::new(file))?;
+
+        fs::rename(&part_path, &image_path)?;
+    }
+
+    let meta = CachedDownload {
+        etag: etag.or(cached.etag),
+        fetched_at: now,
+    };
+    fs::write(&meta_path, serde_json::to_string(&meta)?)?;
+
+    path_string(image_path)
+}
+
+/// Makes the next update download the image at `url` again.
+pub fn forget_cached_download(url: &str) {
+    let (image_path, meta_path) = cache_paths(url.trim());
+
+    fs::remove_file(image_path).ok();
+    fs::remove_file(meta_path).ok();
+}
+
+fn cache_paths(url: &str) -> (PathBuf, PathBuf) {
+    let hash = unique_hash(url);
+    let cache_dir = get_cache_dir();
+
+    (
+        cache_dir.join(format!("{}.png", hash)),
+        cache_dir.join(format!("{}.json", hash)),
+    )
+}
+
+fn path_string(path: PathBuf) -> DownloadResult<String> {
+    Ok(path.to_str().ok_or("no file path")?.into())
+}
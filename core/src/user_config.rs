@@ -0,0 +1,258 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    net::{Ipv4Addr, TcpListener},
+    path::{Path, PathBuf},
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::{
+    current_sanitized_conf, get_profile_cache_dir, migrate_sanitized_conf, new_path, rgb_to_hex,
+    trace, unwrap_or_return, Background, SanitizedBackground, SanitizedConf,
+};
+
+const CONFIG_FILE: &str = "config.json";
+const MANAGED_FILE: &str = "managed.json";
+const PORT_FILE: &str = "schedular_port.txt";
+
+/// Per-OS-user directory holding the config the schedular runs with,
+/// so users sharing a machine never overwrite each other's deadlines.
+pub fn get_config_dir() -> PathBuf {
+    let config_dir = dirs::config_dir().ok_or("no config dir").unwrap();
+    let config_dir = config_dir.join("deadliner");
+
+    fs::create_dir_all(&config_dir).ok();
+
+    config_dir
+}
+
+pub fn get_config_file() -> PathBuf {
+    get_config_dir().join(CONFIG_FILE)
+}
+
+/// System-wide file that only an admin (or a parent) can write to.
+pub fn get_managed_file() -> PathBuf {
+    #[cfg(target_os = "windows")]
+    let dir = PathBuf::from(
+        std::env::var("ProgramData").unwrap_or_else(|_| String::from("C:\\ProgramData")),
+    );
+    #[cfg(target_os = "macos")]
+    let dir = PathBuf::from("/Library/Application Support");
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let dir = PathBuf::from("/etc");
+
+    dir.join("deadliner").join(MANAGED_FILE)
+}
+
+/// Reads the managed layer, it's a JSON object holding any subset of the settings
+/// saved by the GUI, Ex: `{ "font_size": 80, "branding_location": "/etc/deadliner/school.json" }`.
+pub fn load_managed_layer() -> Result<Map<String, Value>, String> {
+    let managed = get_managed_file();
+
+    if !managed.exists() {
+        return Ok(Map::new());
+    }
+
+    let managed_str = unwrap_or_return!(
+        fs::read_to_string(managed),
+        "Couldn't read the managed configuration!"
+    );
+
+    match serde_json::from_str(&managed_str) {
+        Ok(Value::Object(layer)) => Ok(layer),
+        _ => Err(String::from("Managed configuration is malformed!")),
+    }
+}
+
+/// Names of the settings that are locked by the managed layer.
+pub fn managed_settings() -> Vec<String> {
+    load_managed_layer()
+        .map(|layer| layer.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Overrides the user's settings with the managed ones, users can't opt out of them.
+pub fn apply_managed_layer<T: Serialize + DeserializeOwned>(conf: &T) -> Result<T, String> {
+    let layer = load_managed_layer()?;
+
+    let mut merged = serde_json::to_value(conf).unwrap();
+
+    if let Value::Object(settings) = &mut merged {
+        for (key, value) in layer {
+            settings.insert(key, value);
+        }
+    }
+
+    Ok(unwrap_or_return!(
+        serde_json::from_value(merged),
+        "Managed configuration is malformed!"
+    ))
+}
+
+/// The managed layer as config.json has it, the settings saved differently there
+/// are converted and the ones it doesn't have are left out.
+fn sanitized_managed_layer(layer: Map<String, Value>) -> Result<Map<String, Value>, String> {
+    let mut sanitized = Map::new();
+
+    for (key, value) in layer {
+        match key.as_str() {
+            "font_color" | "title_font_color" => {
+                let [r, g, b]: [u8; 3] = unwrap_or_return!(
+                    serde_json::from_value(value),
+                    "Managed configuration is malformed!"
+                );
+                sanitized.insert(key, json!(rgb_to_hex(r, g, b)));
+            }
+            "default_background" => {
+                let background: Background = unwrap_or_return!(
+                    serde_json::from_value(value),
+                    "Managed configuration is malformed!"
+                );
+                sanitized.insert(String::from("bg_mode"), json!(background.mode()));
+                sanitized.insert(
+                    String::from("default_bg"),
+                    json!(SanitizedBackground::from(background)),
+                );
+            }
+            _ => {
+                sanitized.insert(key, value);
+            }
+        }
+    }
+
+    Ok(sanitized)
+}
+
+/// Applies the managed layer to the config the schedular runs with too,
+/// so editing config.json by hand doesn't get around the locked settings.
+fn apply_managed_layer_to_sanitized(conf: SanitizedConf) -> Result<SanitizedConf, String> {
+    let layer = sanitized_managed_layer(load_managed_layer()?)?;
+
+    if layer.is_empty() {
+        return Ok(conf);
+    }
+
+    let mut merged = serde_json::to_value(conf).unwrap();
+
+    if let Value::Object(settings) = &mut merged {
+        for (key, value) in layer {
+            if settings.contains_key(&key) {
+                settings.insert(key, value);
+            }
+        }
+    }
+
+    Ok(unwrap_or_return!(
+        serde_json::from_value(merged),
+        "Managed configuration is malformed!"
+    ))
+}
+
+fn read_sanitized_conf() -> Result<String, String> {
+    let conf_file = get_config_file();
+    let conf_file = if !conf_file.exists() && new_path(CONFIG_FILE).exists() {
+        new_path(CONFIG_FILE)
+    } else {
+        conf_file
+    };
+
+    Ok(unwrap_or_return!(
+        fs::read_to_string(conf_file),
+        "Can't read Config JSON file!"
+    ))
+}
+
+/// The config as it was saved, an error when it's from an older version.
+pub(crate) fn load_saved_sanitized_conf() -> Result<SanitizedConf, String> {
+    current_sanitized_conf(&read_sanitized_conf()?)
+        .ok_or_else(|| String::from("Config JSON file is from an older version!"))
+}
+
+/// Loads the config the schedular runs with, falling back to the
+/// config.json that older versions used to write next to the binaries.
+/// One from an older version is regenerated from the inputs and saved in its place,
+/// the managed settings are applied over it either way.
+pub fn load_sanitized_conf() -> Result<SanitizedConf, String> {
+    let conf_str = read_sanitized_conf()?;
+
+    if let Some(conf) = current_sanitized_conf(&conf_str) {
+        return apply_managed_layer_to_sanitized(conf);
+    }
+
+    let raw_conf_str = fs::read_to_string(get_profile_cache_dir().join("raw_config.json")).ok();
+    let conf = migrate_sanitized_conf(&conf_str, raw_conf_str.as_deref())?;
+
+    trace!("regenerated the config from an older version");
+
+    fs::write(
+        get_config_file(),
+        serde_json::to_string_pretty(&conf).unwrap(),
+    )
+    .ok();
+
+    apply_managed_layer_to_sanitized(conf)
+}
+
+/// Removes the schedular's config, including the legacy one next to the binaries.
+pub fn remove_sanitized_conf() {
+    fs::remove_file(get_config_file()).ok();
+    fs::remove_file(new_path(CONFIG_FILE)).ok();
+}
+
+/// Every OS user runs their own schedular, so each one gets their own port. It's picked
+/// on the first run from the ones that are free and saved in the user's config dir.
+pub fn get_schedular_port() -> u16 {
+    let port_file = get_config_dir().join(PORT_FILE);
+
+    if let Some(port) = read_port(&port_file) {
+        return port;
+    }
+
+    let port = pick_schedular_port();
+
+    // Another process of this user could've picked one at the same time, the first one wins
+    match OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&port_file)
+    {
+        Ok(mut file) => {
+            write!(file, "{}", port).ok();
+            port
+        }
+        Err(_) => read_port(&port_file).unwrap_or(port),
+    }
+}
+
+fn read_port(path: &Path) -> Option<u16> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// The first port from the installer's one (shifted per user so users rarely try the same
+/// ones) that's free along with the GUI's and the control ports, 1000 and 2000 above it.
+fn pick_schedular_port() -> u16 {
+    let base: u16 = fs::read_to_string(new_path("port.txt"))
+        .unwrap()
+        .trim()
+        .parse()
+        .unwrap();
+
+    let user_offset = get_config_dir()
+        .to_string_lossy()
+        .bytes()
+        .fold(0u32, |acc, b| acc.wrapping_mul(31).wrapping_add(b as u32))
+        % 1000;
+
+    let is_free = |port: u16| TcpListener::bind((Ipv4Addr::LOCALHOST, port)).is_ok();
+
+    (0..1000)
+        .filter_map(|i| base.checked_add(((user_offset + i) % 1000) as u16))
+        .find(|port| {
+            [0, 1000, 2000]
+                .iter()
+                .all(|shift| port.checked_add(*shift).is_some_and(is_free))
+        })
+        .unwrap_or(base)
+}
@@ -0,0 +1,24 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Mutex,
+};
+
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+static LAST_STEP: Mutex<String> = Mutex::new(String::new());
+
+pub fn set_verbose(verbose: bool) {
+    VERBOSE.store(verbose, Ordering::Relaxed);
+}
+
+pub fn is_verbose() -> bool {
+    VERBOSE.load(Ordering::Relaxed)
+}
+
+pub fn record_step(step: String) {
+    *LAST_STEP.lock().unwrap() = step;
+}
+
+/// The last step of rendering that was traced.
+pub fn last_step() -> String {
+    LAST_STEP.lock().unwrap().clone()
+}
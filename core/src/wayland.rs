@@ -0,0 +1,370 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::Duration,
+};
+
+use chrono::Utc;
+use wallpaper::Mode;
+
+use crate::{get_cache_dir, trace, unwrap_or_return};
+
+/// Wallpaper daemons of Wayland compositors the `wallpaper` crate can't set the wallpaper on,
+/// GNOME and KDE on Wayland still go through it.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum WaylandBackend {
+    /// Hyprland's wallpaper daemon, driven through `hyprctl`.
+    Hyprpaper,
+    Swww,
+    /// Works on every wlroots compositor (sway, river, labwc, ...).
+    Swaybg,
+}
+
+pub(crate) fn is_installed(tool: &str) -> bool {
+    env::var_os("PATH")
+        .is_some_and(|paths| env::split_paths(&paths).any(|dir| dir.join(tool).is_file()))
+}
+
+fn succeeds(program: &str, args: &[&str]) -> bool {
+    Command::new(program)
+        .args(args)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+fn output_of(program: &str, args: &[&str]) -> Option<String> {
+    Command::new(program)
+        .args(args)
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// swww's `--resize` for a wallpaper mode, it has no stretching or tiling.
+pub(crate) fn swww_resize(mode: Mode) -> &'static str {
+    match mode {
+        Mode::Fit => "fit",
+        Mode::Center | Mode::Tile => "no",
+        _ => "crop",
+    }
+}
+
+impl WaylandBackend {
+    /// The backend of the current Wayland session, `None` on X11, on other OSes
+    /// and on desktops the `wallpaper` crate handles.
+    pub fn detect() -> Option<WaylandBackend> {
+        if !cfg!(target_os = "linux") || env::var_os("WAYLAND_DISPLAY").is_none() {
+            return None;
+        }
+
+        let desktop = env::var("XDG_CURRENT_DESKTOP")
+            .unwrap_or_default()
+            .to_lowercase();
+        if desktop.contains("gnome") || desktop.contains("kde") {
+            return None;
+        }
+
+        // Running daemons first, they're what the user set up
+        if env::var_os("HYPRLAND_INSTANCE_SIGNATURE").is_some()
+            && succeeds("hyprctl", &["hyprpaper", "listloaded"])
+        {
+            return Some(WaylandBackend::Hyprpaper);
+        }
+
+        if succeeds("swww", &["query"]) {
+            return Some(WaylandBackend::Swww);
+        }
+
+        if is_installed("swaybg") {
+            return Some(WaylandBackend::Swaybg);
+        }
+
+        None
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            WaylandBackend::Hyprpaper => "hyprpaper",
+            WaylandBackend::Swww => "swww",
+            WaylandBackend::Swaybg => "swaybg",
+        }
+    }
+
+    pub fn set(&self, path: &str, mode: Mode) -> Result<(), String> {
+        trace!(
+            "setting the wallpaper with {} ({:?} mode)",
+            self.name(),
+            mode
+        );
+
+        match self {
+            WaylandBackend::Hyprpaper => set_with_hyprpaper(path, mode),
+            WaylandBackend::Swww => {
+                let resize = swww_resize(mode);
+
+                if succeeds(
+                    "swww",
+                    &["img", "--resize", resize, "--transition-type", "none", path],
+                ) {
+                    Ok(())
+                } else {
+                    Err(String::from("Couldn't set the wallpaper with swww!"))
+                }
+            }
+            WaylandBackend::Swaybg => set_with_swaybg(path, mode),
+        }
+    }
+
+    /// The image the backend shows on the first monitor, Ex: to put it back later.
+    pub fn get(&self) -> Result<String, String> {
+        let current = match self {
+            // Ex: "eDP-1 = /home/me/Pictures/mountains.png"
+            WaylandBackend::Hyprpaper => output_of("hyprctl", &["hyprpaper", "listactive"])
+                .and_then(|active| {
+                    active
+                        .lines()
+                        .find_map(|line| line.split_once(" = ").map(|(_, path)| path.to_string()))
+                }),
+            // Ex: "eDP-1: 1920x1080, scale: 1, currently displaying: image: /home/me/mountains.png"
+            WaylandBackend::Swww => output_of("swww", &["query"]).and_then(|query| {
+                query
+                    .lines()
+                    .find_map(|line| line.split_once("image: ").map(|(_, path)| path.to_string()))
+            }),
+            WaylandBackend::Swaybg => running_swaybg_arg(&["-i", "--image"]),
+        };
+
+        current
+            .map(|path| path.trim().to_string())
+            .filter(|path| !path.is_empty())
+            .ok_or_else(|| format!("Couldn't read the wallpaper from {}!", self.name()))
+    }
+
+    /// How the current image is fitted, only swaybg can tell.
+    pub fn get_mode(&self) -> Option<Mode> {
+        if *self != WaylandBackend::Swaybg {
+            return None;
+        }
+
+        match running_swaybg_arg(&["-m", "--mode"])?.as_str() {
+            "center" => Some(Mode::Center),
+            "fit" => Some(Mode::Fit),
+            "fill" => Some(Mode::Crop),
+            "stretch" => Some(Mode::Stretch),
+            "tile" => Some(Mode::Tile),
+            _ => None,
+        }
+    }
+}
+
+/// hyprpaper keeps preloaded images by their path, every render gets its own copy
+/// so it doesn't keep showing the first one.
+fn set_with_hyprpaper(path: &str, mode: Mode) -> Result<(), String> {
+    let copy = get_cache_dir().join(format!("hyprpaper-{}.png", Utc::now().timestamp_millis()));
+    unwrap_or_return!(
+        fs::copy(path, &copy),
+        "Couldn't copy the wallpaper for hyprpaper!"
+    );
+    let copy = copy.to_string_lossy().into_owned();
+
+    let prefix = match mode {
+        Mode::Fit => "contain:",
+        Mode::Tile => "tile:",
+        _ => "",
+    };
+
+    // An empty monitor name sets it on all of them
+    let set = succeeds("hyprctl", &["hyprpaper", "preload", &copy])
+        && succeeds(
+            "hyprctl",
+            &["hyprpaper", "wallpaper", &format!(",{}{}", prefix, copy)],
+        );
+
+    if !set {
+        fs::remove_file(&copy).ok();
+        return Err(String::from("Couldn't set the wallpaper with hyprpaper!"));
+    }
+
+    succeeds("hyprctl", &["hyprpaper", "unload", "unused"]);
+
+    // The previous copies aren't shown anymore
+    if let Ok(entries) = fs::read_dir(get_cache_dir()) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+
+            if name.starts_with("hyprpaper-") && entry.path() != Path::new(&copy) {
+                fs::remove_file(entry.path()).ok();
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The swaybg showing the countdown. It's shared by every process setting the wallpaper
+/// (the GUI, the schedular and the CLI), so whichever one replaces it stops it.
+fn swaybg_pid_path() -> PathBuf {
+    get_cache_dir().join("swaybg.pid")
+}
+
+/// An argument of a running swaybg, Ex: its image, read from its command line since it has no IPC.
+fn running_swaybg_arg(flags: &[&str]) -> Option<String> {
+    fs::read_dir("/proc").ok()?.flatten().find_map(|process| {
+        let comm = fs::read_to_string(process.path().join("comm")).ok()?;
+        if comm.trim() != "swaybg" {
+            return None;
+        }
+
+        let cmdline = fs::read_to_string(process.path().join("cmdline")).ok()?;
+        let args: Vec<&str> = cmdline.split('\0').collect();
+
+        args.iter()
+            .position(|arg| flags.contains(arg))
+            .and_then(|i| args.get(i + 1))
+            .map(|path| path.to_string())
+    })
+}
+
+/// swaybg has no IPC, a new instance is started with the new wallpaper
+/// and the previous one is stopped once the new one had time to draw.
+fn set_with_swaybg(path: &str, mode: Mode) -> Result<(), String> {
+    let mode = match mode {
+        Mode::Center => "center",
+        Mode::Fit => "fit",
+        Mode::Span | Mode::Crop => "fill",
+        Mode::Stretch => "stretch",
+        Mode::Tile => "tile",
+    };
+
+    let swaybg = unwrap_or_return!(
+        Command::new("swaybg")
+            .args(["-i", path, "-m", mode])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn(),
+        "Couldn't run swaybg!"
+    );
+    let pid = swaybg.id();
+
+    // Reaped once it's stopped, even when another process stops it
+    thread::spawn(move || {
+        let mut swaybg = swaybg;
+        swaybg.wait().ok();
+    });
+
+    thread::sleep(Duration::from_millis(300));
+
+    if let Ok(previous) = fs::read_to_string(swaybg_pid_path()) {
+        let previous = previous.trim();
+        // The pid could've been reused by another process since
+        let comm = fs::read_to_string(format!("/proc/{}/comm", previous)).unwrap_or_default();
+
+        if comm.trim() == "swaybg" {
+            succeeds("kill", &[previous]);
+        }
+    }
+
+    fs::write(swaybg_pid_path(), pid.to_string()).ok();
+
+    Ok(())
+}
+
+/// Sets the wallpaper with the session's Wayland backend when there's one
+/// and with the `wallpaper` crate otherwise.
+pub fn set_desktop_wallpaper(path: &str, mode: Mode) -> Result<(), String> {
+    if let Some(backend) = WaylandBackend::detect() {
+        return backend.set(path, mode);
+    }
+
+    unwrap_or_return!(
+        wallpaper::set_mode(mode),
+        "Couldn't set the wallpaper mode!"
+    );
+    unwrap_or_return!(
+        wallpaper::set_from_path(path),
+        "Couldn't set the wallpaper!"
+    );
+
+    Ok(())
+}
+
+/// Reads the current wallpaper with the session's Wayland backend when there's one
+/// and with the `wallpaper` crate otherwise.
+pub fn get_desktop_wallpaper() -> Result<String, String> {
+    if let Some(backend) = WaylandBackend::detect() {
+        return backend.get();
+    }
+
+    Ok(unwrap_or_return!(
+        wallpaper::get(),
+        "Couldn't read the current wallpaper!"
+    ))
+}
+
+/// How the current wallpaper is fitted where it can be read, Ex: to set it back the same way.
+pub fn get_desktop_wallpaper_mode() -> Option<Mode> {
+    if let Some(backend) = WaylandBackend::detect() {
+        return backend.get_mode();
+    }
+
+    if cfg!(target_os = "windows") {
+        // Ex: "    WallpaperStyle    REG_SZ    10"
+        let style = output_of(
+            "reg",
+            &[
+                "query",
+                "HKCU\\Control Panel\\Desktop",
+                "/v",
+                "WallpaperStyle",
+            ],
+        )?;
+        let tiled = output_of(
+            "reg",
+            &[
+                "query",
+                "HKCU\\Control Panel\\Desktop",
+                "/v",
+                "TileWallpaper",
+            ],
+        )
+        .is_some_and(|tiled| tiled.trim_end().ends_with('1'));
+
+        return match style.split_whitespace().last()? {
+            "0" if tiled => Some(Mode::Tile),
+            "0" => Some(Mode::Center),
+            "2" => Some(Mode::Stretch),
+            "6" => Some(Mode::Fit),
+            "10" => Some(Mode::Crop),
+            "22" => Some(Mode::Span),
+            _ => None,
+        };
+    }
+
+    // GNOME and the desktops based on it, the others don't say
+    let desktop = env::var("XDG_CURRENT_DESKTOP").unwrap_or_default();
+    if !(desktop.contains("GNOME") || desktop == "Unity" || desktop == "Pantheon") {
+        return None;
+    }
+
+    // Ex: "'zoom'"
+    let options = output_of(
+        "gsettings",
+        &["get", "org.gnome.desktop.background", "picture-options"],
+    )?;
+
+    match options.trim().trim_matches('\'') {
+        "centered" => Some(Mode::Center),
+        "scaled" => Some(Mode::Fit),
+        "spanned" => Some(Mode::Span),
+        "stretched" => Some(Mode::Stretch),
+        "wallpaper" => Some(Mode::Tile),
+        "zoom" => Some(Mode::Crop),
+        _ => None,
+    }
+}
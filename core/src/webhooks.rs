@@ -0,0 +1,124 @@
+use reqwest::header::CONTENT_TYPE;
+use serde::{Deserialize, Serialize};
+
+use crate::{announcement, http_client, send_with_retry, SanitizedConf, SanitizedDeadline};
+
+/// URLs that are POSTed to when a deadline reaches one of the milestones,
+/// Ex: a Discord or Slack incoming webhook or a Home Assistant automation.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WebhooksConf {
+    pub enabled: bool,
+    pub urls: Vec<String>,
+    /// Minutes left at which the webhooks are called, 0 is when the deadline is over.
+    pub milestones: Vec<u32>,
+}
+
+impl Default for WebhooksConf {
+    fn default() -> Self {
+        WebhooksConf {
+            enabled: false,
+            urls: Vec::new(),
+            milestones: vec![24 * 60, 60, 0],
+        }
+    }
+}
+
+impl WebhooksConf {
+    pub fn sanitized(&self) -> Result<WebhooksConf, String> {
+        let urls: Vec<String> = self
+            .urls
+            .iter()
+            .map(|url| url.trim().to_string())
+            .filter(|url| !url.is_empty())
+            .collect();
+
+        if self.enabled {
+            if urls.is_empty() {
+                return Err(String::from("Add a URL for the webhooks!"));
+            }
+
+            if let Some(url) = urls.iter().find(|url| !url.starts_with("http")) {
+                return Err(format!(
+                    "The webhook {} should start with http:// or https://",
+                    url
+                ));
+            }
+        }
+
+        Ok(WebhooksConf {
+            urls,
+            ..self.clone()
+        })
+    }
+}
+
+/// JSON body POSTed to the webhooks, `content` and `text` carry the same message
+/// so Discord and Slack can show it without any glue in between.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WebhookPayload {
+    pub title: String,
+    /// RFC 3339, Ex: "2024-01-02T00:00:00+00:00".
+    pub deadline: String,
+    /// Ex: "2 Hours remaining" in the deadline's language.
+    pub remaining: String,
+    /// Minutes left at the reached milestone.
+    pub milestone: u32,
+    pub content: String,
+    pub text: String,
+}
+
+/// What's POSTed when `deadline` reaches `minutes` left if that's one of the milestones.
+pub fn webhook_payload(
+    conf: &SanitizedConf,
+    deadline: &SanitizedDeadline,
+    minutes: u32,
+) -> Option<WebhookPayload> {
+    if !conf.webhooks.enabled || !conf.webhooks.milestones.contains(&minutes) {
+        return None;
+    }
+
+    let remaining = if minutes == 0 {
+        deadline.locale.deadline_over().to_string()
+    } else {
+        announcement(deadline.locale, minutes as i64)
+    };
+    let message = if deadline.title.is_empty() {
+        remaining.clone()
+    } else {
+        format!("{}: {}", deadline.title, remaining)
+    };
+
+    Some(WebhookPayload {
+        title: deadline.title.clone(),
+        deadline: deadline.date().to_rfc3339(),
+        remaining,
+        milestone: minutes,
+        content: message.clone(),
+        text: message,
+    })
+}
+
+/// POSTs the payloads to every webhook, returns the ones that failed.
+pub fn send_webhooks(conf: &SanitizedConf, payloads: &[WebhookPayload]) -> Vec<String> {
+    let client = http_client(conf.download_timeout_secs);
+    let mut errors = Vec::new();
+
+    for payload in payloads {
+        let body = serde_json::to_string(payload).unwrap();
+
+        for url in &conf.webhooks.urls {
+            let result = send_with_retry(|| {
+                client
+                    .post(url)
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(body.clone())
+            });
+
+            if let Err(e) = result {
+                errors.push(format!("Couldn't call the webhook {}: {}", url, e));
+            }
+        }
+    }
+
+    errors
+}
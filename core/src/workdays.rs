@@ -0,0 +1,124 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+use crate::public_holidays;
+
+/// Counts only the time left on working days, weekends and holidays are skipped.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WorkdaysConf {
+    pub enabled: bool,
+    /// Days off every week, from Monday to Sunday.
+    pub weekend: [bool; 7],
+    /// Days off formatted as `YYYY-MM-DD`.
+    pub holidays: Vec<String>,
+    /// ISO 3166 code of the country whose public holidays are days off too, Ex: "DE".
+    #[serde(default)]
+    pub country: String,
+}
+
+impl Default for WorkdaysConf {
+    fn default() -> Self {
+        WorkdaysConf {
+            enabled: false,
+            weekend: [false, false, false, false, false, true, true],
+            holidays: Vec::new(),
+            country: String::new(),
+        }
+    }
+}
+
+impl WorkdaysConf {
+    pub fn is_workday(&self, date: NaiveDate) -> bool {
+        !self.is_weekend(date) && !self.is_holiday(date)
+    }
+
+    fn is_weekend(&self, date: NaiveDate) -> bool {
+        self.weekend[date.weekday().num_days_from_monday() as usize]
+    }
+
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.holidays.contains(&date.format("%Y-%m-%d").to_string())
+            || (!self.country.is_empty()
+                && public_holidays(&self.country, date.year()).contains(&date))
+    }
+
+    /// Holidays between `from` and `to` that would've been working days otherwise.
+    pub fn holidays_between(&self, from: NaiveDateTime, to: NaiveDateTime) -> usize {
+        let mut holidays = 0;
+        let mut day = from.date();
+
+        while day <= to.date() {
+            if !self.is_weekend(day) && self.is_holiday(day) {
+                holidays += 1;
+            }
+
+            day += Duration::days(1);
+        }
+
+        holidays
+    }
+
+    /// Time between `from` and `to` that falls on working days.
+    pub fn working_time(&self, from: NaiveDateTime, to: NaiveDateTime) -> Duration {
+        let mut total = Duration::zero();
+        let mut day = from.date();
+
+        while day <= to.date() {
+            if self.is_workday(day) {
+                let start = day.and_hms(0, 0, 0).max(from);
+                let end = (day + Duration::days(1)).and_hms(0, 0, 0).min(to);
+
+                if end > start {
+                    total = total + (end - start);
+                }
+            }
+
+            day += Duration::days(1);
+        }
+
+        total
+    }
+
+    /// Trims the holidays and makes sure they're valid dates.
+    pub fn sanitized(&self) -> Result<WorkdaysConf, String> {
+        let mut holidays = Vec::new();
+
+        for holiday in &self.holidays {
+            let holiday = holiday.trim();
+
+            if holiday.is_empty() {
+                continue;
+            }
+
+            if NaiveDate::parse_from_str(holiday, "%Y-%m-%d").is_err() {
+                return Err(format!(
+                    "Holiday \"{}\" isn't a valid date, Ex: 2022-12-25",
+                    holiday
+                ));
+            }
+
+            holidays.push(holiday.to_string());
+        }
+
+        let country = self.country.trim().to_uppercase();
+
+        if !country.is_empty()
+            && (country.len() != 2 || !country.chars().all(|c| c.is_ascii_alphabetic()))
+        {
+            return Err(format!(
+                "\"{}\" isn't a country code, Ex: US or DE",
+                self.country.trim()
+            ));
+        }
+
+        if self.enabled && self.weekend.iter().all(|day_off| *day_off) {
+            return Err(String::from("There has to be at least one working day!"));
+        }
+
+        Ok(WorkdaysConf {
+            holidays,
+            country,
+            ..self.clone()
+        })
+    }
+}
@@ -0,0 +1,213 @@
+//! Renders the countdown at tricky instants and compares it with `snapshots/countdown.snap`,
+//! run with `UPDATE_SNAPSHOTS=1` to accept intended changes to the output.
+
+use std::{env, fs, path::Path};
+
+use chrono::{DateTime, Utc};
+use deadliner_core::{
+    format_deadline_at, sanitize_inputs_with, Deadline, DeadlinerConf, RoundingMode, SanitizedConf,
+    ScreenDimensions, TimeUnit,
+};
+
+struct Case {
+    name: &'static str,
+    /// In the 12-hour format the schedular reads, Ex: "2024-01-02 12:00 AM".
+    deadline: &'static str,
+    timezone: &'static str,
+    now: &'static str,
+    units: &'static [TimeUnit],
+    rounding: RoundingMode,
+}
+
+const MINUTES: &[TimeUnit] = &[TimeUnit::Day, TimeUnit::Hour, TimeUnit::Minute];
+const SECONDS: &[TimeUnit] = &[
+    TimeUnit::Day,
+    TimeUnit::Hour,
+    TimeUnit::Minute,
+    TimeUnit::Second,
+];
+const HOURS: &[TimeUnit] = &[TimeUnit::Day, TimeUnit::Hour];
+const DAYS: &[TimeUnit] = &[TimeUnit::Month, TimeUnit::Week, TimeUnit::Day];
+
+const CASES: &[Case] = &[
+    Case {
+        name: "one minute before midnight",
+        deadline: "2024-01-02 12:00 AM",
+        timezone: "UTC",
+        now: "2024-01-01T23:59:00Z",
+        units: MINUTES,
+        rounding: RoundingMode::HalfUp,
+    },
+    Case {
+        name: "one second before midnight",
+        deadline: "2024-01-02 12:00 AM",
+        timezone: "UTC",
+        now: "2024-01-01T23:59:59Z",
+        units: SECONDS,
+        rounding: RoundingMode::HalfUp,
+    },
+    Case {
+        name: "half a minute before midnight rounded down",
+        deadline: "2024-01-02 12:00 AM",
+        timezone: "UTC",
+        now: "2024-01-01T23:59:30Z",
+        units: MINUTES,
+        rounding: RoundingMode::Down,
+    },
+    Case {
+        name: "half a minute before midnight rounded up",
+        deadline: "2024-01-02 12:00 AM",
+        timezone: "UTC",
+        now: "2024-01-01T23:59:30Z",
+        units: MINUTES,
+        rounding: RoundingMode::Up,
+    },
+    Case {
+        name: "a day before at midnight",
+        deadline: "2024-01-02 12:00 AM",
+        timezone: "UTC",
+        now: "2024-01-01T00:00:00Z",
+        units: MINUTES,
+        rounding: RoundingMode::HalfUp,
+    },
+    Case {
+        name: "half an hour before midnight in hours",
+        deadline: "2024-01-02 12:00 AM",
+        timezone: "UTC",
+        now: "2024-01-01T23:30:00Z",
+        units: HOURS,
+        rounding: RoundingMode::HalfUp,
+    },
+    Case {
+        name: "across the spring forward DST change",
+        deadline: "2024-03-10 12:00 PM",
+        timezone: "America/New_York",
+        // Noon in New York the day before
+        now: "2024-03-09T17:00:00Z",
+        units: MINUTES,
+        rounding: RoundingMode::HalfUp,
+    },
+    Case {
+        name: "across the fall back DST change",
+        deadline: "2024-11-03 12:00 PM",
+        timezone: "America/New_York",
+        // Noon in New York the day before
+        now: "2024-11-02T16:00:00Z",
+        units: MINUTES,
+        rounding: RoundingMode::HalfUp,
+    },
+    Case {
+        name: "during the skipped DST hour",
+        deadline: "2024-03-10 03:30 AM",
+        timezone: "America/New_York",
+        // 01:30 in New York, 02:30 doesn't exist that night
+        now: "2024-03-10T06:30:00Z",
+        units: MINUTES,
+        rounding: RoundingMode::HalfUp,
+    },
+    Case {
+        name: "to a time skipped by the DST change",
+        // Doesn't exist, it's counted down to 03:00 when the skipped hour ends
+        deadline: "2024-03-10 02:30 AM",
+        timezone: "America/New_York",
+        now: "2024-03-10T06:30:00Z",
+        units: MINUTES,
+        rounding: RoundingMode::HalfUp,
+    },
+    Case {
+        name: "over Feb 29 of a leap year",
+        deadline: "2024-03-01 12:00 AM",
+        timezone: "UTC",
+        now: "2024-02-28T00:00:00Z",
+        units: MINUTES,
+        rounding: RoundingMode::HalfUp,
+    },
+    Case {
+        name: "over Feb 28 of a common year",
+        deadline: "2023-03-01 12:00 AM",
+        timezone: "UTC",
+        now: "2023-02-28T00:00:00Z",
+        units: MINUTES,
+        rounding: RoundingMode::HalfUp,
+    },
+    Case {
+        name: "a month from Feb 29",
+        deadline: "2024-03-29 12:00 AM",
+        timezone: "UTC",
+        now: "2024-02-29T00:00:00Z",
+        units: DAYS,
+        rounding: RoundingMode::HalfUp,
+    },
+    Case {
+        name: "a year from Feb 29",
+        deadline: "2025-03-01 12:00 AM",
+        timezone: "UTC",
+        now: "2024-02-29T00:00:00Z",
+        units: DAYS,
+        rounding: RoundingMode::HalfUp,
+    },
+];
+
+fn base_conf(case: &Case) -> SanitizedConf {
+    let mut conf = DeadlinerConf::new(
+        ScreenDimensions {
+            width: 1920,
+            height: 1080,
+        },
+        vec![],
+    );
+
+    conf.show_months = case.units.contains(&TimeUnit::Month);
+    conf.show_weeks = case.units.contains(&TimeUnit::Week);
+    conf.show_days = case.units.contains(&TimeUnit::Day);
+    conf.show_hours = case.units.contains(&TimeUnit::Hour);
+    conf.show_minutes = case.units.contains(&TimeUnit::Minute);
+    conf.show_seconds = case.units.contains(&TimeUnit::Second);
+    conf.smallest_unit = *case.units.last().unwrap();
+    conf.rounding = case.rounding;
+
+    // Sanitized with any future date, the case's date is put in place afterwards
+    conf.deadlines = vec![Deadline {
+        date: String::from("2999-01-01"),
+        timezone: String::from("UTC"),
+        ..Deadline::default()
+    }];
+
+    // Neither the managed settings nor a config saved on this machine change the output
+    sanitize_inputs_with(&conf, None).unwrap()
+}
+
+fn render(case: &Case) -> String {
+    let mut conf = base_conf(case);
+
+    let deadline = &mut conf.deadlines[0];
+    deadline.deadline_str = case.deadline.to_string();
+    deadline.timezone = Some(case.timezone.to_string());
+    deadline.template = String::from("{countdown}");
+
+    let now: DateTime<Utc> = case.now.parse().unwrap();
+
+    format_deadline_at(&conf, &conf.deadlines[0], now, false)
+}
+
+#[test]
+fn countdown_matches_snapshot() {
+    let rendered: String = CASES
+        .iter()
+        .map(|case| format!("{} => {}\n", case.name, render(case)))
+        .collect();
+
+    let snapshot = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots/countdown.snap");
+
+    if env::var("UPDATE_SNAPSHOTS").is_ok() {
+        fs::write(&snapshot, &rendered).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&snapshot).unwrap();
+
+    assert_eq!(
+        rendered, expected,
+        "the countdown changed, run with UPDATE_SNAPSHOTS=1 if it's intended"
+    );
+}
@@ -0,0 +1,149 @@
+//! Renders representative wallpapers and compares them with the images in `golden/`
+//! with a small tolerance for anti-aliasing differences,
+//! run with `UPDATE_SNAPSHOTS=1` to accept intended changes to the output.
+
+use std::{env, path::Path, process, sync::Once};
+
+use deadliner_core::{
+    render_wallpaper_image, sanitize_inputs_with, Background, Deadline, DeadlinerConf, Font,
+    Layout, OverdueBehavior, Pattern, SanitizedConf, ScreenDimensions, WallpaperMode,
+};
+use image::{DynamicImage, GenericImageView};
+
+/// Channels can be off by this much before a pixel counts as different.
+const CHANNEL_TOLERANCE: u8 = 24;
+/// Part of the pixels that can differ before the images count as different.
+const MAX_DIFFERENT_PIXELS: f64 = 0.005;
+
+/// Points the cache and config dirs at an empty temporary one, so the renders don't read
+/// or write the state of the machine running the tests (Ex: failed refreshes add a warning).
+fn isolate_state_dirs() {
+    static ISOLATED: Once = Once::new();
+
+    ISOLATED.call_once(|| {
+        let dir = env::temp_dir().join(format!("deadliner-golden-{}", process::id()));
+
+        env::set_var("HOME", &dir);
+        env::set_var("XDG_CACHE_HOME", dir.join("cache"));
+        env::set_var("XDG_CONFIG_HOME", dir.join("config"));
+    });
+}
+
+fn conf(configure: impl FnOnce(&mut DeadlinerConf)) -> SanitizedConf {
+    isolate_state_dirs();
+
+    let assets = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets");
+
+    let mut conf = DeadlinerConf::new(
+        ScreenDimensions {
+            width: 480,
+            height: 270,
+        },
+        vec![],
+    );
+
+    // The bundled fonts are looked up next to the executable which tests don't have
+    conf.font = Font::ChooseFromDisk;
+    conf.custom_font_location = assets.join("fonts/PoppinsBlack.ttf").display().to_string();
+    conf.font_size = 40;
+    conf.title_font_size = 20;
+
+    conf.deadlines = vec![Deadline {
+        title: String::from("Thesis submission"),
+        date: String::from("2999-01-01"),
+        timezone: String::from("UTC"),
+        ..Deadline::default()
+    }];
+
+    // The countdown changes every minute, an overdue message doesn't
+    conf.overdue = OverdueBehavior::Message(String::from("Submitted!"));
+
+    configure(&mut conf);
+
+    let mut conf = sanitize_inputs_with(&conf, None).unwrap();
+    conf.deadlines[0].deadline_str = String::from("2000-01-01 12:00 AM");
+
+    conf
+}
+
+fn assert_matches_golden(name: &str, conf: &SanitizedConf) {
+    let (rendered, _) = render_wallpaper_image(conf, false).unwrap();
+    let golden_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("{}.png", name));
+
+    if env::var("UPDATE_SNAPSHOTS").is_ok() {
+        rendered.save(&golden_path).unwrap();
+        return;
+    }
+
+    let golden = image::open(&golden_path).unwrap();
+    let different = different_pixels(&rendered, &golden);
+
+    if different > MAX_DIFFERENT_PIXELS {
+        let actual_path = env::temp_dir().join(format!("{}.actual.png", name));
+        rendered.save(&actual_path).unwrap();
+
+        panic!(
+            "{:.2}% of {} differs from the golden image, see {}, run with UPDATE_SNAPSHOTS=1 if it's intended",
+            different * 100.,
+            name,
+            actual_path.display()
+        );
+    }
+}
+
+/// Part of the pixels that differ beyond the tolerance, all of them when the sizes differ.
+fn different_pixels(a: &DynamicImage, b: &DynamicImage) -> f64 {
+    if a.dimensions() != b.dimensions() {
+        return 1.;
+    }
+
+    let (a, b) = (a.to_rgba8(), b.to_rgba8());
+
+    let different = a
+        .pixels()
+        .zip(b.pixels())
+        .filter(|(a, b)| {
+            a.0.iter()
+                .zip(b.0.iter())
+                .any(|(a, b)| a.abs_diff(*b) > CHANNEL_TOLERANCE)
+        })
+        .count();
+
+    different as f64 / (a.width() * a.height()) as f64
+}
+
+#[test]
+fn solid_background() {
+    let conf = conf(|conf| {
+        conf.default_background = Background::Solid {
+            color: [30, 30, 60],
+            pattern: Pattern::None,
+        }
+    });
+
+    assert_matches_golden("solid_background", &conf);
+}
+
+#[test]
+fn image_background() {
+    let conf = conf(|conf| {
+        conf.default_background = Background::FromDisk {
+            location: Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("../gui/assets/party-popper.png")
+                .display()
+                .to_string(),
+            mode: WallpaperMode::Crop,
+        };
+    });
+
+    assert_matches_golden("image_background", &conf);
+}
+
+#[test]
+fn progress_ring() {
+    let conf = conf(|conf| conf.layout = Layout::ProgressRing);
+
+    assert_matches_golden("progress_ring", &conf);
+}
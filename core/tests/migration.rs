@@ -0,0 +1,88 @@
+//! Reads the inputs and the config saved by the first versions of deadliner,
+//! before the configs were versioned.
+
+use deadliner_core::{
+    migrate_config, migrate_sanitized_conf, Background, SanitizedBackground, CONFIG_VERSION,
+};
+
+/// raw_config.json as the first versions saved it, with a single deadline as top-level fields.
+const BASELINE_RAW_CONFIG: &str = r#"{
+  "screen_dimensions": { "width": 1920, "height": 1080 },
+  "default_background": { "Solid": [12, 34, 56] },
+  "show_months": false,
+  "show_weeks": false,
+  "show_days": true,
+  "show_hours": true,
+  "font": "PoppinsBlack",
+  "font_size": 100,
+  "font_color": [255, 255, 255],
+  "custom_font_location": "",
+  "date": "2099-06-01",
+  "hours": "9",
+  "minutes": "30",
+  "period": "AM"
+}"#;
+
+/// config.json saved next to it, with the deadline already formatted for the schedular.
+const BASELINE_CONFIG: &str = r##"{
+  "screen_dimensions": { "width": 1920, "height": 1080 },
+  "default_bg": { "Solid": { "rgb": [12, 34, 56], "hex": "#0C2238" } },
+  "bg_mode": "Center",
+  "show_months": false,
+  "show_weeks": false,
+  "show_days": true,
+  "show_hours": true,
+  "font": "PoppinsBlack",
+  "font_size": 100,
+  "font_color": "#FFFFFF",
+  "custom_font_location": "",
+  "deadline_str": "2099-06-01 9:30 AM"
+}"##;
+
+#[test]
+fn migrates_baseline_inputs() {
+    let conf = migrate_config(BASELINE_RAW_CONFIG).unwrap();
+
+    assert_eq!(conf.version, CONFIG_VERSION);
+    assert_eq!(conf.deadlines.len(), 1);
+    assert_eq!(conf.deadlines[0].date, "2099-06-01");
+    assert_eq!(conf.deadlines[0].hours, "9");
+    assert_eq!(conf.deadlines[0].minutes, "30");
+    assert!(matches!(
+        conf.default_background,
+        Background::Solid {
+            color: [12, 34, 56],
+            ..
+        }
+    ));
+}
+
+#[test]
+fn regenerates_baseline_config() {
+    let conf = migrate_sanitized_conf(BASELINE_CONFIG, Some(BASELINE_RAW_CONFIG)).unwrap();
+
+    assert_eq!(conf.version, CONFIG_VERSION);
+    assert_eq!(conf.deadlines.len(), 1);
+    assert_eq!(conf.deadlines[0].deadline_str, "2099-06-01 09:30 AM");
+    assert_eq!(conf.font_color, "#FFFFFF");
+    assert!(matches!(conf.default_bg, SanitizedBackground::Solid { .. }));
+}
+
+#[test]
+fn keeps_current_config() {
+    let conf = migrate_sanitized_conf(BASELINE_CONFIG, Some(BASELINE_RAW_CONFIG)).unwrap();
+    let saved = serde_json::to_string(&conf).unwrap();
+
+    // The inputs aren't needed once the config is from this version
+    let read = migrate_sanitized_conf(&saved, None).unwrap();
+
+    assert_eq!(
+        read.deadlines[0].deadline_str,
+        conf.deadlines[0].deadline_str
+    );
+}
+
+#[test]
+fn refuses_baseline_config_without_inputs() {
+    assert!(migrate_sanitized_conf(BASELINE_CONFIG, None).is_err());
+}
@@ -1,11 +1,12 @@
 use std::{
-    env, fs, io,
+    env, fs,
     path::{Path, PathBuf},
 };
 #[cfg(windows)]
 use winres::WindowsResource;
 
-const COPY_DIR: &'static str = "assets";
+const COPY_DIR: &str = "assets";
+const CORE_FONTS_DIR: &str = "../core/assets/fonts";
 
 /// A helper function for recursively copying a directory.
 fn copy_dir<P, Q>(from: P, to: Q)
@@ -50,6 +51,13 @@ fn main() {
     // Copy the directory
     copy_dir(COPY_DIR, &out);
 
+    // The bundled fonts belong to the core crate, they're copied next to the other assets
+    // so they can still be replaced on disk
+    fs::create_dir(out.join("fonts")).unwrap();
+    copy_dir(CORE_FONTS_DIR, out.join("fonts"));
+    println!("cargo:rerun-if-changed={}", COPY_DIR);
+    println!("cargo:rerun-if-changed={}", CORE_FONTS_DIR);
+
     // Add Icon to exec
     #[cfg(windows)]
     {
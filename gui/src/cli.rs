@@ -0,0 +1,279 @@
+use std::{fs::File, io::BufWriter, path::Path};
+
+use crate::{
+    check_config, detect_backend, encode_png, export_animation, export_deadlines, export_ics,
+    export_share_card, get_profile_cache_dir, is_quiet, load_sanitized_conf, paint_error,
+    paint_success, render_wallpaper_image, run_backend_tests, run_doctor, run_tui,
+    send_control_command, set_cli_output, set_verbose, status_line,
+    stop_and_restore_original_wallpaper, sync_calendar, trace, uninstall_cleanup, AnimationStyle,
+    ShareCardSize, StatusFormat,
+};
+
+const USAGE: &str = "Usage: deadliner [command] [-v] [-q] [--no-color]
+
+Runs the GUI when no command is given.
+
+Options:
+    -v, --verbose        Log every step of rendering the wallpaper
+    -q, --quiet          Only print errors and the output of status and control, Ex: for cron
+        --no-color       Print without colors, also off with NO_COLOR or outside of a terminal
+
+Commands:
+    check                Validate your config and the files and URLs it uses
+    doctor               Test setting the wallpaper on this desktop
+    test-backend         Test every installed Linux wallpaper backend and report which ones work
+        --fake-display   Test them against a headless Xvfb display instead of your session
+    --dry-run            Render the wallpaper from your saved config without setting it
+    sync                 Sync the deadlines from your calendar
+    restore-wallpaper    Stop the countdown and set your original wallpaper back
+    uninstall-cleanup    Restore your original wallpaper and remove all of deadliner's data
+    export-gif <file>    Export a looping GIF of the countdown for sharing
+        --final-seconds  Animate the final 10 seconds instead of pulsing
+    export-card <file>   Export a 1200x630 PNG card for posting on social media
+        --square         Export a 1080x1080 card instead
+    export-ics <file>    Export the deadlines as a calendar with reminders at your milestones
+    export-deadlines <file>
+                         Export the deadlines and their history as CSV, or JSON for a .json file
+    tui                  Edit your deadline from the terminal, Ex: over SSH
+    status               Print the current countdown for status bars (waybar, polybar, i3blocks)
+        --format <json|plain>
+                         JSON with the urgency class for waybar, plain text by default
+    control <command>    Control the running deadliner, answers with JSON:
+                         refresh, pause, resume, set-deadline <YYYY-MM-DD HH:MM>, query
+    help                 Print this message";
+
+/// Runs `deadliner <command>` without opening the GUI,
+/// returns the exit code or `None` if there's no command to run.
+pub fn run_cli(args: &[String]) -> Option<i32> {
+    let is_verbose_flag = |arg: &String| arg == "-v" || arg == "--verbose";
+    let is_quiet_flag = |arg: &String| arg == "-q" || arg == "--quiet";
+    let is_no_color_flag = |arg: &String| arg == "--no-color";
+
+    set_verbose(args.iter().any(is_verbose_flag));
+    let quiet = args.iter().any(is_quiet_flag);
+    let color = !args.iter().any(is_no_color_flag);
+
+    let args: Vec<String> = args
+        .iter()
+        .filter(|arg| !is_verbose_flag(arg) && !is_quiet_flag(arg) && !is_no_color_flag(arg))
+        .cloned()
+        .collect();
+    let command = args.get(1)?;
+
+    // Set once there's a command, the GUI doesn't print any progress
+    set_cli_output(quiet, color);
+
+    let result = match command.as_str() {
+        "check" => check(),
+        "doctor" => doctor(),
+        "test-backend" => test_backend(&args[2..]),
+        "--dry-run" => dry_run(),
+        "sync" => sync(),
+        "restore-wallpaper" => stop_and_restore_original_wallpaper()
+            .map(|_| String::from("Restored your original wallpaper.")),
+        "uninstall-cleanup" => uninstall_cleanup(),
+        "export-gif" => export_gif(&args[2..]),
+        "export-card" => export_card(&args[2..]),
+        "export-ics" => export_calendar(&args[2..]),
+        "export-deadlines" => export_deadlines_file(&args[2..]),
+        "tui" => run_tui(),
+        "status" => status(&args[2..]),
+        "control" => control(&args[2..]),
+        "help" | "--help" | "-h" => Ok(String::from(USAGE)),
+        _ => Err(format!("Unknown command `{}`\n\n{}", command, USAGE)),
+    };
+
+    // What status and control print is read by scripts and bars, it's never quieted
+    let prints_output = matches!(
+        command.as_str(),
+        "status" | "control" | "help" | "--help" | "-h"
+    );
+
+    match result {
+        Ok(msg) => {
+            if prints_output {
+                println!("{}", msg);
+            } else if !is_quiet() {
+                println!("{}", paint_success(&msg));
+            }
+            Some(0)
+        }
+        Err(msg) => {
+            eprintln!("{}", paint_error(&msg));
+            Some(1)
+        }
+    }
+}
+
+fn check() -> Result<String, String> {
+    let report = check_config();
+
+    if report.has_errors() {
+        Err(report.summary())
+    } else {
+        Ok(report.summary())
+    }
+}
+
+fn doctor() -> Result<String, String> {
+    let report = run_doctor();
+
+    if report.has_errors() {
+        Err(report.summary())
+    } else {
+        Ok(report.summary())
+    }
+}
+
+fn test_backend(args: &[String]) -> Result<String, String> {
+    let report = run_backend_tests(args.iter().any(|arg| arg == "--fake-display"));
+
+    if report.has_errors() {
+        Err(report.summary())
+    } else {
+        Ok(report.summary())
+    }
+}
+
+fn dry_run() -> Result<String, String> {
+    let conf = load_sanitized_conf()?;
+
+    trace!(
+        "resolved config:\n{}",
+        serde_json::to_string_pretty(&conf).unwrap()
+    );
+
+    let (desktop, backend) = detect_backend();
+    trace!("the wallpaper would be set with {} on {}", backend, desktop);
+
+    // Rendered next to result.png instead of over it, the desktop still shows that one
+    let (wallpaper, mode) = render_wallpaper_image(&conf, false)?;
+    let file_path = get_profile_cache_dir().join("dry_run.png");
+
+    let file = match File::create(&file_path) {
+        Ok(file) => file,
+        Err(_) => return Err(format!("Couldn't save {}", file_path.display())),
+    };
+    encode_png(&wallpaper, conf.png_compression, BufWriter::new(file))?;
+
+    Ok(format!(
+        "Dry run: rendered {} without setting it ({:?} mode with {})",
+        file_path.display(),
+        mode,
+        backend
+    ))
+}
+
+fn sync() -> Result<String, String> {
+    let conf = load_sanitized_conf()?;
+
+    if !conf.calendar_sync.enabled {
+        return Err(String::from(
+            "Calendar sync is off, turn it on in the GUI first",
+        ));
+    }
+
+    let changed = sync_calendar(&conf)?;
+
+    Ok(if changed {
+        String::from("Synced the deadlines from your calendar.")
+    } else {
+        String::from("The deadlines from your calendar didn't change.")
+    })
+}
+
+fn export_gif(args: &[String]) -> Result<String, String> {
+    let output = match args.iter().find(|arg| !arg.starts_with("--")) {
+        Some(output) => output,
+        None => return Err(format!("Missing the output file\n\n{}", USAGE)),
+    };
+
+    let style = if args.iter().any(|arg| arg == "--final-seconds") {
+        AnimationStyle::FinalSeconds
+    } else {
+        AnimationStyle::Pulse
+    };
+
+    let conf = load_sanitized_conf()?;
+
+    export_animation(&conf, style, Path::new(output))?;
+
+    Ok(format!("Exported the countdown to {}", output))
+}
+
+fn export_card(args: &[String]) -> Result<String, String> {
+    let output = match args.iter().find(|arg| !arg.starts_with("--")) {
+        Some(output) => output,
+        None => return Err(format!("Missing the output file\n\n{}", USAGE)),
+    };
+
+    let size = if args.iter().any(|arg| arg == "--square") {
+        ShareCardSize::Square
+    } else {
+        ShareCardSize::Landscape
+    };
+
+    let conf = load_sanitized_conf()?;
+
+    export_share_card(&conf, size, Path::new(output))?;
+
+    Ok(format!("Exported the share card to {}", output))
+}
+
+fn export_calendar(args: &[String]) -> Result<String, String> {
+    let output = match args.first() {
+        Some(output) => output,
+        None => return Err(format!("Missing the output file\n\n{}", USAGE)),
+    };
+
+    let conf = load_sanitized_conf()?;
+
+    export_ics(&conf, Path::new(output))?;
+
+    Ok(format!("Exported the deadlines to {}", output))
+}
+
+fn export_deadlines_file(args: &[String]) -> Result<String, String> {
+    let output = match args.first() {
+        Some(output) => output,
+        None => return Err(format!("Missing the output file\n\n{}", USAGE)),
+    };
+
+    let conf = load_sanitized_conf()?;
+
+    export_deadlines(&conf, Path::new(output))?;
+
+    Ok(format!("Exported the deadlines to {}", output))
+}
+
+fn status(args: &[String]) -> Result<String, String> {
+    let format = match args.iter().position(|arg| arg == "--format") {
+        Some(i) => match args.get(i + 1) {
+            Some(format) => StatusFormat::parse(format)?,
+            None => return Err(format!("Missing the status format\n\n{}", USAGE)),
+        },
+        None => StatusFormat::Plain,
+    };
+
+    let conf = load_sanitized_conf()?;
+
+    Ok(status_line(&conf, format))
+}
+
+fn control(args: &[String]) -> Result<String, String> {
+    if args.is_empty() {
+        return Err(format!("Missing the command to send\n\n{}", USAGE));
+    }
+
+    let response = send_control_command(&args.join(" "))?;
+
+    // Scripts get the JSON either way, the exit code tells them if it worked
+    let ok = serde_json::from_str::<serde_json::Value>(&response)
+        .is_ok_and(|response| response["ok"] == true);
+
+    if ok {
+        Ok(response)
+    } else {
+        Err(response)
+    }
+}
@@ -0,0 +1,149 @@
+use std::{fmt::Debug, hash::Hash};
+
+use chrono::{Datelike, Duration, Local, NaiveDate};
+use eframe::{
+    egui::{self, Area, Frame, Grid, Order, RichText, Ui},
+    epaint::Color32,
+};
+
+use crate::{PADDING, YELLOW};
+
+/// Month shown by an open calendar popup, `None` while it's closed.
+#[derive(Clone, Copy, Default)]
+struct CalendarState {
+    shown_month: Option<NaiveDate>,
+}
+
+/// A date input with a calendar popup that always writes a valid `YYYY-MM-DD`,
+/// typing natural dates like "next friday" in the input still works.
+/// `id_source` has to be unique for every date picker that's shown.
+pub fn render_date_picker(
+    ui: &mut Ui,
+    id_source: impl Hash + Debug,
+    label: &str,
+    date: &mut String,
+    placeholder: &str,
+) {
+    let popup_id = ui.make_persistent_id(("date_picker", id_source));
+
+    ui.horizontal(|ui| {
+        ui.label(label);
+
+        ui.add(
+            egui::TextEdit::singleline(date)
+                .desired_width(95.)
+                .hint_text(RichText::new(placeholder).color(Color32::from_white_alpha(20))),
+        );
+
+        let calendar_button = ui.button("📅").on_hover_text("Pick from a calendar");
+
+        let mut state: CalendarState = ui.memory().data.get_temp(popup_id).unwrap_or_default();
+
+        if calendar_button.clicked() {
+            state.shown_month = match state.shown_month {
+                Some(_) => None,
+                None => Some(first_of_month(picked_date(date))),
+            };
+        }
+
+        if let Some(shown_month) = state.shown_month {
+            let popup = Area::new(popup_id)
+                .order(Order::Foreground)
+                .fixed_pos(calendar_button.rect.left_bottom())
+                .show(ui.ctx(), |ui| {
+                    Frame::popup(ui.style())
+                        .show(ui, |ui| calendar(ui, shown_month, date))
+                        .inner
+                });
+
+            state.shown_month = popup.inner;
+
+            // Close the calendar when clicking anywhere else
+            let clicked_outside = ui.input().pointer.any_click()
+                && ui.input().pointer.interact_pos().is_some_and(|pos| {
+                    !popup.response.rect.contains(pos) && !calendar_button.rect.contains(pos)
+                });
+
+            if clicked_outside || ui.input().key_pressed(egui::Key::Escape) {
+                state.shown_month = None;
+            }
+        }
+
+        ui.memory().data.insert_temp(popup_id, state);
+    });
+}
+
+/// Renders the month grid, returns the month to show next or `None` once a day is picked.
+fn calendar(ui: &mut Ui, shown_month: NaiveDate, date: &mut String) -> Option<NaiveDate> {
+    let mut next_month = Some(shown_month);
+    let today = Local::now().naive_local().date();
+    let selected = picked_date(date);
+
+    ui.horizontal(|ui| {
+        if ui.small_button("◀").clicked() {
+            next_month = Some(first_of_month(shown_month - Duration::days(1)));
+        }
+
+        ui.label(shown_month.format("%B %Y").to_string());
+
+        if ui.small_button("▶").clicked() {
+            next_month = Some(first_of_month(shown_month + Duration::days(31)));
+        }
+    });
+
+    ui.add_space(PADDING);
+
+    Grid::new("calendar_days")
+        .num_columns(7)
+        .spacing([4., 4.])
+        .show(ui, |ui| {
+            for weekday in ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"] {
+                ui.label(RichText::new(weekday).color(Color32::from_white_alpha(120)));
+            }
+            ui.end_row();
+
+            // Weeks start on Monday
+            let leading_days = shown_month.weekday().num_days_from_monday();
+            for _ in 0..leading_days {
+                ui.label("");
+            }
+
+            let mut day = shown_month;
+            while day.month() == shown_month.month() {
+                let mut text = RichText::new(format!("{:>2}", day.day()));
+                if day == today {
+                    text = text.color(YELLOW);
+                }
+
+                if ui.selectable_label(day == selected, text).clicked() {
+                    *date = day.format("%Y-%m-%d").to_string();
+                    next_month = None;
+                }
+
+                if day.weekday().num_days_from_monday() == 6 {
+                    ui.end_row();
+                }
+
+                day += Duration::days(1);
+            }
+        });
+
+    ui.add_space(PADDING);
+
+    if ui.button("Today").clicked() {
+        *date = today.format("%Y-%m-%d").to_string();
+        next_month = None;
+    }
+
+    next_month
+}
+
+/// The date currently in the input, falling back to today.
+fn picked_date(date: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+        .unwrap_or_else(|_| Local::now().naive_local().date())
+}
+
+fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap()
+}
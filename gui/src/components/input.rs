@@ -1,10 +1,12 @@
+use std::ops::RangeInclusive;
+
 use eframe::{
     self,
     egui::{self, RichText, TextBuffer, Ui},
     epaint::Color32,
 };
 
-pub fn render_input_with_label<'a, T>(ui: &mut Ui, label: &str, value: &'a mut T, placeholder: &str)
+pub fn render_input_with_label<T>(ui: &mut Ui, label: &str, value: &mut T, placeholder: &str)
 where
     T: TextBuffer,
 {
@@ -19,7 +21,7 @@ where
     });
 }
 
-pub fn render_input<'a, T>(ui: &mut Ui, value: &'a mut T, placeholder: &str, width: f32)
+pub fn render_input<T>(ui: &mut Ui, value: &mut T, placeholder: &str, width: f32)
 where
     T: TextBuffer,
 {
@@ -29,3 +31,21 @@ where
             .hint_text(RichText::new(placeholder).color(Color32::from_white_alpha(20))),
     );
 }
+
+/// Bounded number input for a value kept as text, anything out of `range` is clamped
+/// so it can never hold an invalid value.
+pub fn render_stepper(ui: &mut Ui, value: &mut String, range: RangeInclusive<u32>) {
+    let mut number = value
+        .trim()
+        .parse::<u32>()
+        .unwrap_or(*range.start())
+        .clamp(*range.start(), *range.end());
+
+    ui.add(
+        egui::DragValue::new(&mut number)
+            .clamp_range(range)
+            .speed(0.1),
+    );
+
+    *value = number.to_string();
+}
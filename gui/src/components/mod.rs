@@ -1,4 +1,5 @@
 mod button;
+mod date_picker;
 mod footer;
 mod header;
 mod input;
@@ -6,6 +7,7 @@ mod line;
 mod section;
 
 pub use button::*;
+pub use date_picker::*;
 pub use footer::*;
 pub use header::*;
 pub use input::*;
@@ -4,7 +4,7 @@ use crate::PADDING;
 
 pub fn render_section<T>(ui: &mut Ui, header_txt: &str, add_contents: T)
 where
-    T: FnOnce(&mut Ui) -> (),
+    T: FnOnce(&mut Ui),
 {
     ui.heading(header_txt);
     ui.add_space(PADDING);
@@ -1,9 +1,28 @@
 use crate::{
-    button, draw_line, get_cache_dir, get_current_file_ext, get_file_name_from_path,
-    is_string_numeric, new_path, render_footer, render_header, render_input,
-    render_input_with_label, render_section, save_inputs, unwrap_or_return, BACKGROUND, BLACK,
-    GREY_WHITE, MARGIN, PADDING, SECONDARY, SECONDARY_BRIGHT, SECONDARY_DARK, WHITE, YELLOW,
+    active_profile, announcement, apply_managed_layer, archive_passed_deadlines, button,
+    delete_profile, download_progress, draw_line, export_deadlines, export_ics, export_settings,
+    export_share_card, find_tag, folder_images, forget_cached_download, get_file_name_from_path,
+    import_settings, is_paused, is_safe_mode, list_profiles, load_archive, load_calendar, load_csv,
+    load_sanitized_conf, managed_settings, parse_timezone, profile_cache_dir, refresh_now,
+    render_date_picker, render_footer, render_header, render_input_with_label, render_section,
+    render_stepper, render_wallpaper_image, resolve_deadline, resolve_deadline_date,
+    sanitize_inputs, sanitize_profile_name, sanity_warnings, save_archive, save_inputs,
+    set_active_profile, set_paused, simulate_wallpaper_mode, spanned_bounds, speak,
+    stop_and_restore_original_wallpaper, subscribe, synced_deadlines, trace, upcoming_events,
+    watch_config, AgendaConf, AnnouncementsConf, ArchivedDeadline, BackdropConf, Background,
+    BroadcastConf, CalendarEvent, CalendarSyncConf, Corner, CountdownStyle, Deadline,
+    DeadlineHookConf, DeadlineTag, DeadlinerConf, DeadlinerError, DeadlinerEvent, FinalHoursConf,
+    FocusConf, Font, GradientDirection, HealthStatus, ImageFilters, InstanceLock, Launch, Layout,
+    Locale, MonitorConf, NotificationsConf, OnlineSource, OutputTargetConf, OverdueBehavior,
+    Pattern, PerformanceBudget, Periods, PngCompression, QuickPick, Recurrence, RefreshHealth,
+    RotationMode, RoundingMode, ScreenDimensions, ScriptVariableConf, Season, SeasonalConf,
+    ShareCardSize, SoundAlertsConf, StaleWarningConf, StressFreeConf, TextPosition, TimeUnit,
+    UnitFormat, UrgencyColorsConf, WallpaperMode, WebhooksConf, WorkdaysConf, BACKGROUND, BLACK,
+    DEFAULT_PROFILE, EMOJI_FONT, GREY_WHITE, MARGIN, NOTO_EMOJI_REGULAR, PADDING, POPPINS_LIGHT,
+    POPPINS_MEDIUM, POPPINS_REGULAR, SECONDARY, SECONDARY_BRIGHT, SECONDARY_DARK,
+    SETTINGS_EXTENSION, SUPPORTED_IMAGE_EXTENSIONS, YELLOW,
 };
+use chrono::{Local, TimeZone, Utc};
 use eframe::{
     self,
     egui::{
@@ -14,45 +33,15 @@ use eframe::{
     epaint::{Color32, FontFamily, FontId, Rounding, Stroke, TextureHandle},
     epi::App,
 };
-use image::GenericImageView;
-use serde::{Deserialize, Serialize};
+use image::{DynamicImage, GenericImageView};
 use std::{
     collections::{BTreeMap, HashMap},
-    fmt::Debug,
     fs,
-    process::Command,
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+    time::{Duration, Instant},
 };
 use strum::IntoEnumIterator;
-use strum_macros::EnumIter;
-use wallpaper::Mode;
-
-#[derive(Serialize, Deserialize, Debug)]
-pub struct DeadlinerConf {
-    pub screen_dimensions: ScreenDimensions,
-
-    pub default_background: Background,
-
-    pub show_months: bool,
-    pub show_weeks: bool,
-    pub show_days: bool,
-    pub show_hours: bool,
-
-    pub font: Font,
-    pub font_size: u8,
-    pub font_color: [u8; 3],
-    pub custom_font_location: String,
-
-    pub date: String,
-    pub hours: String,
-    pub minutes: String,
-    pub period: Periods,
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
-pub struct ScreenDimensions {
-    pub width: u32,
-    pub height: u32,
-}
 
 pub struct Deadliner<'a> {
     // Preloaded textures on setup to use in the lifecycle methods.
@@ -60,93 +49,113 @@ pub struct Deadliner<'a> {
 
     error_msg: String,
     invalid_font: bool,
+    // Index of the deadline being edited in the GUI.
+    selected_deadline: usize,
+    invalid_branding: bool,
+    // Settings locked by the managed config layer.
+    managed_settings: Vec<String>,
+    refresh_health: RefreshHealth,
+    // Result of the "Refresh now" running in the background.
+    refreshing: Option<Receiver<Result<(), String>>>,
+    paused: bool,
+    // Result of the save running in the background, downloads can take a while.
+    saving: Option<Receiver<Result<(), DeadlinerError>>>,
+    // Refresh events of the save in progress, shown as its current step.
+    refresh_events: Receiver<DeadlinerEvent>,
+    saving_step: &'static str,
+    // Likely mistakes found on save, waiting for the user to confirm them.
+    pending_warnings: Vec<String>,
+    calendar_import: CalendarImport,
+    mode_preview: ModePreview,
+    wallpaper_preview: WallpaperPreview,
+    profiles: Profiles,
+    // Whether exported settings carry the custom font and background image.
+    bundle_files: bool,
+    // Sends when the saved inputs are changed outside of the GUI, started on setup.
+    config_changes: Option<Receiver<()>>,
+    // Inputs of the last save, the watcher sees the GUI's own writes too.
+    last_saved: Option<serde_json::Value>,
+    // First runs and updates get a note until the first save.
+    launch: Launch,
+    // Taken on setup to listen for later launches of the GUI.
+    instance_lock: Option<InstanceLock>,
+    focus_requests: Option<Receiver<()>>,
+    // When deadliner was last launched again while this window was open.
+    focus_requested_at: Option<Instant>,
+    tab: Tab,
+    // Loaded every time the archive tab is opened, the schedular adds to it.
+    archive: Vec<ArchivedDeadline>,
 
     conf: DeadlinerConf,
 }
 
-#[derive(Debug, PartialEq, Copy, Clone, EnumIter, Serialize, Deserialize)]
-pub enum Periods {
-    AM,
-    PM,
+#[derive(PartialEq, Clone, Copy)]
+enum Tab {
+    Deadlines,
+    Archive,
 }
 
-#[derive(Debug, PartialEq, Clone, EnumIter, Serialize, Deserialize)]
-pub enum Background {
-    Solid([u8; 3]),
-    FromDisk {
-        location: String,
-        mode: WallpaperMode,
-    },
-    FromURL {
-        url: String,
-        mode: WallpaperMode,
-    },
+/// Named setups (Ex: "Work" and "University"), each with its own inputs and wallpaper.
+struct Profiles {
+    active: String,
+    names: Vec<String>,
+    new_name: String,
 }
 
-impl Background {
-    pub fn mode(&self) -> WallpaperMode {
-        match self {
-            Background::FromURL { mode, .. } | Background::FromDisk { mode, .. } => *mode,
-            Background::Solid(_) => WallpaperMode::Center,
+impl Profiles {
+    fn load() -> Profiles {
+        Profiles {
+            active: active_profile(),
+            names: list_profiles(),
+            new_name: String::new(),
         }
     }
 }
 
-impl std::fmt::Display for Background {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Self::Solid(_) => "Solid",
-                Self::FromDisk { .. } => "From Disk",
-                Self::FromURL { .. } => "From URL",
-            }
-        )
-    }
-}
-
-#[derive(PartialEq, Debug, Clone, Copy, EnumIter, Serialize, Deserialize)]
-pub enum Font {
-    PoppinsBlack,
-    PoppinsMedium,
-    PoppinsRegular,
-    PoppinsLight,
-    ChooseFromDisk,
-}
-
-#[derive(Debug, PartialEq, Clone, Copy, EnumIter, Serialize, Deserialize)]
-pub enum WallpaperMode {
-    Center,
-    Crop,
-    Fit,
-    Span,
+/// The background as the OS will show it with the picked wallpaper mode,
+/// it's only rendered again when the image, the mode or the screen change.
+#[derive(Default)]
+struct ModePreview {
+    key: Option<(String, WallpaperMode, ScreenDimensions)>,
+    texture: Option<TextureHandle>,
 }
 
-impl Default for WallpaperMode {
-    fn default() -> Self {
-        WallpaperMode::Center
-    }
+/// A scaled-down render of the final wallpaper, it's rendered again in the background
+/// shortly after the inputs stop changing.
+#[derive(Default)]
+struct WallpaperPreview {
+    // Inputs of the shown (or rendering) preview.
+    key: Option<String>,
+    changed_at: Option<Instant>,
+    rendering: Option<Receiver<Result<DynamicImage, String>>>,
+    texture: Option<TextureHandle>,
+    error: Option<String>,
 }
 
-impl From<WallpaperMode> for Mode {
-    fn from(mode: WallpaperMode) -> Self {
-        match mode {
-            WallpaperMode::Center => Mode::Center,
-            WallpaperMode::Crop => Mode::Crop,
-            WallpaperMode::Fit => Mode::Fit,
-            WallpaperMode::Span => Mode::Span,
-        }
-    }
+/// Upcoming events of a calendar, picked ones are added as deadlines.
+#[derive(Default)]
+struct CalendarImport {
+    source: String,
+    events: Vec<(CalendarEvent, bool)>,
+    error: Option<String>,
 }
 
 impl<'a> App for Deadliner<'a> {
     fn setup(
         &mut self,
         ctx: &Context,
-        _frame: &eframe::epi::Frame,
+        frame: &eframe::epi::Frame,
         _storage: Option<&dyn eframe::epi::Storage>,
     ) {
+        let repaint_frame = frame.clone();
+        self.focus_requests = self
+            .instance_lock
+            .take()
+            .map(|lock| lock.on_focus_request(move || repaint_frame.request_repaint()));
+
+        let frame = frame.clone();
+        self.config_changes = Some(watch_config(move || frame.request_repaint()));
+
         self.load_logo_texture(ctx);
         self.load_footer_github_texture(ctx);
         self.set_custom_fonts(ctx);
@@ -214,6 +223,24 @@ impl<'a> App for Deadliner<'a> {
     }
 
     fn update(&mut self, ctx: &egui::Context, _frame: &eframe::epi::Frame) {
+        let config_changed = self
+            .config_changes
+            .as_ref()
+            .is_some_and(|changes| changes.try_iter().count() > 0);
+
+        if config_changed {
+            self.reload_changed_config();
+        }
+
+        let focus_requested = self
+            .focus_requests
+            .as_ref()
+            .is_some_and(|requests| requests.try_iter().count() > 0);
+
+        if focus_requested {
+            self.focus_requested_at = Some(Instant::now());
+        }
+
         let logo = self
             .textures
             .get("logo")
@@ -235,10 +262,66 @@ impl<'a> App for Deadliner<'a> {
 
         central_panel.show(ctx, |ui| {
             render_header(ui, logo);
+            if profiles_edit(ui, &mut self.profiles, &mut self.conf, &mut self.error_msg) {
+                self.selected_deadline = 0;
+            }
             draw_line(ui, 2.);
 
+            if !self.managed_settings.is_empty() {
+                ui.add_space(PADDING);
+                ui.label(
+                    RichText::new(format!(
+                        "🔒 Managed by your administrator: {}",
+                        self.managed_settings.join(", ")
+                    ))
+                    .color(Color32::from_white_alpha(120)),
+                );
+            }
+
+            launch_note_edit(ui, &self.launch);
+            already_open_edit(ui, &mut self.focus_requested_at);
+            safe_mode_edit(ui);
+            refresh_health_edit(
+                ui,
+                &mut self.refresh_health,
+                &mut self.refreshing,
+                &mut self.paused,
+                &mut self.error_msg,
+            );
+
+            tabs_edit(ui, &mut self.tab, &mut self.archive);
+
+            if self.tab == Tab::Archive {
+                archive_edit(ui, &mut self.archive, &self.conf.tags, &mut self.error_msg);
+                return;
+            }
+
             render_section(ui, "Styling", |ui| {
-                background_edit(ui, &mut self.conf.default_background);
+                background_edit(
+                    ui,
+                    &mut self.conf.default_background,
+                    &mut self.conf.background_filters,
+                    &mut self.conf.url_cache_ttl_hours,
+                );
+
+                ui.add_space(PADDING);
+
+                ui.checkbox(
+                    &mut self.conf.render_mode_in_app,
+                    "Apply the wallpaper mode while rendering",
+                )
+                .on_hover_text(
+                    "Renders an image of the exact screen size so the text lands \
+                     in the same place on every OS",
+                );
+
+                mode_preview_edit(
+                    ui,
+                    &mut self.mode_preview,
+                    &self.conf.default_background,
+                    self.conf.screen_dimensions,
+                    &self.conf.monitors,
+                );
 
                 ui.add_space(PADDING);
 
@@ -251,6 +334,192 @@ impl<'a> App for Deadliner<'a> {
                     ui.checkbox(&mut self.conf.show_weeks, "Weeks");
                     ui.checkbox(&mut self.conf.show_months, "Months");
                 });
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.conf.show_minutes, "Minutes");
+                    ui.checkbox(&mut self.conf.show_seconds, "Seconds");
+
+                    ui.label("Precision:");
+
+                    ComboBox::from_id_source("smallest_unit")
+                        .selected_text(format!("{:?}", self.conf.smallest_unit))
+                        .show_ui(ui, |ui| {
+                            for option in [
+                                TimeUnit::Day,
+                                TimeUnit::Hour,
+                                TimeUnit::Minute,
+                                TimeUnit::Second,
+                            ] {
+                                ui.selectable_value(
+                                    &mut self.conf.smallest_unit,
+                                    option,
+                                    format!("{:?}", option),
+                                );
+                            }
+                        })
+                        .response
+                        .on_hover_text("The smallest unit shown, Ex: Second for exams");
+                });
+
+                ui.add_space(PADDING);
+
+                ui.horizontal(|ui| {
+                    ui.label("Rounding:");
+
+                    ComboBox::from_id_source("rounding")
+                        .selected_text(format!("{:?}", self.conf.rounding))
+                        .show_ui(ui, |ui| {
+                            for option in RoundingMode::iter().collect::<Vec<_>>() {
+                                ui.selectable_value(
+                                    &mut self.conf.rounding,
+                                    option,
+                                    format!("{:?}", option),
+                                );
+                            }
+                        })
+                        .response
+                        .on_hover_text("How the time left is rounded to the smallest shown unit");
+                });
+
+                ui.add_space(PADDING);
+
+                ui.horizontal(|ui| {
+                    ui.label("Phrasing:");
+
+                    ComboBox::from_id_source("countdown_style")
+                        .selected_text(format!("{:?}", self.conf.countdown_style))
+                        .show_ui(ui, |ui| {
+                            for option in CountdownStyle::iter().collect::<Vec<_>>() {
+                                ui.selectable_value(
+                                    &mut self.conf.countdown_style,
+                                    option,
+                                    format!("{:?}", option),
+                                );
+                            }
+                        })
+                        .response
+                        .on_hover_text("Fuzzy shows approximate phrases like \"About 3 Weeks\"");
+                });
+
+                ui.add_space(PADDING);
+
+                // Fuzzy phrases are always written out in full
+                if self.conf.countdown_style == CountdownStyle::Exact {
+                    unit_format_edit(ui, &mut self.conf.unit_format);
+
+                    ui.add_space(PADDING);
+                }
+
+                stress_free_edit(ui, &mut self.conf.stress_free);
+
+                ui.add_space(PADDING);
+
+                workdays_edit(ui, &mut self.conf.workdays);
+
+                ui.add_space(PADDING);
+
+                focus_edit(ui, &mut self.conf.focus);
+
+                ui.add_space(PADDING);
+
+                ui.horizontal(|ui| {
+                    ui.label("Layout:");
+
+                    ComboBox::from_id_source("layout")
+                        .selected_text(format!("{:?}", self.conf.layout))
+                        .show_ui(ui, |ui| {
+                            for option in Layout::iter().collect::<Vec<_>>() {
+                                ui.selectable_value(
+                                    &mut self.conf.layout,
+                                    option,
+                                    format!("{:?}", option),
+                                );
+                            }
+                        })
+                        .response
+                        .on_hover_text("ProgressRing draws the countdown inside a draining ring");
+                });
+
+                ui.add_space(PADDING);
+
+                ui.horizontal(|ui| {
+                    ui.label("Compression:");
+
+                    ComboBox::from_id_source("png_compression")
+                        .selected_text(format!("{:?}", self.conf.png_compression))
+                        .show_ui(ui, |ui| {
+                            for option in PngCompression::iter().collect::<Vec<_>>() {
+                                ui.selectable_value(
+                                    &mut self.conf.png_compression,
+                                    option,
+                                    format!("{:?}", option),
+                                );
+                            }
+                        })
+                        .response
+                        .on_hover_text(
+                            "Fast refreshes big wallpapers quicker, Smallest saves disk space",
+                        );
+                });
+
+                ui.add_space(PADDING);
+
+                ui.horizontal(|ui| {
+                    ui.label("Performance:");
+
+                    ComboBox::from_id_source("performance")
+                        .selected_text(format!("{:?}", self.conf.performance))
+                        .show_ui(ui, |ui| {
+                            for option in PerformanceBudget::iter().collect::<Vec<_>>() {
+                                ui.selectable_value(
+                                    &mut self.conf.performance,
+                                    option,
+                                    format!("{:?}", option),
+                                );
+                            }
+                        })
+                        .response
+                        .on_hover_text(
+                            "Low skips the blur and refreshes at most every minute, \
+                             High renders smoother text",
+                        );
+                });
+
+                ui.add_space(PADDING);
+
+                ui.horizontal(|ui| {
+                    ui.label("Refresh timeout:");
+                    ui.add(
+                        egui::DragValue::new(&mut self.conf.refresh_timeout_secs)
+                            .clamp_range(5..=600)
+                            .suffix("s"),
+                    )
+                    .on_hover_text(
+                        "A stuck refresh (Ex: a hanging download) is aborted and retried",
+                    );
+
+                    ui.label("Download timeout:");
+                    ui.add(
+                        egui::DragValue::new(&mut self.conf.download_timeout_secs)
+                            .clamp_range(1..=120)
+                            .suffix("s"),
+                    )
+                    .on_hover_text("Failed downloads are retried twice before giving up");
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("Memory cap:");
+                    ui.add(
+                        egui::DragValue::new(&mut self.conf.memory_cap_mb)
+                            .clamp_range(0..=16384)
+                            .suffix(" MB"),
+                    )
+                    .on_hover_text(
+                        "Bigger backgrounds are decoded at a lower resolution (JPEG) or refused, \
+                         0 doesn't cap them",
+                    );
+                });
+
+                stale_warning_edit(ui, &mut self.conf.stale_warning);
 
                 ui.add_space(PADDING);
 
@@ -312,262 +581,2456 @@ impl<'a> App for Deadliner<'a> {
 
                 ui.add_space(PADDING);
 
-                ui.horizontal(|ui| {
-                    ui.label("Font Color:");
-                    ui.color_edit_button_srgb(&mut self.conf.font_color);
-                });
-            });
+                // The urgency colors replace the countdown's font color
+                if !self.conf.urgency_colors.enabled {
+                    ui.horizontal(|ui| {
+                        ui.label("Font Color:");
+                        ui.color_edit_button_srgb(&mut self.conf.font_color);
+                    });
 
-            render_section(ui, "Pick your Deadline", |ui| {
-                let date_error_popup_id = ui.make_persistent_id("invalid-date-error");
+                    ui.add_space(PADDING);
+                }
 
-                render_input_with_label(ui, "Date:", &mut self.conf.date, "2022-08-26");
+                urgency_colors_edit(ui, &mut self.conf.urgency_colors);
 
                 ui.add_space(PADDING);
 
-                ui.horizontal(|ui| {
-                    ui.label("Time:");
-
-                    render_input(ui, &mut self.conf.hours, "7", 18.);
-                    ui.label(":");
-                    render_input(ui, &mut self.conf.minutes, "28", 18.);
+                final_hours_edit(ui, &mut self.conf.final_hours);
 
-                    // Check if inputs are numeric
-                    if !is_string_numeric(&self.conf.hours) {
-                        self.conf.hours = String::new();
-                    }
-                    if !is_string_numeric(&self.conf.minutes) {
-                        self.conf.minutes = String::new();
-                    }
+                ui.add_space(PADDING);
 
-                    ComboBox::from_id_source("time_period")
-                        .width(70.)
-                        .selected_text(format!("{:?}", self.conf.period))
-                        .show_ui(ui, |ui| {
-                            for option in Periods::iter().collect::<Vec<_>>() {
-                                ui.selectable_value(
-                                    &mut self.conf.period,
-                                    option,
-                                    format!("{:?}", option),
-                                );
-                            }
-                        });
+                ui.horizontal(|ui| {
+                    ui.label("Title Size:");
+                    ui.add(egui::Slider::new(&mut self.conf.title_font_size, 5..=255));
                 });
 
-                ui.add_space(20.);
+                ui.add_space(PADDING);
 
                 ui.horizontal(|ui| {
-                    let start_button = button("Save!", BLACK, YELLOW, 600, 32.);
+                    ui.label("Title Color:");
+                    ui.color_edit_button_srgb(&mut self.conf.title_font_color);
+                });
 
-                    let start_button = ui.add(start_button);
+                ui.add_space(PADDING);
 
-                    // Setup error popups
-                    egui::popup::popup_below_widget(ui, date_error_popup_id, &start_button, |ui| {
-                        ui.set_min_width(200.0); // if you want to control the size
-                        ui.label(&self.error_msg);
-                    });
+                text_position_edit(ui, &mut self.conf.text_position);
 
-                    let start_clicked = start_button.clicked();
+                ui.add_space(PADDING);
 
-                    if start_clicked {
-                        // Pass true to exit only if the user hit save
-                        match save_inputs(&self.conf) {
-                            Err(msg) => {
-                                self.error_msg = msg;
-                                ui.memory().toggle_popup(date_error_popup_id);
+                ui.checkbox(&mut self.conf.high_contrast, "High contrast")
+                    .on_hover_text(
+                        "Recolors the text or adds a backdrop behind it when it's hard to read",
+                    );
+
+                ui.add_space(PADDING);
+
+                backdrop_edit(ui, &mut self.conf.backdrop);
+
+                ui.add_space(PADDING);
+
+                seasonal_edit(ui, &mut self.conf.seasonal);
+
+                ui.add_space(PADDING);
+
+                ui.horizontal(|ui| {
+                    ui.label("Branding:");
+
+                    if ui.button("Open preset…").clicked() {
+                        if let Some(path) = rfd::FileDialog::new().pick_file() {
+                            let location = path.display().to_string();
+                            let file_name = get_file_name_from_path(&location);
+                            let file_ext =
+                                file_name.split(".").collect::<Vec<&str>>().pop().unwrap();
+
+                            if file_ext == "json" {
+                                self.invalid_branding = false;
+                                self.conf.branding_location = location;
+                            } else {
+                                self.invalid_branding = true;
                             }
-                            _ => (),
                         }
-                    };
+                    }
+
+                    if self.invalid_branding {
+                        ui.colored_label(Color32::from_rgb(255, 48, 48), "Not a preset");
+                    } else if !self.conf.branding_location.is_empty() {
+                        ui.colored_label(
+                            Color32::from_rgba_unmultiplied(254, 216, 67, 200),
+                            get_file_name_from_path(&self.conf.branding_location),
+                        );
+
+                        if ui.small_button("✖").clicked() {
+                            self.conf.branding_location = String::new();
+                        }
+                    }
                 });
             });
 
-            let github = self
-                .textures
-                .get("github")
-                .expect("Github texture wasn't preloaded");
+            render_section(ui, "Preview", |ui| {
+                wallpaper_preview_edit(ui, &mut self.wallpaper_preview, &self.conf);
+            });
 
-            render_footer(&ctx, ui, github);
-        });
-    }
+            render_section(ui, "Text Variables", |ui| {
+                text_variables_edit(ui, &mut self.conf.text_variables);
+            });
 
-    fn name(&self) -> &str {
-        "Deadliner"
-    }
-}
+            if self.conf.monitors.len() > 1 {
+                render_section(ui, "Monitors", |ui| {
+                    monitors_edit(ui, &mut self.conf.monitors, self.conf.deadlines.len());
+                });
+            }
 
-fn background_edit(ui: &mut egui::Ui, bg: &mut Background) {
-    ui.horizontal(|ui| {
-        ui.label("Background:");
+            render_section(ui, "Calendar Sync", |ui| {
+                calendar_sync_edit(ui, &mut self.conf.calendar_sync);
 
-        ComboBox::from_id_source("background_options")
-            .selected_text(bg.to_string())
-            .show_ui(ui, |ui| {
-                for option in Background::iter().collect::<Vec<_>>() {
-                    let label = option.to_string();
-                    ui.selectable_value(bg, option, label);
+                if self.conf.calendar_sync.enabled {
+                    ui.add_space(PADDING);
+                    agenda_edit(ui, &mut self.conf.agenda);
                 }
             });
-    });
 
-    ui.add_space(PADDING);
+            render_section(ui, "Outputs", |ui| {
+                output_targets_edit(ui, &mut self.conf.output_targets);
 
-    match bg {
-        Background::Solid(color) => {
-            ui.horizontal(|ui| {
-                ui.label("Pick a Color:");
-                ui.color_edit_button_srgb(color);
+                ui.add_space(PADDING);
+                ui.checkbox(&mut self.conf.launch_on_login, "Start Deadliner on login")
+                    .on_hover_text("Keeps the wallpaper updated from the tray after restarts");
             });
-        }
-        Background::FromURL { url, .. } => {
-            ui.horizontal(|ui| {
-                ui.label("Image URL:");
-                ui.add(
-                    egui::TextEdit::singleline(url)
-                        .desired_width(180.)
-                        .hint_text(
-                            RichText::new("https://source.unsplash.com/random")
-                                .color(Color32::from_white_alpha(45)),
-                        ),
-                );
+
+            render_section(ui, "Broadcast", |ui| {
+                broadcast_edit(ui, &mut self.conf.broadcast);
             });
-        }
-        Background::FromDisk { location, .. } => {
-            ui.horizontal(|ui| {
-                #[derive(Clone)]
-                struct IsValid(bool);
 
-                if ui.button("Open file…").clicked() {
-                    if let Some(path) = rfd::FileDialog::new().pick_file() {
-                        let new_location = path.display().to_string();
+            render_section(ui, "Alerts", |ui| {
+                announcements_edit(ui, &mut self.conf.announcements);
 
-                        let file_name = get_file_name_from_path(&new_location);
-                        let file_ext = file_name.split(".").collect::<Vec<&str>>().pop().unwrap();
-                        let supported_file_ext = ["png", "gif", "jpg", "jpeg"];
+                ui.add_space(PADDING);
 
-                        let mut data = ui.data();
-                        let is_valid = data.get_temp_mut_or(ui.id(), IsValid(true));
-                        
-                        if supported_file_ext.contains(&file_ext) {
-                            *location = new_location;
+                sound_alerts_edit(ui, &mut self.conf.sound_alerts);
 
-                            *is_valid = IsValid(true);
-                        } else {
-                            *is_valid = IsValid(false);
+                ui.add_space(PADDING);
+
+                notifications_edit(ui, &mut self.conf.notifications);
+
+                ui.add_space(PADDING);
+
+                deadline_hook_edit(ui, &mut self.conf.deadline_hook);
+
+                ui.add_space(PADDING);
+
+                webhooks_edit(ui, &mut self.conf.webhooks);
+            });
+
+            render_section(ui, "Pick your Deadline", |ui| {
+                let date_error_popup_id = ui.make_persistent_id("invalid-date-error");
+
+                deadlines_edit(
+                    ui,
+                    &mut self.conf.deadlines,
+                    &self.conf.tags,
+                    &mut self.selected_deadline,
+                );
+
+                calendar_import_edit(
+                    ui,
+                    &mut self.calendar_import,
+                    &mut self.conf,
+                    &mut self.selected_deadline,
+                );
+
+                ui.add_space(PADDING);
+
+                let mut hours_format_changed = false;
+                let deadline = &mut self.conf.deadlines[self.selected_deadline];
+
+                render_input_with_label(ui, "Title:", &mut deadline.title, "Thesis submission");
+
+                tag_edit(ui, &mut deadline.tag, &mut self.conf.tags);
+
+                ui.add_space(PADDING);
+
+                render_date_picker(ui, "deadline", "Date:", &mut deadline.date, "2022-08-26");
+
+                ui.horizontal(|ui| {
+                    for pick in QuickPick::iter() {
+                        if ui.small_button(pick.label()).clicked() {
+                            let now = Local::now().naive_local();
+                            deadline.set_date_time(pick.resolve(now), self.conf.use_24_hour);
+                        }
+                    }
+                });
+
+                // Show how dates like "next friday 5pm" were understood
+                if let Ok(date) = resolve_deadline_date(deadline, self.conf.use_24_hour) {
+                    let format = if self.conf.use_24_hour {
+                        "%a, %Y-%m-%d %H:%M"
+                    } else {
+                        "%a, %Y-%m-%d %I:%M %p"
+                    };
+
+                    ui.label(
+                        RichText::new(date.format(format).to_string())
+                            .color(Color32::from_white_alpha(120)),
+                    );
+                }
+
+                ui.add_space(PADDING);
+
+                ui.horizontal(|ui| {
+                    ui.label("Time:");
+
+                    let hours_range = if self.conf.use_24_hour {
+                        0..=23
+                    } else {
+                        1..=12
+                    };
+
+                    render_stepper(ui, &mut deadline.hours, hours_range);
+                    ui.label(":");
+                    render_stepper(ui, &mut deadline.minutes, 0..=59);
+
+                    if !self.conf.use_24_hour {
+                        ComboBox::from_id_source("time_period")
+                            .width(70.)
+                            .selected_text(format!("{:?}", deadline.period))
+                            .show_ui(ui, |ui| {
+                                for option in Periods::iter().collect::<Vec<_>>() {
+                                    ui.selectable_value(
+                                        &mut deadline.period,
+                                        option,
+                                        format!("{:?}", option),
+                                    );
+                                }
+                            });
+                    }
+
+                    hours_format_changed = ui.checkbox(&mut self.conf.use_24_hour, "24h").changed();
+                });
+
+                ui.add_space(PADDING);
+
+                ui.checkbox(&mut deadline.counts_up, "Count up since this date")
+                    .on_hover_text("Shows the time since a past date, Ex: \"It's been 127 Days\"");
+
+                ui.add_space(PADDING);
+
+                ui.horizontal(|ui| {
+                    ui.label("Repeats:");
+
+                    ComboBox::from_id_source("deadline_recurrence")
+                        .selected_text(deadline.recurrence.to_string())
+                        .show_ui(ui, |ui| {
+                            for option in Recurrence::iter().collect::<Vec<_>>() {
+                                ui.selectable_value(
+                                    &mut deadline.recurrence,
+                                    option,
+                                    option.to_string(),
+                                );
+                            }
+                        });
+                });
+
+                ui.add_space(PADDING);
+
+                ui.horizontal(|ui| {
+                    ui.label("Language:");
+
+                    ComboBox::from_id_source("deadline_locale")
+                        .selected_text(format!("{:?}", deadline.locale))
+                        .show_ui(ui, |ui| {
+                            for option in Locale::iter().collect::<Vec<_>>() {
+                                ui.selectable_value(
+                                    &mut deadline.locale,
+                                    option,
+                                    format!("{:?}", option),
+                                );
+                            }
+                        });
+                });
+
+                ui.add_space(PADDING);
+
+                ui.horizontal(|ui| {
+                    ui.label("Template:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut deadline.template)
+                            .desired_width(180.)
+                            .hint_text(
+                                RichText::new(if deadline.counts_up {
+                                    deadline.locale.count_up_template()
+                                } else {
+                                    deadline.locale.default_template()
+                                })
+                                .color(Color32::from_white_alpha(45)),
+                            ),
+                    );
+                });
+
+                ui.add_space(PADDING);
+
+                render_input_with_label(ui, "Time Zone:", &mut deadline.timezone, "Local");
+
+                if let Some(local_time) = resolved_local_time(deadline, self.conf.use_24_hour) {
+                    ui.label(
+                        RichText::new(format!("{} your time", local_time))
+                            .color(Color32::from_white_alpha(120)),
+                    );
+                }
+
+                ui.add_space(PADDING);
+
+                world_clocks_edit(ui, &mut self.conf.world_clocks);
+
+                if hours_format_changed {
+                    for deadline in &mut self.conf.deadlines {
+                        deadline.convert_hours(self.conf.use_24_hour);
+                    }
+                }
+
+                ui.add_space(PADDING);
+
+                overdue_edit(ui, &mut self.conf.overdue);
+
+                if self.conf.deadlines.len() > 1 {
+                    ui.add_space(PADDING);
+
+                    rotation_edit(ui, &mut self.conf.rotation);
+                }
+
+                ui.add_space(20.);
+
+                ui.horizontal(|ui| {
+                    let start_button = button("Save!", BLACK, YELLOW, 600, 32.);
+
+                    let start_button = ui.add(start_button);
+
+                    // Setup error popups
+                    egui::popup::popup_below_widget(ui, date_error_popup_id, &start_button, |ui| {
+                        ui.set_min_width(200.0); // if you want to control the size
+                        ui.label(&self.error_msg);
+                    });
+
+                    let start_clicked = start_button.clicked();
+
+                    if start_clicked && self.saving.is_none() {
+                        self.pending_warnings = sanity_warnings(&self.conf);
+
+                        if self.pending_warnings.is_empty() {
+                            self.launch = Launch::Again;
+                            self.saving_step = "Saving…";
+                            self.saving = Some(start_saving(&self.conf, &mut self.last_saved));
+                        }
+                    };
+
+                    for event in self.refresh_events.try_iter() {
+                        self.saving_step = match event {
+                            DeadlinerEvent::RefreshStarted => "Rendering…",
+                            DeadlinerEvent::RenderCompleted => "Setting the wallpaper…",
+                            _ => self.saving_step,
+                        };
+                    }
+
+                    if let Some(saving) = &self.saving {
+                        match saving.try_recv() {
+                            Err(TryRecvError::Empty) => {
+                                let status = download_progress()
+                                    .map(|progress| progress.to_string())
+                                    .unwrap_or_else(|| self.saving_step.to_string());
+
+                                ui.label(
+                                    RichText::new(status).color(Color32::from_white_alpha(120)),
+                                );
+
+                                // Keep the progress moving without any input
+                                ctx.request_repaint();
+                            }
+                            Ok(Err(e)) => {
+                                self.error_msg = e.user_message();
+                                ui.memory().toggle_popup(date_error_popup_id);
+                                self.saving = None;
+                            }
+                            _ => self.saving = None,
+                        }
+                    }
+                });
+
+                if !self.pending_warnings.is_empty() {
+                    ui.add_space(PADDING);
+
+                    for warning in &self.pending_warnings {
+                        ui.colored_label(Color32::from_rgb(254, 216, 67), format!("⚠ {}", warning));
+                    }
+
+                    ui.horizontal(|ui| {
+                        if ui.button("Save anyway").clicked() {
+                            self.pending_warnings.clear();
+                            self.saving_step = "Saving…";
+                            self.saving = Some(start_saving(&self.conf, &mut self.last_saved));
+                        }
+                        if ui.button("Cancel").clicked() {
+                            self.pending_warnings.clear();
+                        }
+                    });
+                }
+
+                ui.add_space(PADDING);
+
+                ui.horizontal(|ui| {
+                    let restore_error_popup_id = ui.make_persistent_id("restore-error");
+
+                    let restore_button = ui.add(button(
+                        "Restore original wallpaper",
+                        GREY_WHITE,
+                        SECONDARY,
+                        400,
+                        14.,
+                    ));
+
+                    egui::popup::popup_below_widget(
+                        ui,
+                        restore_error_popup_id,
+                        &restore_button,
+                        |ui| {
+                            ui.set_min_width(200.0);
+                            ui.label(&self.error_msg);
+                        },
+                    );
+
+                    if restore_button.clicked() {
+                        if let Err(msg) = stop_and_restore_original_wallpaper() {
+                            self.error_msg = msg;
+                            ui.memory().toggle_popup(restore_error_popup_id);
+                        }
+                    }
+
+                    let share_error_popup_id = ui.make_persistent_id("share-error");
+
+                    let share_button =
+                        ui.add(button("Share card…", GREY_WHITE, SECONDARY, 400, 14.));
+
+                    egui::popup::popup_below_widget(
+                        ui,
+                        share_error_popup_id,
+                        &share_button,
+                        |ui| {
+                            ui.set_min_width(200.0);
+                            ui.label(&self.error_msg);
+                        },
+                    );
+
+                    if share_button.clicked() {
+                        if let Some(path) = rfd::FileDialog::new()
+                            .add_filter("PNG", &["png"])
+                            .set_file_name("deadline.png")
+                            .save_file()
+                        {
+                            // Cards are rendered from the last saved configuration
+                            let exported = load_sanitized_conf().and_then(|conf| {
+                                export_share_card(&conf, ShareCardSize::Landscape, &path)
+                            });
+
+                            if let Err(msg) = exported {
+                                self.error_msg = msg;
+                                ui.memory().toggle_popup(share_error_popup_id);
+                            }
+                        }
+                    }
+
+                    let calendar_button =
+                        ui.add(button("Add to calendar…", GREY_WHITE, SECONDARY, 400, 14.));
+
+                    if calendar_button.clicked() {
+                        if let Some(path) = rfd::FileDialog::new()
+                            .add_filter("iCalendar", &["ics"])
+                            .set_file_name("deadlines.ics")
+                            .save_file()
+                        {
+                            // Exported from the last saved configuration like the share card
+                            let exported =
+                                load_sanitized_conf().and_then(|conf| export_ics(&conf, &path));
+
+                            if let Err(msg) = exported {
+                                self.error_msg = msg;
+                                ui.memory().toggle_popup(share_error_popup_id);
+                            }
+                        }
+                    }
+
+                    let export_button = ui.add(button("Export…", GREY_WHITE, SECONDARY, 400, 14.));
+
+                    if export_button
+                        .on_hover_text("Back up the deadlines and their history as CSV or JSON")
+                        .clicked()
+                    {
+                        if let Some(path) = rfd::FileDialog::new()
+                            .add_filter("CSV", &["csv"])
+                            .add_filter("JSON", &["json"])
+                            .set_file_name("deadlines.csv")
+                            .save_file()
+                        {
+                            let exported = load_sanitized_conf()
+                                .and_then(|conf| export_deadlines(&conf, &path));
+
+                            if let Err(msg) = exported {
+                                self.error_msg = msg;
+                                ui.memory().toggle_popup(share_error_popup_id);
+                            }
+                        }
+                    }
+                });
+
+                ui.add_space(PADDING);
+
+                ui.horizontal(|ui| {
+                    let settings_error_popup_id = ui.make_persistent_id("settings-error");
+
+                    let export_settings_button =
+                        ui.add(button("Export settings…", GREY_WHITE, SECONDARY, 400, 14.));
+
+                    egui::popup::popup_below_widget(
+                        ui,
+                        settings_error_popup_id,
+                        &export_settings_button,
+                        |ui| {
+                            ui.set_min_width(200.0);
+                            ui.label(&self.error_msg);
+                        },
+                    );
+
+                    if export_settings_button
+                        .on_hover_text(
+                            "Save the inputs in one file to share, commands, webhooks, \
+                            outputs and calendar logins stay on this computer",
+                        )
+                        .clicked()
+                    {
+                        if let Some(path) = rfd::FileDialog::new()
+                            .add_filter("Deadliner settings", &[SETTINGS_EXTENSION])
+                            .set_file_name(&format!("settings.{}", SETTINGS_EXTENSION))
+                            .save_file()
+                        {
+                            // The inputs as they're shown, even if they weren't saved yet
+                            if let Err(msg) = export_settings(&self.conf, &path, self.bundle_files)
+                            {
+                                self.error_msg = msg;
+                                ui.memory().toggle_popup(settings_error_popup_id);
+                            }
+                        }
+                    }
+
+                    let import_settings_button =
+                        ui.add(button("Import settings…", GREY_WHITE, SECONDARY, 400, 14.));
+
+                    if import_settings_button
+                        .on_hover_text("Replace the inputs with exported ones, save to apply them")
+                        .clicked()
+                    {
+                        if let Some(path) = rfd::FileDialog::new()
+                            .add_filter("Deadliner settings", &[SETTINGS_EXTENSION])
+                            .pick_file()
+                        {
+                            match import_settings(&path, &self.conf)
+                                .and_then(|conf| apply_managed_layer(&conf))
+                            {
+                                Ok(conf) => {
+                                    self.conf = conf;
+                                    self.selected_deadline = 0;
+                                }
+                                Err(msg) => {
+                                    self.error_msg = msg;
+                                    ui.memory().toggle_popup(settings_error_popup_id);
+                                }
+                            }
+                        }
+                    }
+
+                    ui.checkbox(&mut self.bundle_files, "Include the font and background")
+                        .on_hover_text(
+                            "Put the custom font and the background image in the export",
+                        );
+                });
+            });
+
+            let github = self
+                .textures
+                .get("github")
+                .expect("Github texture wasn't preloaded");
+
+            render_footer(ctx, ui, github);
+        });
+    }
+
+    fn name(&self) -> &str {
+        "Deadliner"
+    }
+}
+
+fn background_edit(
+    ui: &mut egui::Ui,
+    bg: &mut Background,
+    filters: &mut ImageFilters,
+    url_cache_ttl_hours: &mut u32,
+) {
+    ui.horizontal(|ui| {
+        ui.label("Background:");
+
+        ComboBox::from_id_source("background_options")
+            .selected_text(bg.to_string())
+            .show_ui(ui, |ui| {
+                for option in Background::iter().collect::<Vec<_>>() {
+                    let label = option.to_string();
+                    ui.selectable_value(bg, option, label);
+                }
+            });
+    });
+
+    ui.add_space(PADDING);
+
+    match bg {
+        Background::Solid { color, pattern } => {
+            ui.horizontal(|ui| {
+                ui.label("Pick a Color:");
+                ui.color_edit_button_srgb(color);
+            });
+            ui.horizontal(|ui| {
+                ui.label("Pattern:");
+
+                ComboBox::from_id_source("solid_pattern")
+                    .selected_text(format!("{:?}", pattern))
+                    .show_ui(ui, |ui| {
+                        for option in Pattern::iter() {
+                            ui.selectable_value(pattern, option, format!("{:?}", option));
+                        }
+                    });
+            });
+        }
+        Background::Gradient {
+            start,
+            end,
+            direction,
+        } => {
+            ui.horizontal(|ui| {
+                ui.label("From:");
+                ui.color_edit_button_srgb(start);
+                ui.label("To:");
+                ui.color_edit_button_srgb(end);
+            });
+            ui.horizontal(|ui| {
+                ui.label("Direction:");
+
+                ComboBox::from_id_source("gradient_direction")
+                    .selected_text(format!("{:?}", direction))
+                    .show_ui(ui, |ui| {
+                        for option in GradientDirection::iter().collect::<Vec<_>>() {
+                            ui.selectable_value(direction, option, format!("{:?}", option));
+                        }
+                    });
+            });
+        }
+        Background::FromURL { url, .. } => {
+            ui.horizontal(|ui| {
+                ui.label("Image URL:");
+                ui.add(
+                    egui::TextEdit::singleline(url)
+                        .desired_width(180.)
+                        .hint_text(
+                            RichText::new("https://source.unsplash.com/random")
+                                .color(Color32::from_white_alpha(45)),
+                        ),
+                );
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Check for changes every");
+                ui.add(
+                    egui::DragValue::new(url_cache_ttl_hours)
+                        .clamp_range(0..=720)
+                        .suffix("h"),
+                );
+
+                if ui
+                    .button("Force refresh")
+                    .on_hover_text("Downloads the image again on the next update")
+                    .clicked()
+                {
+                    forget_cached_download(url);
+                }
+            });
+        }
+        Background::FromDisk { location, .. } => {
+            ui.horizontal(|ui| {
+                #[derive(Clone)]
+                struct IsValid(bool);
+
+                if ui.button("Open file…").clicked() {
+                    if let Some(path) = rfd::FileDialog::new().pick_file() {
+                        let new_location = path.display().to_string();
+
+                        let file_name = get_file_name_from_path(&new_location);
+                        let file_ext = file_name.split(".").collect::<Vec<&str>>().pop().unwrap();
+
+                        let mut data = ui.data();
+                        let is_valid = data.get_temp_mut_or(ui.id(), IsValid(true));
+
+                        if SUPPORTED_IMAGE_EXTENSIONS.contains(&file_ext) {
+                            *location = new_location;
+
+                            *is_valid = IsValid(true);
+                        } else {
+                            *is_valid = IsValid(false);
+                        }
+                    }
+                }
+
+                let is_valid = ui.data().get_temp_mut_or(ui.id(), IsValid(true)).0;
+                if !is_valid {
+                    ui.colored_label(Color32::from_rgb(255, 48, 48), "Not an Image");
+                } else if !location.is_empty() {
+                    ui.colored_label(
+                        Color32::from_rgba_unmultiplied(254, 216, 67, 200),
+                        get_file_name_from_path(location),
+                    );
+                }
+            });
+        }
+        Background::FromFolder { path, shuffle, .. } => {
+            ui.horizontal(|ui| {
+                if ui.button("Open folder…").clicked() {
+                    if let Some(folder) = rfd::FileDialog::new().pick_folder() {
+                        *path = folder.display().to_string();
+                    }
+                }
+
+                if !path.is_empty() {
+                    match folder_images(path) {
+                        Ok(images) if !images.is_empty() => {
+                            ui.colored_label(
+                                Color32::from_rgba_unmultiplied(254, 216, 67, 200),
+                                format!(
+                                    "{} ({} images)",
+                                    get_file_name_from_path(path),
+                                    images.len()
+                                ),
+                            );
+                        }
+                        _ => {
+                            ui.colored_label(Color32::from_rgb(255, 48, 48), "No Images");
+                        }
+                    }
+                }
+            });
+
+            ui.checkbox(shuffle, "Shuffle")
+                .on_hover_text("Every image is used once before any is repeated");
+        }
+        Background::Online {
+            source, keyword, ..
+        } => {
+            ui.horizontal(|ui| {
+                ui.label("Source:");
+
+                ComboBox::from_id_source("online_source")
+                    .selected_text(format!("{:?}", source))
+                    .show_ui(ui, |ui| {
+                        for option in OnlineSource::iter().collect::<Vec<_>>() {
+                            ui.selectable_value(source, option, format!("{:?}", option));
+                        }
+                    })
+                    .response
+                    .on_hover_text("A new photo is downloaded once a day");
+            });
+
+            if *source == OnlineSource::Unsplash {
+                render_input_with_label(ui, "Keyword:", keyword, "mountains");
+            }
+        }
+    }
+
+    if let Background::FromDisk { mode, .. }
+    | Background::FromURL { mode, .. }
+    | Background::FromFolder { mode, .. }
+    | Background::Online { mode, .. } = bg
+    {
+        ui.add_space(PADDING);
+
+        ui.horizontal(|ui| {
+            ui.label("Wallpaper Mode:");
+
+            ComboBox::from_id_source("background_mode")
+                .selected_text(format!("{:?}", mode))
+                .show_ui(ui, |ui| {
+                    for option in WallpaperMode::iter().collect::<Vec<_>>() {
+                        ui.selectable_value(mode, option, format!("{:?}", option));
+                    }
+                });
+        });
+
+        ui.add_space(PADDING);
+
+        ui.horizontal(|ui| {
+            ui.label("Darken:");
+            ui.add(egui::Slider::new(&mut filters.darken, 0..=100).suffix("%"));
+        });
+        ui.horizontal(|ui| {
+            ui.label("Blur:");
+            ui.add(egui::Slider::new(&mut filters.blur, 0.0..=30.0).suffix("px"));
+        });
+        ui.checkbox(&mut filters.grayscale, "Grayscale");
+    }
+}
+
+const MODE_PREVIEW_WIDTH: u32 = 240;
+
+fn mode_preview_edit(
+    ui: &mut egui::Ui,
+    preview: &mut ModePreview,
+    bg: &Background,
+    screen: ScreenDimensions,
+    monitors: &[MonitorConf],
+) {
+    // Only local images can be previewed without downloading anything
+    let (location, mode) = match bg {
+        Background::FromDisk { location, mode } if !location.trim().is_empty() => {
+            (location.trim().to_string(), *mode)
+        }
+        Background::FromFolder { path, mode, .. } => match folder_images(path) {
+            Ok(images) if !images.is_empty() => (images[0].display().to_string(), *mode),
+            _ => return,
+        },
+        _ => return,
+    };
+
+    let desktop = if mode == WallpaperMode::Span && monitors.len() > 1 {
+        spanned_bounds(monitors).1
+    } else {
+        screen
+    };
+
+    let key = Some((location.clone(), mode, desktop));
+
+    if preview.key != key {
+        preview.key = key;
+        preview.texture = image::open(&location).ok().map(|image| {
+            let simulated = simulate_wallpaper_mode(&image, mode, desktop, MODE_PREVIEW_WIDTH);
+            let size = [simulated.width() as _, simulated.height() as _];
+            let image_buffer = simulated.to_rgba8();
+            let pixels = image_buffer.as_flat_samples();
+
+            ui.ctx().load_texture(
+                "mode_preview",
+                egui::ColorImage::from_rgba_unmultiplied(size, pixels.as_slice()),
+            )
+        });
+    }
+
+    if let Some(texture) = &preview.texture {
+        ui.add_space(PADDING);
+        ui.image(texture, texture.size_vec2())
+            .on_hover_text(format!("How {:?} mode will look on your screen", mode));
+    }
+}
+
+const WALLPAPER_PREVIEW_WIDTH: u32 = 480;
+// Waits for the typing to stop instead of rendering on every key press.
+const WALLPAPER_PREVIEW_DELAY: Duration = Duration::from_millis(400);
+
+fn wallpaper_preview_edit(ui: &mut egui::Ui, preview: &mut WallpaperPreview, conf: &DeadlinerConf) {
+    let key = serde_json::to_string(conf).ok();
+
+    if preview.key != key {
+        preview.key = key;
+        preview.changed_at = Some(Instant::now());
+    }
+
+    if let Some(changed_at) = preview.changed_at {
+        if changed_at.elapsed() >= WALLPAPER_PREVIEW_DELAY && preview.rendering.is_none() {
+            preview.changed_at = None;
+            preview.rendering = Some(start_preview_render(conf));
+        } else {
+            // Keep polling until the delay is over without any input
+            ui.ctx().request_repaint();
+        }
+    }
+
+    if let Some(rendering) = &preview.rendering {
+        match rendering.try_recv() {
+            Err(TryRecvError::Empty) => ui.ctx().request_repaint(),
+            Err(TryRecvError::Disconnected) => preview.rendering = None,
+            Ok(result) => {
+                preview.rendering = None;
+
+                match result {
+                    Ok(wallpaper) => {
+                        let size = [wallpaper.width() as _, wallpaper.height() as _];
+                        let image_buffer = wallpaper.to_rgba8();
+                        let pixels = image_buffer.as_flat_samples();
+
+                        preview.texture = Some(ui.ctx().load_texture(
+                            "wallpaper_preview",
+                            egui::ColorImage::from_rgba_unmultiplied(size, pixels.as_slice()),
+                        ));
+                        preview.error = None;
+                    }
+                    Err(e) => preview.error = Some(e),
+                }
+            }
+        }
+    }
+
+    if let Some(error) = &preview.error {
+        ui.label(RichText::new(error).color(Color32::from_white_alpha(120)));
+    }
+
+    if let Some(texture) = &preview.texture {
+        ui.add_space(PADDING);
+        ui.image(texture, texture.size_vec2())
+            .on_hover_text("How the wallpaper will look once saved");
+    }
+}
+
+/// Renders the wallpaper on another thread and scales it down to fit the window.
+fn start_preview_render(conf: &DeadlinerConf) -> Receiver<Result<DynamicImage, String>> {
+    let mut conf = conf.clone();
+
+    // Picking from a folder moves its rotation, the preview sticks to its first image
+    if let Background::FromFolder { path, mode, .. } = &conf.default_background {
+        if let Some(image) = folder_images(path)
+            .ok()
+            .and_then(|images| images.into_iter().next())
+        {
+            conf.default_background = Background::FromDisk {
+                location: image.display().to_string(),
+                mode: *mode,
+            };
+        }
+    }
+
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        let preview = sanitize_inputs(&conf).and_then(|sanitized| {
+            let (wallpaper, _) =
+                render_wallpaper_image(&sanitized, false).map_err(|e| e.user_message())?;
+            let height = wallpaper.height() * WALLPAPER_PREVIEW_WIDTH / wallpaper.width().max(1);
+
+            Ok(wallpaper.thumbnail(WALLPAPER_PREVIEW_WIDTH, height.max(1)))
+        });
+
+        sender.send(preview).ok();
+    });
+
+    receiver
+}
+
+/// Saves on another thread so the window stays responsive while images are downloaded.
+/// Saves in the background, `last_saved` keeps what was saved to tell the GUI's own writes apart.
+fn start_saving(
+    conf: &DeadlinerConf,
+    last_saved: &mut Option<serde_json::Value>,
+) -> Receiver<Result<(), DeadlinerError>> {
+    *last_saved = serde_json::to_value(conf).ok();
+
+    let conf = conf.clone();
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || sender.send(save_inputs(&conf)).ok());
+
+    receiver
+}
+
+/// Greets new users and tells updated ones their deadlines were kept.
+fn launch_note_edit(ui: &mut egui::Ui, launch: &Launch) {
+    let note = match launch {
+        Launch::FirstRun => String::from(
+            "👋 Welcome! Pick your deadline below and press Start to put it on your wallpaper.",
+        ),
+        Launch::Updated { from } => format!(
+            "Updated from {} to {}, your deadlines were kept.",
+            from,
+            env!("CARGO_PKG_VERSION")
+        ),
+        Launch::Again => return,
+    };
+
+    ui.add_space(PADDING);
+    ui.label(RichText::new(note).color(Color32::from_white_alpha(120)));
+}
+
+/// How long the note stays after deadliner was launched again.
+const ALREADY_OPEN_NOTE: Duration = Duration::from_secs(5);
+
+/// Points out this window when deadliner is launched again, eframe can't raise
+/// the window itself so the tray's "Open/Close Window" stays the way to bring it back.
+fn already_open_edit(ui: &mut egui::Ui, requested_at: &mut Option<Instant>) {
+    if !requested_at.is_some_and(|at| at.elapsed() < ALREADY_OPEN_NOTE) {
+        *requested_at = None;
+        return;
+    }
+
+    ui.add_space(PADDING);
+    ui.colored_label(
+        Color32::from_rgb(254, 216, 67),
+        "👋 Deadliner is already open, it's this window.",
+    );
+
+    // Hide the note once its time is up without any input
+    ui.ctx().request_repaint();
+}
+
+fn safe_mode_edit(ui: &mut egui::Ui) {
+    if !is_safe_mode() {
+        return;
+    }
+
+    ui.add_space(PADDING);
+    ui.colored_label(
+        Color32::from_rgb(254, 216, 67),
+        "⚠ Safe mode: the last wallpaper update crashed, so the background and styling \
+         are the defaults. Fix your inputs and save them to leave it.",
+    );
+}
+
+/// A dot showing whether the background refreshes have been working lately,
+/// with the last update, the last error and buttons to update the wallpaper right away
+/// or to pause the updates (Ex: while screen-sharing).
+fn refresh_health_edit(
+    ui: &mut egui::Ui,
+    health: &mut RefreshHealth,
+    refreshing: &mut Option<Receiver<Result<(), String>>>,
+    paused: &mut bool,
+    error_msg: &mut String,
+) {
+    let status = health.status();
+    let [r, g, b] = status.color();
+
+    let last_update = match health.last_success() {
+        Some(success) => format!(
+            "Updated at {}",
+            Local.timestamp(success.at, 0).format("%b %d, %H:%M")
+        ),
+        None if status == HealthStatus::Unknown => String::from("No updates yet"),
+        None => String::from("Never updated"),
+    };
+    let last_update = if *paused {
+        format!("Paused, {}", last_update.to_lowercase())
+    } else {
+        last_update
+    };
+
+    ui.add_space(PADDING);
+    ui.horizontal(|ui| {
+        ui.colored_label(Color32::from_rgb(r, g, b), "●");
+        ui.label(RichText::new(last_update).color(Color32::from_white_alpha(120)))
+            .on_hover_text(health.summary());
+
+        match refreshing {
+            Some(refresh) => match refresh.try_recv() {
+                Err(TryRecvError::Empty) => {
+                    ui.label(RichText::new("Refreshing…").color(Color32::from_white_alpha(120)));
+                    ui.ctx().request_repaint();
+                }
+                _ => {
+                    *refreshing = None;
+                    *health = RefreshHealth::load();
+                }
+            },
+            None => {
+                let pause_label = if *paused { "Resume" } else { "Pause" };
+                let mut refresh_clicked = false;
+
+                if ui
+                    .small_button(pause_label)
+                    .on_hover_text("Stop updating the wallpaper, your settings are kept")
+                    .clicked()
+                {
+                    match set_paused(!*paused) {
+                        Ok(_) => {
+                            *paused = !*paused;
+                            // Catch up on the updates that were skipped
+                            refresh_clicked = !*paused;
+                        }
+                        Err(e) => *error_msg = e,
+                    }
+                }
+
+                if !*paused {
+                    refresh_clicked |= ui
+                        .small_button("Refresh now")
+                        .on_hover_text("Update the wallpaper with the saved settings")
+                        .clicked();
+                }
+
+                if refresh_clicked {
+                    let (sender, receiver) = mpsc::channel();
+                    thread::spawn(move || sender.send(refresh_now()).ok());
+
+                    *refreshing = Some(receiver);
+                }
+            }
+        }
+    });
+
+    if let Some(error) = health.recent.last().and_then(|last| last.error.as_ref()) {
+        ui.colored_label(
+            Color32::from_rgb(255, 48, 48),
+            format!("Last update failed: {}", error),
+        );
+    }
+}
+
+fn stale_warning_edit(ui: &mut egui::Ui, stale_warning: &mut StaleWarningConf) {
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut stale_warning.enabled, "Warn when updates fail for")
+            .on_hover_text(
+                "Adds \"Last updated 9h ago\" to the wallpaper so a stuck countdown isn't trusted",
+            );
+
+        ui.add_enabled(
+            stale_warning.enabled,
+            egui::DragValue::new(&mut stale_warning.after_hours)
+                .clamp_range(1..=72)
+                .suffix("h"),
+        );
+    });
+}
+
+fn tabs_edit(ui: &mut egui::Ui, tab: &mut Tab, archive: &mut Vec<ArchivedDeadline>) {
+    ui.add_space(PADDING);
+    ui.horizontal(|ui| {
+        ui.selectable_value(tab, Tab::Deadlines, "Deadlines");
+
+        if ui.selectable_value(tab, Tab::Archive, "Archive").clicked() {
+            *archive = load_archive();
+        }
+    });
+    ui.add_space(PADDING);
+}
+
+/// Deadlines that are over with when they were completed, newest first.
+fn archive_edit(
+    ui: &mut egui::Ui,
+    archive: &mut Vec<ArchivedDeadline>,
+    tags: &[DeadlineTag],
+    error_msg: &mut String,
+) {
+    render_section(ui, "Completed Deadlines", |ui| {
+        if archive.is_empty() {
+            ui.label(
+                RichText::new("Deadlines that are over show up here")
+                    .color(Color32::from_white_alpha(120)),
+            );
+            return;
+        }
+
+        let mut removed = None;
+
+        egui::ScrollArea::vertical()
+            .id_source("archive")
+            .max_height(300.)
+            .show(ui, |ui| {
+                for (i, archived) in archive.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        let title = if archived.title.is_empty() {
+                            "Untitled deadline"
+                        } else {
+                            archived.title.as_str()
+                        };
+                        let title = match find_tag(tags, &archived.tag) {
+                            Some(tag) => {
+                                let [r, g, b] = tag.color;
+                                RichText::new(title).color(Color32::from_rgb(r, g, b))
+                            }
+                            None => RichText::new(title),
+                        };
+
+                        ui.label(title);
+                        ui.label(
+                            RichText::new(format!(
+                                "due {} · completed {}",
+                                Local.timestamp(archived.date, 0).format("%Y-%m-%d %H:%M"),
+                                Local
+                                    .timestamp(archived.completed_at, 0)
+                                    .format("%Y-%m-%d %H:%M")
+                            ))
+                            .color(Color32::from_white_alpha(120)),
+                        );
+
+                        if ui
+                            .small_button("🗑")
+                            .on_hover_text("Remove from the archive")
+                            .clicked()
+                        {
+                            removed = Some(i);
+                        }
+                    });
+                }
+            });
+
+        ui.add_space(PADDING);
+
+        let cleared = ui.button("Clear archive").clicked();
+
+        if cleared {
+            archive.clear();
+        } else if let Some(i) = removed {
+            archive.remove(i);
+        } else {
+            return;
+        }
+
+        if let Err(msg) = save_archive(archive) {
+            *error_msg = msg;
+        }
+    });
+}
+
+fn deadlines_edit(
+    ui: &mut egui::Ui,
+    deadlines: &mut Vec<Deadline>,
+    tags: &[DeadlineTag],
+    selected: &mut usize,
+) {
+    // Tagged deadlines are listed in their tag's color
+    let deadline_label = |i: usize, deadline: &Deadline| {
+        let label = RichText::new(format!("Deadline {}", i + 1));
+
+        match find_tag(tags, &deadline.tag) {
+            Some(tag) => {
+                let [r, g, b] = tag.color;
+                label.color(Color32::from_rgb(r, g, b))
+            }
+            None => label,
+        }
+    };
+
+    ui.horizontal(|ui| {
+        ui.label("Deadline:");
+
+        ComboBox::from_id_source("selected_deadline")
+            .width(125.)
+            .selected_text(deadline_label(*selected, &deadlines[*selected]))
+            .show_ui(ui, |ui| {
+                for (i, deadline) in deadlines.iter().enumerate() {
+                    ui.selectable_value(selected, i, deadline_label(i, deadline));
+                }
+            });
+
+        if ui.small_button("➕").clicked() {
+            deadlines.push(Deadline::default());
+            *selected = deadlines.len() - 1;
+        }
+
+        // There's always at least one deadline to edit
+        if deadlines.len() > 1 && ui.small_button("➖").clicked() {
+            deadlines.remove(*selected);
+            *selected = selected.saturating_sub(1);
+        }
+    });
+}
+
+/// The deadline's tag with its color, typing a new name adds it to the tags.
+fn tag_edit(ui: &mut egui::Ui, tag: &mut String, tags: &mut Vec<DeadlineTag>) {
+    ui.horizontal(|ui| {
+        ui.label("Tag:");
+
+        ComboBox::from_id_source("deadline_tag")
+            .width(80.)
+            .selected_text(if tag.is_empty() { "None" } else { tag.as_str() })
+            .show_ui(ui, |ui| {
+                ui.selectable_value(tag, String::new(), "None");
+
+                for existing in tags.iter() {
+                    let [r, g, b] = existing.color;
+
+                    ui.selectable_value(
+                        tag,
+                        existing.name.clone(),
+                        RichText::new(&existing.name).color(Color32::from_rgb(r, g, b)),
+                    );
+                }
+            });
+
+        let typed = ui.add(
+            egui::TextEdit::singleline(tag)
+                .desired_width(80.)
+                .hint_text("uni"),
+        );
+
+        // Added once it's typed out, not for every letter
+        if typed.lost_focus() && !tag.trim().is_empty() && find_tag(tags, tag).is_none() {
+            let new_tag = DeadlineTag::new(tag, tags);
+            tags.push(new_tag);
+        }
+
+        if let Some(existing) = tags
+            .iter_mut()
+            .find(|existing| existing.name.eq_ignore_ascii_case(tag.trim()))
+        {
+            ui.color_edit_button_srgb(&mut existing.color)
+                .on_hover_text("Every deadline tagged with it uses this color");
+        }
+    });
+}
+
+fn calendar_import_edit(
+    ui: &mut egui::Ui,
+    import: &mut CalendarImport,
+    conf: &mut DeadlinerConf,
+    selected: &mut usize,
+) {
+    let mut load_from = None;
+
+    ui.horizontal(|ui| {
+        if ui.button("Import from calendar…").clicked() {
+            if let Some(path) = rfd::FileDialog::new()
+                .add_filter("iCalendar", &["ics"])
+                .pick_file()
+            {
+                load_from = Some(path.display().to_string());
+            }
+        }
+
+        if ui
+            .button("Import from CSV…")
+            .on_hover_text(
+                "Rows of name, date and an optional tag, Ex: Essay,2022-08-26 17:30,English",
+            )
+            .clicked()
+        {
+            if let Some(path) = rfd::FileDialog::new()
+                .add_filter("CSV", &["csv"])
+                .pick_file()
+            {
+                load_from = Some(path.display().to_string());
+            }
+        }
+
+        ui.add(egui::TextEdit::singleline(&mut import.source).hint_text("or a calendar URL"));
+
+        if !import.source.trim().is_empty() && ui.small_button("Load").clicked() {
+            load_from = Some(import.source.clone());
+        }
+    });
+
+    if let Some(source) = load_from {
+        let events = if source.trim().to_lowercase().ends_with(".csv") {
+            load_csv(&source)
+        } else {
+            load_calendar(&source, conf.download_timeout_secs)
+        };
+
+        match events {
+            Ok(events) => {
+                let upcoming = upcoming_events(events, Utc::now());
+
+                import.error = upcoming
+                    .is_empty()
+                    .then(|| String::from("There are no upcoming deadlines to import"));
+                import.events = upcoming.into_iter().map(|event| (event, false)).collect();
+            }
+            Err(msg) => {
+                import.error = Some(msg);
+                import.events.clear();
+            }
+        }
+    }
+
+    if let Some(error) = &import.error {
+        ui.colored_label(Color32::from_rgb(255, 48, 48), error);
+    }
+
+    if import.events.is_empty() {
+        return;
+    }
+
+    ui.add_space(PADDING);
+
+    egui::ScrollArea::vertical()
+        .id_source("calendar_events")
+        .max_height(150.)
+        .show(ui, |ui| {
+            for (event, picked) in import.events.iter_mut() {
+                let end = event.end_instant().with_timezone(&Local);
+
+                let mut label = format!("{} ({})", event.summary, end.format("%Y-%m-%d %H:%M"));
+                if !event.categories.is_empty() {
+                    label.push_str(&format!(" · {}", event.categories.join(", ")));
+                }
+
+                ui.checkbox(picked, label);
+            }
+        });
+
+    let picked: Vec<Deadline> = import
+        .events
+        .iter()
+        .filter(|(_, picked)| *picked)
+        .map(|(event, _)| event.to_deadline(conf.use_24_hour))
+        .collect();
+
+    ui.horizontal(|ui| {
+        if ui
+            .add_enabled(
+                !picked.is_empty(),
+                egui::Button::new(format!("Add {} deadlines", picked.len())),
+            )
+            .clicked()
+        {
+            // The untouched deadline every config starts with is replaced
+            if conf.deadlines == [Deadline::default()] {
+                conf.deadlines.clear();
+            }
+
+            conf.deadlines.extend(picked.iter().cloned());
+            *selected = conf.deadlines.len() - 1;
+            import.events.clear();
+        }
+
+        if ui.button("Cancel").clicked() {
+            import.events.clear();
+        }
+    });
+}
+
+fn monitors_edit(ui: &mut egui::Ui, monitors: &mut [MonitorConf], deadlines_count: usize) {
+    for (i, monitor) in monitors.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            let ScreenDimensions { width, height } = monitor.dimensions;
+            ui.checkbox(&mut monitor.enabled, format!("{}x{}", width, height))
+                .on_hover_text(&monitor.name);
+
+            let deadline_label = |deadline: Option<usize>| match deadline {
+                Some(i) => format!("Deadline {}", i + 1),
+                None => String::from("All Deadlines"),
+            };
+
+            ComboBox::from_id_source(format!("monitor_deadline_{}", i))
+                .selected_text(deadline_label(monitor.deadline))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut monitor.deadline, None, deadline_label(None));
+
+                    for deadline in 0..deadlines_count {
+                        ui.selectable_value(
+                            &mut monitor.deadline,
+                            Some(deadline),
+                            deadline_label(Some(deadline)),
+                        );
+                    }
+                });
+
+            ui.label("Gamma:");
+            ui.add(
+                egui::DragValue::new(&mut monitor.gamma)
+                    .clamp_range(0.1..=3.0)
+                    .speed(0.01),
+            )
+            .on_hover_text("Above 1 brightens the midtones of this monitor's wallpaper");
+
+            ui.label("Brightness:");
+            ui.add(egui::DragValue::new(&mut monitor.brightness).clamp_range(-100..=100))
+                .on_hover_text("Evens out a monitor that's darker or brighter than the others");
+        });
+    }
+}
+
+/// The deadline's time converted to the local time zone,
+/// only for deadlines in another valid time zone.
+fn resolved_local_time(deadline: &Deadline, use_24_hour: bool) -> Option<String> {
+    let timezone = parse_timezone(&deadline.timezone).ok()??;
+    let date = resolve_deadline_date(deadline, use_24_hour).ok()?;
+
+    let local_format = if use_24_hour {
+        "%Y-%m-%d %H:%M"
+    } else {
+        "%Y-%m-%d %I:%M %p"
+    };
+
+    Some(
+        resolve_deadline(date, Some(timezone))
+            .with_timezone(&Local)
+            .format(local_format)
+            .to_string(),
+    )
+}
+
+fn broadcast_edit(ui: &mut egui::Ui, broadcast: &mut BroadcastConf) {
+    ui.checkbox(&mut broadcast.enabled, "Serve on the local network")
+        .on_hover_text("Classroom displays can open http://<this machine>:<port> in a browser");
+
+    if broadcast.enabled {
+        ui.add_space(PADDING);
+
+        ui.horizontal(|ui| {
+            ui.label("Port:");
+            ui.add(egui::DragValue::new(&mut broadcast.port).clamp_range(1024..=65535));
+        });
+        ui.horizontal(|ui| {
+            ui.label("Resolution:");
+            ui.add(egui::DragValue::new(&mut broadcast.dimensions.width).clamp_range(320..=7680));
+            ui.label("x");
+            ui.add(egui::DragValue::new(&mut broadcast.dimensions.height).clamp_range(240..=4320));
+        });
+        ui.horizontal(|ui| {
+            ui.label("Refresh every");
+            ui.add(egui::DragValue::new(&mut broadcast.refresh_secs).clamp_range(5..=3600));
+            ui.label("seconds");
+        });
+    }
+}
+
+fn announcements_edit(ui: &mut egui::Ui, announcements: &mut AnnouncementsConf) {
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut announcements.enabled, "Speak the time left")
+            .on_hover_text("Uses your system's text-to-speech, Ex: \"2 Hours remaining\"");
+
+        if announcements.enabled && ui.small_button("🔊 Test").clicked() {
+            speak(&announcement(Locale::English, 120)).ok();
+        }
+    });
+
+    if !announcements.enabled {
+        return;
+    }
+
+    ui.add_space(PADDING);
+
+    let mut removed = None;
+
+    for (i, threshold) in announcements.thresholds.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            ui.label("At");
+            ui.add(egui::DragValue::new(threshold).clamp_range(1..=525600));
+            ui.label("minutes left");
+
+            if ui.small_button("✖").clicked() {
+                removed = Some(i);
+            }
+        });
+    }
+
+    if let Some(i) = removed {
+        announcements.thresholds.remove(i);
+    }
+
+    if ui.small_button("+ Add").clicked() {
+        announcements.thresholds.push(60);
+    }
+}
+
+fn notifications_edit(ui: &mut egui::Ui, notifications: &mut NotificationsConf) {
+    ui.checkbox(
+        &mut notifications.enabled,
+        "Show notifications at milestones",
+    );
+
+    if !notifications.enabled {
+        return;
+    }
+
+    ui.add_space(PADDING);
+
+    let mut removed = None;
+
+    for (i, milestone) in notifications.milestones.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            ui.label("At");
+            ui.add(egui::DragValue::new(milestone).clamp_range(0..=525600));
+            ui.label("minutes left")
+                .on_hover_text("0 is when the deadline is over");
+
+            if ui.small_button("✖").clicked() {
+                removed = Some(i);
+            }
+        });
+    }
+
+    if let Some(i) = removed {
+        notifications.milestones.remove(i);
+    }
+
+    if ui.small_button("+ Add").clicked() {
+        notifications.milestones.push(60);
+    }
+}
+
+/// Switches between profiles and returns whether another one was loaded, it's run once it's saved.
+fn profiles_edit(
+    ui: &mut egui::Ui,
+    profiles: &mut Profiles,
+    conf: &mut DeadlinerConf,
+    error_msg: &mut String,
+) -> bool {
+    let mut switched_to = None;
+
+    ui.horizontal(|ui| {
+        ui.label("Profile:");
+
+        ComboBox::from_id_source("profile")
+            .selected_text(&profiles.active)
+            .show_ui(ui, |ui| {
+                for name in &profiles.names {
+                    if ui
+                        .selectable_label(*name == profiles.active, name)
+                        .clicked()
+                        && *name != profiles.active
+                    {
+                        switched_to = Some(name.clone());
+                    }
+                }
+            })
+            .response
+            .on_hover_text("Save to run the picked profile");
+
+        if profiles.active != DEFAULT_PROFILE
+            && ui
+                .small_button("✖")
+                .on_hover_text("Delete this profile")
+                .clicked()
+        {
+            match delete_profile(&profiles.active) {
+                Ok(()) => switched_to = Some(String::from(DEFAULT_PROFILE)),
+                Err(msg) => *error_msg = msg,
+            }
+        }
+
+        ui.add(
+            egui::TextEdit::singleline(&mut profiles.new_name)
+                .hint_text("Work")
+                .desired_width(120.),
+        );
+
+        // New profiles start as a copy of the current inputs
+        if ui.button("+ New profile").clicked() {
+            match sanitize_profile_name(&profiles.new_name) {
+                Ok(name) if profiles.names.contains(&name) => {
+                    *error_msg = format!("There's already a profile named {}!", name)
+                }
+                Ok(name) => {
+                    let cached = profile_cache_dir(&name).join("raw_config.json");
+
+                    match set_active_profile(&name) {
+                        Ok(()) => {
+                            fs::write(cached, serde_json::to_string_pretty(conf).unwrap()).ok();
+                            *profiles = Profiles::load();
+                        }
+                        Err(msg) => *error_msg = msg,
+                    }
+                }
+                Err(msg) => *error_msg = msg,
+            }
+        }
+    });
+
+    if let Some(name) = switched_to {
+        if let Err(msg) = set_active_profile(&name) {
+            *error_msg = msg;
+            return false;
+        }
+
+        *profiles = Profiles::load();
+        *error_msg = String::new();
+        *conf = DeadlinerConf::load_cached(conf.screen_dimensions, Some(conf.monitors.clone()));
+
+        match apply_managed_layer(conf) {
+            Ok(managed) => *conf = managed,
+            Err(msg) => *error_msg = msg,
+        }
+
+        return true;
+    }
+
+    false
+}
+
+fn calendar_sync_edit(ui: &mut egui::Ui, sync: &mut CalendarSyncConf) {
+    ui.checkbox(&mut sync.enabled, "Sync deadlines from a CalDAV calendar")
+        .on_hover_text("Upcoming events are added as deadlines and kept up to date");
+
+    if !sync.enabled {
+        return;
+    }
+
+    ui.add_space(PADDING);
+
+    render_input_with_label(
+        ui,
+        "Calendar URL:",
+        &mut sync.url,
+        "https://cloud.example.com/remote.php/dav/calendars/me/exams/",
+    );
+    render_input_with_label(ui, "Username:", &mut sync.username, "");
+    ui.horizontal(|ui| {
+        ui.label("Password:");
+        ui.add(egui::TextEdit::singleline(&mut sync.password).password(true));
+    });
+    ui.horizontal(|ui| {
+        ui.label("Token:");
+        ui.add(
+            egui::TextEdit::singleline(&mut sync.token)
+                .password(true)
+                .hint_text("OAuth token, Ex: for Google Calendar"),
+        );
+    });
+    render_input_with_label(ui, "Only tagged:", &mut sync.tag, "deadline");
+    ui.horizontal(|ui| {
+        ui.label("Sync every:");
+        ui.add(
+            egui::DragValue::new(&mut sync.interval_minutes)
+                .clamp_range(1..=1440)
+                .suffix(" min"),
+        );
+    });
+
+    let synced = synced_deadlines().len();
+    if synced > 0 {
+        ui.label(
+            RichText::new(format!("{} deadlines synced", synced))
+                .color(Color32::from_white_alpha(120)),
+        );
+    }
+}
+
+fn agenda_edit(ui: &mut egui::Ui, agenda: &mut AgendaConf) {
+    ui.checkbox(&mut agenda.enabled, "Show today's agenda")
+        .on_hover_text("Today's next events from the calendar, in a corner of the wallpaper");
+
+    if !agenda.enabled {
+        return;
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("Events:");
+        ui.add(egui::DragValue::new(&mut agenda.max_events).clamp_range(1..=5));
+
+        ui.label("Font size:");
+        ui.add(egui::DragValue::new(&mut agenda.font_size).clamp_range(8..=96));
+    });
+
+    ui.horizontal(|ui| {
+        ui.label("Corner:");
+
+        ComboBox::from_id_source("agenda_corner")
+            .selected_text(format!("{:?}", agenda.corner))
+            .show_ui(ui, |ui| {
+                for option in Corner::iter().collect::<Vec<_>>() {
+                    ui.selectable_value(&mut agenda.corner, option, format!("{:?}", option));
+                }
+            });
+    });
+}
+
+fn output_targets_edit(ui: &mut egui::Ui, targets: &mut Vec<OutputTargetConf>) {
+    // The wallpaper has to go somewhere
+    let removable = targets.len() > 1;
+    let mut removed = None;
+
+    for (i, target) in targets.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            ComboBox::from_id_source(format!("output_target_{}", i))
+                .selected_text(target.to_string())
+                .show_ui(ui, |ui| {
+                    for option in OutputTargetConf::iter().collect::<Vec<_>>() {
+                        let label = option.to_string();
+                        let is_selected =
+                            std::mem::discriminant(target) == std::mem::discriminant(&option);
+
+                        // Keep the typed path or URL when re-selecting the same option
+                        if ui.selectable_label(is_selected, label).clicked() && !is_selected {
+                            *target = option;
+                        }
+                    }
+                });
+
+            match target {
+                OutputTargetConf::File(path) => {
+                    if ui.button("Save as…").clicked() {
+                        if let Some(picked) = rfd::FileDialog::new()
+                            .add_filter("PNG", &["png"])
+                            .save_file()
+                        {
+                            *path = picked.display().to_string();
                         }
                     }
+
+                    if !path.is_empty() {
+                        ui.label(get_file_name_from_path(path));
+                    }
+                }
+                OutputTargetConf::Http(url) => {
+                    ui.add(
+                        egui::TextEdit::singleline(url).hint_text("https://example.com/wallpaper"),
+                    );
                 }
+                _ => {}
+            }
+
+            if removable && ui.small_button("✖").clicked() {
+                removed = Some(i);
+            }
+        });
+    }
+
+    if let Some(i) = removed {
+        targets.remove(i);
+    }
+
+    if ui.small_button("+ Add output").clicked() {
+        targets.push(OutputTargetConf::File(String::new()));
+    }
+}
+
+fn webhooks_edit(ui: &mut egui::Ui, webhooks: &mut WebhooksConf) {
+    ui.checkbox(&mut webhooks.enabled, "Call webhooks at milestones")
+        .on_hover_text("POSTs the title, time left and milestone as JSON");
+
+    if !webhooks.enabled {
+        return;
+    }
+
+    ui.add_space(PADDING);
+
+    let mut removed_url = None;
+
+    for (i, url) in webhooks.urls.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(url).hint_text("https://discord.com/api/webhooks/…"));
+
+            if ui.small_button("✖").clicked() {
+                removed_url = Some(i);
+            }
+        });
+    }
+
+    if let Some(i) = removed_url {
+        webhooks.urls.remove(i);
+    }
+
+    if ui.small_button("+ Add URL").clicked() {
+        webhooks.urls.push(String::new());
+    }
+
+    ui.add_space(PADDING);
+
+    let mut removed = None;
+
+    for (i, milestone) in webhooks.milestones.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            ui.label("At");
+            ui.add(egui::DragValue::new(milestone).clamp_range(0..=525600));
+            ui.label("minutes left")
+                .on_hover_text("0 is when the deadline is over");
+
+            if ui.small_button("✖").clicked() {
+                removed = Some(i);
+            }
+        });
+    }
 
-                let is_valid = ui.data().get_temp_mut_or(ui.id(), IsValid(true)).0;
-                if !is_valid {
-                    ui.colored_label(Color32::from_rgb(255, 48, 48), "Not an Image");
-                } else if !location.is_empty() {
-                    ui.colored_label(
-                        Color32::from_rgba_unmultiplied(254, 216, 67, 200),
-                        get_file_name_from_path(location),
-                    );
+    if let Some(i) = removed {
+        webhooks.milestones.remove(i);
+    }
+
+    if ui.small_button("+ Add").clicked() {
+        webhooks.milestones.push(60);
+    }
+}
+
+fn deadline_hook_edit(ui: &mut egui::Ui, hook: &mut DeadlineHookConf) {
+    ui.checkbox(
+        &mut hook.enabled,
+        "Run a command when the deadline is reached",
+    );
+
+    if !hook.enabled {
+        return;
+    }
+
+    ui.add_space(PADDING);
+
+    render_input_with_label(ui, "Command:", &mut hook.command, "shutdown");
+
+    let mut removed = None;
+
+    for (i, arg) in hook.args.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            ui.label("Argument:");
+            ui.text_edit_singleline(arg);
+
+            if ui.small_button("✖").clicked() {
+                removed = Some(i);
+            }
+        });
+    }
+
+    if let Some(i) = removed {
+        hook.args.remove(i);
+    }
+
+    if ui.small_button("+ Add argument").clicked() {
+        hook.args.push(String::new());
+    }
+}
+
+fn text_variables_edit(ui: &mut egui::Ui, variables: &mut Vec<ScriptVariableConf>) {
+    ui.label(
+        RichText::new("Use {name} in a template or a title to show what a command prints")
+            .color(Color32::from_white_alpha(120)),
+    );
+
+    let mut removed = None;
+
+    for (i, variable) in variables.iter_mut().enumerate() {
+        ui.add_space(PADDING);
+
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut variable.name)
+                    .desired_width(100.)
+                    .hint_text("word_count"),
+            );
+            ui.label("=");
+            ui.add(
+                egui::TextEdit::singleline(&mut variable.command)
+                    .desired_width(140.)
+                    .hint_text("wc"),
+            );
+
+            if ui.small_button("✖").clicked() {
+                removed = Some(i);
+            }
+        });
+
+        let mut removed_arg = None;
+
+        for (j, arg) in variable.args.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.label("Argument:");
+                ui.text_edit_singleline(arg);
+
+                if ui.small_button("✖").clicked() {
+                    removed_arg = Some(j);
                 }
             });
         }
+
+        if let Some(j) = removed_arg {
+            variable.args.remove(j);
+        }
+
+        if ui.small_button("+ Add argument").clicked() {
+            variable.args.push(String::new());
+        }
     }
 
-    if let Background::FromDisk { mode, .. } | Background::FromURL { mode, .. } = bg {
-        ui.add_space(PADDING);
+    if let Some(i) = removed {
+        variables.remove(i);
+    }
+
+    ui.add_space(PADDING);
+
+    if ui.small_button("+ Add variable").clicked() {
+        variables.push(ScriptVariableConf::default());
+    }
+}
+
+fn sound_alerts_edit(ui: &mut egui::Ui, alerts: &mut SoundAlertsConf) {
+    ui.checkbox(&mut alerts.enabled, "Play a sound at milestones");
+
+    if !alerts.enabled {
+        return;
+    }
+
+    ui.add_space(PADDING);
+
+    ui.horizontal(|ui| {
+        if ui.button("Open sound…").clicked() {
+            if let Some(path) = rfd::FileDialog::new()
+                .add_filter("Audio", &["wav", "mp3", "ogg", "aiff"])
+                .pick_file()
+            {
+                alerts.sound_location = path.display().to_string();
+            }
+        }
+
+        if !alerts.sound_location.is_empty() {
+            ui.label(get_file_name_from_path(&alerts.sound_location));
+        }
+    });
+    ui.horizontal(|ui| {
+        ui.label("Volume:");
+        ui.add(egui::Slider::new(&mut alerts.volume, 0..=100).suffix("%"));
+    });
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut alerts.quiet_hours, "Quiet from");
+        ui.add(
+            egui::DragValue::new(&mut alerts.quiet_from)
+                .clamp_range(0..=23)
+                .suffix(":00"),
+        );
+        ui.label("till");
+        ui.add(
+            egui::DragValue::new(&mut alerts.quiet_till)
+                .clamp_range(0..=23)
+                .suffix(":00"),
+        );
+    });
 
+    let mut removed = None;
+
+    for (i, milestone) in alerts.milestones.iter_mut().enumerate() {
         ui.horizontal(|ui| {
-            ui.label("Wallpaper Mode:");
+            ui.label("At");
+            ui.add(egui::DragValue::new(milestone).clamp_range(1..=525600));
+            ui.label("minutes left");
 
-            ComboBox::from_id_source("background_mode")
-                .selected_text(format!("{:?}", mode))
-                .show_ui(ui, |ui| {
-                    for option in WallpaperMode::iter().collect::<Vec<_>>() {
-                        ui.selectable_value(mode, option, format!("{:?}", option));
+            if ui.small_button("✖").clicked() {
+                removed = Some(i);
+            }
+        });
+    }
+
+    if let Some(i) = removed {
+        alerts.milestones.remove(i);
+    }
+
+    if ui.small_button("+ Add").clicked() {
+        alerts.milestones.push(30);
+    }
+}
+
+fn world_clocks_edit(ui: &mut egui::Ui, world_clocks: &mut Vec<String>) {
+    ui.label("Also show the time in:")
+        .on_hover_text("Adds a line like \"17:00 CET / 11:00 EST\" under the countdown");
+
+    let mut removed = None;
+
+    for (i, name) in world_clocks.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(name)
+                    .desired_width(180.)
+                    .hint_text("America/New_York"),
+            );
+
+            if ui.small_button("✖").clicked() {
+                removed = Some(i);
+            }
+        });
+    }
+
+    if let Some(i) = removed {
+        world_clocks.remove(i);
+    }
+
+    if ui.small_button("+ Add time zone").clicked() {
+        world_clocks.push(String::new());
+    }
+}
+
+fn rotation_edit(ui: &mut egui::Ui, rotation: &mut RotationMode) {
+    ui.horizontal(|ui| {
+        ui.label("Deadlines shown:");
+
+        ComboBox::from_id_source("rotation_mode")
+            .selected_text(format!("{:?}", rotation))
+            .show_ui(ui, |ui| {
+                for option in RotationMode::iter() {
+                    ui.selectable_value(rotation, option, format!("{:?}", option));
+                }
+            })
+            .response
+            .on_hover_text(
+                "Stacked shows all of them, the others show one per refresh in turns, \
+                 Urgency gives closer deadlines more turns",
+            );
+    });
+}
+
+fn overdue_edit(ui: &mut egui::Ui, overdue: &mut OverdueBehavior) {
+    ui.horizontal(|ui| {
+        ui.label("When Over:");
+
+        ComboBox::from_id_source("overdue_behavior")
+            .selected_text(overdue.to_string())
+            .show_ui(ui, |ui| {
+                for option in OverdueBehavior::iter().collect::<Vec<_>>() {
+                    let label = option.to_string();
+                    let is_selected =
+                        std::mem::discriminant(overdue) == std::mem::discriminant(&option);
+
+                    // Keep the typed message when re-selecting the same option
+                    if ui.selectable_label(is_selected, label).clicked() && !is_selected {
+                        *overdue = option;
                     }
-                });
+                }
+            });
+    });
+
+    if let OverdueBehavior::Message(msg) = overdue {
+        ui.add_space(PADDING);
+
+        render_input_with_label(ui, "Message:", msg, "Done!");
+    }
+}
+
+fn unit_format_edit(ui: &mut egui::Ui, unit_format: &mut UnitFormat) {
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut unit_format.compact, "Compact")
+            .on_hover_text("Short units like \"2d 4h\"");
+        ui.checkbox(&mut unit_format.hide_zero_units, "Hide zeros")
+            .on_hover_text("Skip units that are zero, Ex: \"1 Week, 2 Hours\"");
+    });
+    ui.horizontal(|ui| {
+        ui.label("Separator:");
+        ui.add(
+            egui::TextEdit::singleline(&mut unit_format.separator)
+                .desired_width(60.)
+                .hint_text(RichText::new("default").color(Color32::from_white_alpha(20))),
+        )
+        .on_hover_text("Joins the units, Ex: \" · \" or \" and \"");
+    });
+}
+
+fn stress_free_edit(ui: &mut egui::Ui, stress_free: &mut StressFreeConf) {
+    ui.checkbox(&mut stress_free.enabled, "Stress-free")
+        .on_hover_text("Hide the small units while the deadline is still far away");
+
+    if stress_free.enabled {
+        ui.add_space(PADDING);
+
+        ui.horizontal(|ui| {
+            ui.label("Hide hours above");
+            ui.add(
+                egui::DragValue::new(&mut stress_free.hours_threshold_days).clamp_range(0..=365),
+            );
+            ui.label("days");
+        });
+        ui.horizontal(|ui| {
+            ui.label("Hide days above");
+            ui.add(egui::DragValue::new(&mut stress_free.days_threshold_weeks).clamp_range(0..=52));
+            ui.label("weeks");
+        });
+    }
+}
+
+fn focus_edit(ui: &mut egui::Ui, focus: &mut FocusConf) {
+    ui.checkbox(&mut focus.enabled, "Focus timer")
+        .on_hover_text("Counts down work and break intervals instead of the deadlines");
+
+    if focus.enabled {
+        ui.add_space(PADDING);
+
+        ui.horizontal(|ui| {
+            ui.label("Work for");
+            ui.add(egui::DragValue::new(&mut focus.work_minutes).clamp_range(1..=240));
+            ui.label("minutes, then break for");
+            ui.add(egui::DragValue::new(&mut focus.break_minutes).clamp_range(0..=120));
+            ui.label("minutes");
+        });
+    }
+}
+
+fn workdays_edit(ui: &mut egui::Ui, workdays: &mut WorkdaysConf) {
+    ui.checkbox(&mut workdays.enabled, "Working days only")
+        .on_hover_text("Skip weekends and holidays, weeks and months are counted in days");
+
+    if !workdays.enabled {
+        return;
+    }
+
+    ui.add_space(PADDING);
+
+    ui.horizontal(|ui| {
+        ui.label("Days off:");
+
+        for (day_off, name) in workdays
+            .weekend
+            .iter_mut()
+            .zip(["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"])
+        {
+            ui.checkbox(day_off, name);
+        }
+    });
+
+    ui.horizontal(|ui| {
+        ui.label("Public holidays of:");
+        ui.add(
+            egui::TextEdit::singleline(&mut workdays.country)
+                .hint_text("US")
+                .desired_width(40.),
+        )
+        .on_hover_text("Country code, its public holidays are downloaded and skipped too");
+    });
+
+    let mut removed = None;
+
+    for (i, holiday) in workdays.holidays.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            render_date_picker(ui, ("holiday", i), "Holiday:", holiday, "2022-12-25");
+
+            if ui.small_button("✖").clicked() {
+                removed = Some(i);
+            }
+        });
+    }
+
+    if let Some(i) = removed {
+        workdays.holidays.remove(i);
+    }
+
+    if ui.small_button("+ Add holiday").clicked() {
+        workdays.holidays.push(String::new());
+    }
+}
+
+fn urgency_colors_edit(ui: &mut egui::Ui, urgency: &mut UrgencyColorsConf) {
+    ui.checkbox(&mut urgency.enabled, "Urgency colors")
+        .on_hover_text("Blend the countdown's color as the deadline gets closer");
+
+    if !urgency.enabled {
+        return;
+    }
+
+    ui.add_space(PADDING);
+
+    ui.horizontal(|ui| {
+        ui.label("Calm:");
+        ui.color_edit_button_srgb(&mut urgency.calm_color);
+        ui.label("Urgent:");
+        ui.color_edit_button_srgb(&mut urgency.urgent_color);
+    });
+    ui.horizontal(|ui| {
+        ui.label("From");
+        ui.add(egui::DragValue::new(&mut urgency.start_days).clamp_range(1..=365));
+        ui.label("days till");
+        ui.add(egui::DragValue::new(&mut urgency.end_hours).clamp_range(0..=240));
+        ui.label("hours left");
+    });
+}
+
+fn final_hours_edit(ui: &mut egui::Ui, final_hours: &mut FinalHoursConf) {
+    ui.checkbox(&mut final_hours.enabled, "Final hours")
+        .on_hover_text("A bigger, centered countdown when the deadline is close");
+
+    if !final_hours.enabled {
+        return;
+    }
+
+    ui.add_space(PADDING);
+
+    ui.horizontal(|ui| {
+        ui.label("In the last");
+        ui.add(egui::DragValue::new(&mut final_hours.hours).clamp_range(1..=72));
+        ui.label("hours");
+    });
+    ui.horizontal(|ui| {
+        ui.label("Scale:");
+        ui.add(egui::Slider::new(&mut final_hours.font_scale, 1.0..=3.0));
+        ui.label("Color:");
+        ui.color_edit_button_srgb(&mut final_hours.color);
+    });
+}
+
+/// A checkbox per season, the picked ones theme the wallpaper while they're on.
+fn seasonal_edit(ui: &mut egui::Ui, seasonal: &mut SeasonalConf) {
+    ui.label("Seasonal accents:");
+
+    ui.horizontal_wrapped(|ui| {
+        for season in Season::iter() {
+            let mut picked = seasonal.seasons.contains(&season);
+
+            if ui.checkbox(&mut picked, format!("{:?}", season)).changed() {
+                if picked {
+                    seasonal.seasons.push(season);
+                } else {
+                    seasonal.seasons.retain(|s| *s != season);
+                }
+            }
+        }
+    });
+}
+
+fn backdrop_edit(ui: &mut egui::Ui, backdrop: &mut BackdropConf) {
+    ui.checkbox(&mut backdrop.enabled, "Backdrop box")
+        .on_hover_text("Draw a rounded box behind the text");
+
+    if !backdrop.enabled {
+        return;
+    }
+
+    ui.add_space(PADDING);
+
+    ui.horizontal(|ui| {
+        ui.label("Color:");
+        ui.color_edit_button_srgb(&mut backdrop.color);
+        ui.label("Opacity:");
+        ui.add(egui::Slider::new(&mut backdrop.opacity, 0..=100).suffix("%"));
+    });
+    ui.horizontal(|ui| {
+        ui.label("Padding:");
+        ui.add(
+            egui::DragValue::new(&mut backdrop.padding)
+                .clamp_range(0..=500)
+                .suffix("px"),
+        );
+        ui.label("Corners:");
+        ui.add(
+            egui::DragValue::new(&mut backdrop.corner_radius)
+                .clamp_range(0..=500)
+                .suffix("px"),
+        );
+    });
+}
+
+fn text_position_edit(ui: &mut egui::Ui, position: &mut TextPosition) {
+    ui.horizontal(|ui| {
+        ui.label("Text Position:");
+
+        ComboBox::from_id_source("text_position")
+            .selected_text(position.to_string())
+            .show_ui(ui, |ui| {
+                for option in TextPosition::iter().collect::<Vec<_>>() {
+                    let label = option.to_string();
+                    ui.selectable_value(position, option, label);
+                }
+            });
+    });
+
+    if let TextPosition::Custom {
+        x,
+        y,
+        offset_x,
+        offset_y,
+    } = position
+    {
+        ui.add_space(PADDING);
+
+        ui.horizontal(|ui| {
+            ui.label("X:");
+            ui.add(egui::Slider::new(x, 0.0..=100.0).suffix("%"));
+        });
+        ui.horizontal(|ui| {
+            ui.label("Y:");
+            ui.add(egui::Slider::new(y, 0.0..=100.0).suffix("%"));
+        });
+        ui.horizontal(|ui| {
+            ui.label("Offset:");
+            ui.add(egui::DragValue::new(offset_x).prefix("x: ").suffix("px"));
+            ui.add(egui::DragValue::new(offset_y).prefix("y: ").suffix("px"));
         });
     }
 }
 
 impl<'a> Deadliner<'a> {
-    pub fn new(screen_width: u32, screen_height: u32) -> Deadliner<'a> {
-        let default = Deadliner {
+    pub fn new(
+        screen_width: u32,
+        screen_height: u32,
+        monitors: Vec<MonitorConf>,
+        launch: Launch,
+        instance_lock: Option<InstanceLock>,
+    ) -> Deadliner<'a> {
+        let screen_dimensions = ScreenDimensions {
+            width: screen_width,
+            height: screen_height,
+        };
+
+        // Deadlines that are over would fail the next save
+        let mut conf = DeadlinerConf::load_cached(screen_dimensions, Some(monitors));
+        archive_passed_deadlines(&mut conf);
+
+        Deadliner {
             textures: HashMap::new(),
             error_msg: String::new(),
             invalid_font: false,
-            conf: DeadlinerConf {
-                screen_dimensions: ScreenDimensions {
-                    width: screen_width,
-                    height: screen_height,
-                },
-                default_background: Background::Solid([0; 3]),
-                custom_font_location: String::new(),
-                font: Font::PoppinsBlack,
-                date: String::new(),
-                hours: String::new(),
-                minutes: String::new(),
-                period: Periods::AM,
-                font_size: 100,
-                font_color: [255, 255, 255],
-                show_hours: true,
-                show_days: true,
-                show_weeks: false,
-                show_months: false,
-            },
-        };
-        let cached = get_cache_dir().join("raw_config.json");
+            selected_deadline: 0,
+            invalid_branding: false,
+            managed_settings: managed_settings(),
+            refresh_health: RefreshHealth::load(),
+            refreshing: None,
+            paused: is_paused(),
+            saving: None,
+            refresh_events: subscribe(),
+            saving_step: "Saving…",
+            pending_warnings: vec![],
+            calendar_import: CalendarImport::default(),
+            mode_preview: ModePreview::default(),
+            wallpaper_preview: WallpaperPreview::default(),
+            profiles: Profiles::load(),
+            bundle_files: true,
+            config_changes: None,
+            last_saved: None,
+            launch,
+            instance_lock,
+            focus_requests: None,
+            focus_requested_at: None,
+            tab: Tab::Deadlines,
+            archive: vec![],
+            conf,
+        }
+        .with_managed_layer()
+    }
+
+    /// Loads the inputs again after they were changed on disk and refreshes the wallpaper
+    /// with them, the GUI's own saves are ignored.
+    fn reload_changed_config(&mut self) {
+        if self.saving.is_some() {
+            return;
+        }
 
-        if cached.exists() {
-            let conf_str = fs::read_to_string(&cached).unwrap();
+        let conf = DeadlinerConf::load_cached(
+            self.conf.screen_dimensions,
+            Some(self.conf.monitors.clone()),
+        );
+        let conf = apply_managed_layer(&conf).unwrap_or(conf);
 
-            Deadliner {
-                conf: serde_json::from_str(&conf_str).unwrap_or_else(|_| {
-                    fs::remove_file(&cached).unwrap();
+        let reloaded = serde_json::to_value(&conf).ok();
+        if reloaded == serde_json::to_value(&self.conf).ok() || reloaded == self.last_saved {
+            return;
+        }
 
-                    default.conf
-                }),
-                ..default
-            }
-        } else {
-            default
+        trace!("reloading the inputs changed on disk");
+
+        self.conf = conf;
+        self.selected_deadline = 0;
+        self.saving_step = "Reloading…";
+        self.saving = Some(start_saving(&self.conf, &mut self.last_saved));
+    }
+
+    /// Shows the managed settings instead of the user's own ones,
+    /// they're applied again on save anyways.
+    fn with_managed_layer(mut self) -> Self {
+        match apply_managed_layer(&self.conf) {
+            Ok(conf) => self.conf = conf,
+            Err(msg) => self.error_msg = msg,
         }
+
+        self
     }
 
     fn set_custom_fonts(&mut self, ctx: &Context) {
         let mut fonts = FontDefinitions::default();
 
-        let fonts_data: Vec<(&str, u16, Vec<u8>)> = vec![
-            (
-                "Poppins-Regular",
-                400,
-                fs::read(new_path("assets/fonts/PoppinsLight.ttf")).unwrap(),
-            ),
-            (
-                "Poppins-Medium",
-                500,
-                fs::read(new_path("assets/fonts/PoppinsRegular.ttf")).unwrap(),
-            ),
-            (
-                "Poppins-SemiBold",
-                600,
-                fs::read(new_path("assets/fonts/PoppinsMedium.ttf")).unwrap(),
-            ),
+        let fonts_data: Vec<(&str, u16, &'static [u8])> = vec![
+            ("Poppins-Regular", 400, POPPINS_LIGHT),
+            ("Poppins-Medium", 500, POPPINS_REGULAR),
+            ("Poppins-SemiBold", 600, POPPINS_MEDIUM),
         ];
 
         // Emoji Fonts
         fonts.font_data.insert(
             "emoji-icon-font".to_owned(),
-            FontData::from_owned(fs::read(new_path("assets/fonts/EmojiFont.ttf")).unwrap()),
+            FontData::from_static(EMOJI_FONT),
         );
         fonts.font_data.insert(
             "noto-emoji-font".to_owned(),
-            FontData::from_owned(fs::read(new_path("assets/fonts/NotoEmojiRegular.ttf")).unwrap()),
+            FontData::from_static(NOTO_EMOJI_REGULAR),
         );
 
         // Insert all of the fonts data
         for (name, font_weight, buffer) in fonts_data {
             fonts
                 .font_data
-                .insert(name.to_owned(), FontData::from_owned(buffer));
+                .insert(name.to_owned(), FontData::from_static(buffer));
 
             fonts.families.insert(
                 FontFamily::Name(format!("Poppins-{}", font_weight).into()),
@@ -1,8 +1,11 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")] // hide console window on Windows in release
 
-use std::fs;
+use std::{env, fs, process};
 
-use deadliner_gui::{new_path, Deadliner};
+use deadliner_gui::{
+    enter_safe_mode_if_crashed, lock_single_instance, run_cli, run_install_hooks, Deadliner,
+    MonitorConf, ScreenDimensions, SingleInstance,
+};
 use eframe::{
     epaint::{Pos2, Vec2},
     run_native, NativeOptions,
@@ -10,7 +13,7 @@ use eframe::{
 #[cfg(target_os = "windows")]
 use winit::platform::windows::WindowBuilderExtWindows;
 use winit::{
-    dpi::PhysicalSize,
+    dpi::{PhysicalPosition, PhysicalSize},
     event_loop::EventLoop,
     window::{Icon, Window, WindowBuilder},
 };
@@ -24,12 +27,42 @@ fn main() {
         fs::create_dir(deadliner_cache).unwrap();
     }
 
+    let args: Vec<String> = env::args().collect();
+    if let Some(exit_code) = run_cli(&args) {
+        process::exit(exit_code);
+    }
+
+    // A second GUI would fight the first one over the wallpaper, hand off to it instead
+    let instance_lock = match lock_single_instance() {
+        SingleInstance::AlreadyRunning => process::exit(0),
+        SingleInstance::Locked(lock) => Some(lock),
+        SingleInstance::Unavailable => None,
+    };
+
+    // After the CLI, `deadliner uninstall-cleanup` mustn't set up the data it removes
+    let launch = run_install_hooks();
+    enter_safe_mode_if_crashed();
+
     // Get the primary screen dimensions
     let event_loop = EventLoop::new();
     let window = build_window(event_loop);
 
     let PhysicalSize { width, height } = window.primary_monitor().unwrap().size();
 
+    let monitors = window
+        .available_monitors()
+        .map(|monitor| {
+            let PhysicalSize { width, height } = monitor.size();
+            let PhysicalPosition { x, y } = monitor.position();
+
+            MonitorConf::new(
+                monitor.name().unwrap_or_default(),
+                [x, y],
+                ScreenDimensions { width, height },
+            )
+        })
+        .collect();
+
     // Set the initial window position at the very bottom right
     let app_width = 400.;
     let app_height = 655.;
@@ -45,14 +78,14 @@ fn main() {
         ..Default::default()
     };
 
-    let app = Deadliner::new(width, height);
+    let app = Deadliner::new(width, height, monitors, launch, instance_lock);
 
     run_native(Box::new(app), win_options);
 }
 
 fn build_window(event_loop: EventLoop<()>) -> Window {
-    let icon = image::open(new_path("assets/icon.png"))
-        .expect("Failed to open icon path")
+    let icon = image::load_from_memory(include_bytes!("../assets/icon.png"))
+        .expect("The bundled icon is a valid PNG")
         .to_rgba8();
 
     let (icon_width, icon_height) = icon.dimensions();
@@ -0,0 +1,371 @@
+use std::io::{self, Stdout};
+
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame, Terminal,
+};
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+use crate::{
+    deadline_lines, render_wallpaper, sanitize_inputs, save_inputs, unwrap_or_return, Background,
+    CountdownStyle, DeadlinerConf, Font, Periods, ScreenDimensions, StressFreeConf,
+};
+
+/// A quick way to style the countdown where there's no color picker.
+#[derive(Debug, PartialEq, Clone, Copy, EnumIter)]
+pub enum StylePreset {
+    Classic,
+    Minimal,
+    Bold,
+    Calm,
+}
+
+impl StylePreset {
+    /// Only the solid background's color is changed, background images are kept.
+    pub fn apply(&self, conf: &mut DeadlinerConf) {
+        let (font, font_size, font_color, bg_color) = match self {
+            StylePreset::Classic => (Font::PoppinsBlack, 100, [255, 255, 255], [0, 0, 0]),
+            StylePreset::Minimal => (Font::PoppinsLight, 70, [231, 231, 231], [17, 17, 17]),
+            StylePreset::Bold => (Font::PoppinsBlack, 140, [255, 208, 0], [0, 0, 0]),
+            StylePreset::Calm => (Font::PoppinsRegular, 80, [220, 230, 240], [28, 40, 56]),
+        };
+
+        conf.font = font;
+        conf.font_size = font_size;
+        conf.font_color = font_color;
+
+        if let Background::Solid { color, .. } = &mut conf.default_background {
+            *color = bg_color;
+        }
+
+        conf.countdown_style = if *self == StylePreset::Calm {
+            CountdownStyle::Fuzzy
+        } else {
+            CountdownStyle::Exact
+        };
+        conf.stress_free = StressFreeConf {
+            enabled: *self == StylePreset::Calm,
+            ..conf.stress_free
+        };
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, EnumIter)]
+enum Field {
+    Deadline,
+    Title,
+    Date,
+    Hours,
+    Minutes,
+    Period,
+    TimeZone,
+    Preset,
+}
+
+impl Field {
+    fn label(&self) -> &str {
+        match self {
+            Field::Deadline => "Deadline",
+            Field::Title => "Title",
+            Field::Date => "Date",
+            Field::Hours => "Hours",
+            Field::Minutes => "Minutes",
+            Field::Period => "Period",
+            Field::TimeZone => "Time Zone",
+            Field::Preset => "Style",
+        }
+    }
+}
+
+struct Tui {
+    conf: DeadlinerConf,
+    selected_deadline: usize,
+    selected_field: usize,
+    /// `None` keeps the current styling.
+    preset: Option<StylePreset>,
+    status: Result<String, String>,
+    saved: bool,
+}
+
+impl Tui {
+    fn field(&self) -> Field {
+        Field::iter().nth(self.selected_field).unwrap()
+    }
+
+    /// Text of the fields that are edited by typing.
+    fn text_mut(&mut self, field: Field) -> Option<&mut String> {
+        let deadline = &mut self.conf.deadlines[self.selected_deadline];
+
+        match field {
+            Field::Title => Some(&mut deadline.title),
+            Field::Date => Some(&mut deadline.date),
+            Field::Hours => Some(&mut deadline.hours),
+            Field::Minutes => Some(&mut deadline.minutes),
+            Field::TimeZone => Some(&mut deadline.timezone),
+            _ => None,
+        }
+    }
+
+    fn value(&self, field: Field) -> String {
+        let deadline = &self.conf.deadlines[self.selected_deadline];
+
+        match field {
+            Field::Deadline => format!(
+                "◀ {} of {} ▶",
+                self.selected_deadline + 1,
+                self.conf.deadlines.len()
+            ),
+            Field::Title => deadline.title.clone(),
+            Field::Date => deadline.date.clone(),
+            Field::Hours => deadline.hours.clone(),
+            Field::Minutes => deadline.minutes.clone(),
+            Field::Period if self.conf.use_24_hour => String::from("24-hour"),
+            Field::Period => format!("◀ {:?} ▶", deadline.period),
+            Field::TimeZone => deadline.timezone.clone(),
+            Field::Preset => match self.preset {
+                Some(preset) => format!("◀ {:?} ▶", preset),
+                None => String::from("◀ Keep current ▶"),
+            },
+        }
+    }
+
+    /// Cycles the value of the fields that have a fixed set of choices.
+    fn cycle(&mut self, forward: bool) {
+        match self.field() {
+            Field::Deadline => {
+                let count = self.conf.deadlines.len();
+                self.selected_deadline = if forward {
+                    (self.selected_deadline + 1) % count
+                } else {
+                    (self.selected_deadline + count - 1) % count
+                };
+            }
+            Field::Period if !self.conf.use_24_hour => {
+                let deadline = &mut self.conf.deadlines[self.selected_deadline];
+                deadline.period = match deadline.period {
+                    Periods::AM => Periods::PM,
+                    Periods::PM => Periods::AM,
+                };
+            }
+            Field::Preset => {
+                let mut choices: Vec<Option<StylePreset>> = vec![None];
+                choices.extend(StylePreset::iter().map(Some));
+
+                let current = choices.iter().position(|p| *p == self.preset).unwrap();
+                let next = if forward {
+                    (current + 1) % choices.len()
+                } else {
+                    (current + choices.len() - 1) % choices.len()
+                };
+                self.preset = choices[next];
+            }
+            _ => (),
+        }
+    }
+
+    fn apply_preset(&mut self) {
+        if let Some(preset) = self.preset {
+            preset.apply(&mut self.conf);
+        }
+    }
+
+    fn save(&mut self) {
+        self.apply_preset();
+
+        self.status = save_inputs(&self.conf)
+            .map(|_| String::from("Saved! The wallpaper is updated and the schedular is running."))
+            .map_err(|e| e.user_message());
+        self.saved |= self.status.is_ok();
+    }
+
+    /// Renders the wallpaper to the cache without setting it or saving the inputs.
+    fn render(&mut self) {
+        self.apply_preset();
+
+        self.status = sanitize_inputs(&self.conf).and_then(|conf| {
+            let (file_path, _) = render_wallpaper(&conf, false)?;
+
+            let countdown: Vec<String> = deadline_lines(&conf, false)
+                .into_iter()
+                .map(|(_, line)| line.text)
+                .collect();
+
+            Ok(format!("Rendered {}\n{}", file_path, countdown.join(" | ")))
+        });
+    }
+
+    /// Returns `false` once the user quits.
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let fields_count = Field::iter().count();
+
+        match key.code {
+            KeyCode::Esc => return false,
+            KeyCode::Char('c') | KeyCode::Char('q') if ctrl => return false,
+            KeyCode::Char('s') if ctrl => self.save(),
+            KeyCode::Enter => self.save(),
+            KeyCode::Char('r') if ctrl => self.render(),
+            KeyCode::Tab | KeyCode::Down => {
+                self.selected_field = (self.selected_field + 1) % fields_count
+            }
+            KeyCode::BackTab | KeyCode::Up => {
+                self.selected_field = (self.selected_field + fields_count - 1) % fields_count
+            }
+            KeyCode::Left => self.cycle(false),
+            KeyCode::Right => self.cycle(true),
+            KeyCode::Backspace => {
+                if let Some(text) = self.text_mut(self.field()) {
+                    text.pop();
+                }
+            }
+            KeyCode::Char(c) if !ctrl => {
+                if let Some(text) = self.text_mut(self.field()) {
+                    text.push(c);
+                }
+            }
+            _ => (),
+        }
+
+        true
+    }
+
+    fn draw(&self, f: &mut Frame) {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(Field::iter().count() as u16 + 2),
+                Constraint::Min(4),
+                Constraint::Length(1),
+            ])
+            .split(f.size());
+
+        let fields: Vec<Line> = Field::iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let style = if i == self.selected_field {
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                };
+
+                let cursor = if i == self.selected_field && field_is_text(field) {
+                    "▏"
+                } else {
+                    ""
+                };
+
+                Line::from(vec![
+                    Span::styled(format!("{:>10}: ", field.label()), style),
+                    Span::raw(self.value(field)),
+                    Span::styled(cursor, style),
+                ])
+            })
+            .collect();
+
+        f.render_widget(
+            Paragraph::new(fields)
+                .block(Block::default().borders(Borders::ALL).title(" Deadliner ")),
+            rows[0],
+        );
+
+        let (status, color) = match &self.status {
+            Ok(msg) => (msg.as_str(), Color::Green),
+            Err(msg) => (msg.as_str(), Color::Red),
+        };
+
+        f.render_widget(
+            Paragraph::new(status)
+                .style(Style::default().fg(color))
+                .wrap(Wrap { trim: true })
+                .block(Block::default().borders(Borders::ALL).title(" Status ")),
+            rows[1],
+        );
+
+        f.render_widget(
+            Paragraph::new(
+                "Tab/↑↓ move  ←→ change  Enter/Ctrl-S save  Ctrl-R render only  Esc quit",
+            )
+            .style(Style::default().fg(Color::DarkGray)),
+            rows[2],
+        );
+    }
+}
+
+fn field_is_text(field: Field) -> bool {
+    matches!(
+        field,
+        Field::Title | Field::Date | Field::Hours | Field::Minutes | Field::TimeZone
+    )
+}
+
+/// Edits the saved inputs from the terminal, used by `deadliner tui` on SSH or headless boxes.
+pub fn run_tui() -> Result<String, String> {
+    // There's no way to detect monitors from a terminal, keep the saved ones
+    let conf = DeadlinerConf::load_cached(
+        ScreenDimensions {
+            width: 1920,
+            height: 1080,
+        },
+        None,
+    );
+
+    let mut tui = Tui {
+        conf,
+        selected_deadline: 0,
+        selected_field: 1,
+        preset: None,
+        status: Ok(String::from("Edit the deadline and hit Enter to save it.")),
+        saved: false,
+    };
+
+    let mut terminal = unwrap_or_return!(setup_terminal(), "Couldn't start the terminal UI!");
+    let result = event_loop(&mut terminal, &mut tui);
+    restore_terminal(&mut terminal);
+
+    unwrap_or_return!(result, "The terminal UI crashed!");
+
+    if tui.saved {
+        Ok(String::from("Saved your deadline."))
+    } else {
+        Ok(String::from("Quit without saving."))
+    }
+}
+
+fn setup_terminal() -> io::Result<Terminal<CrosstermBackend<Stdout>>> {
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen)?;
+
+    Terminal::new(CrosstermBackend::new(io::stdout()))
+}
+
+fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<Stdout>>) {
+    disable_raw_mode().ok();
+    execute!(terminal.backend_mut(), LeaveAlternateScreen).ok();
+    terminal.show_cursor().ok();
+}
+
+fn event_loop(terminal: &mut Terminal<CrosstermBackend<Stdout>>, tui: &mut Tui) -> io::Result<()> {
+    loop {
+        terminal.draw(|f| tui.draw(f))?;
+
+        if let Event::Key(key) = event::read()? {
+            // Windows reports key releases as well
+            if key.kind == KeyEventKind::Press && !tui.handle_key(key) {
+                return Ok(());
+            }
+        }
+    }
+}
@@ -4,7 +4,7 @@ use std::{
     path::{Path, PathBuf},
 };
 
-const COPY_DIR: &'static str = "icons";
+const COPY_DIR: &str = "icons";
 
 /// A helper function for recursively copying a directory.
 fn copy_dir<P, Q>(from: P, to: Q)
@@ -48,5 +48,5 @@ fn main() {
 
     // Copy the directory
     copy_dir(COPY_DIR, &out);
-    copy("port.txt", &out.join("../port.txt")).unwrap();
+    copy("port.txt", out.join("../port.txt")).unwrap();
 }
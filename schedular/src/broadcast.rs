@@ -0,0 +1,56 @@
+use deadliner_core::{render_broadcast, SanitizedConf};
+use std::{
+    io::prelude::*,
+    net::{TcpListener, TcpStream},
+};
+
+use crate::{response, Request};
+
+/// Serves an auto-refreshing page with the countdown to every machine on the local network.
+pub fn run_broadcast_server(conf: SanitizedConf) {
+    let addr = format!("0.0.0.0:{}", conf.broadcast.port);
+
+    let listener = match TcpListener::bind(&addr) {
+        Ok(listener) => listener,
+        Err(_) => return eprintln!("Couldn't broadcast the countdown on {}!", addr),
+    };
+
+    for stream in listener.incoming().flatten() {
+        handle_broadcast_connection(stream, &conf);
+    }
+}
+
+fn handle_broadcast_connection(mut stream: TcpStream, conf: &SanitizedConf) {
+    let mut buffer = [0; 1024];
+    let read = match stream.read(&mut buffer) {
+        Ok(read) => read,
+        Err(_) => return,
+    };
+
+    let uri = Request::new(&buffer[..read]).parse().map(|req| req.uri);
+
+    let res = match uri.as_deref() {
+        Some("/") => response(
+            200,
+            "OK",
+            "Content-Type: text/html; charset=utf-8",
+            &conf.broadcast.page(),
+        )
+        .into_bytes(),
+        Some("/countdown.png") => match render_broadcast(conf) {
+            Ok(png) => {
+                let headers = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: image/png\r\nContent-Length: {}\r\nCache-Control: no-store\r\n\r\n",
+                    png.len()
+                );
+
+                [headers.into_bytes(), png].concat()
+            }
+            Err(msg) => response(500, "Internal Server Error", "", &msg).into_bytes(),
+        },
+        _ => response(404, "Not Found", "", "").into_bytes(),
+    };
+
+    stream.write_all(&res).ok();
+    stream.flush().ok();
+}
@@ -0,0 +1,25 @@
+use std::{thread, time::Duration};
+
+use deadliner_core::{sync_calendar, SanitizedConf};
+
+use crate::{log_refresh, refresh_with_watchdog};
+
+/// Pulls the synced calendar every `interval_minutes` and re-renders when its deadlines changed.
+pub fn start_calendar_sync(conf: &SanitizedConf) {
+    if !conf.calendar_sync.enabled {
+        return;
+    }
+
+    let conf = conf.clone();
+    let interval = Duration::from_secs(conf.calendar_sync.interval_minutes as u64 * 60);
+
+    thread::spawn(move || loop {
+        match sync_calendar(&conf) {
+            Ok(true) => refresh_with_watchdog(&conf),
+            Ok(false) => {}
+            Err(e) => log_refresh(&format!("calendar sync failed: {}", e)),
+        }
+
+        thread::sleep(interval);
+    });
+}
@@ -0,0 +1,40 @@
+use std::{fs, thread};
+
+use deadliner_core::{control_socket_path, serve_control_connection};
+
+/// Lets scripts tell the running schedular to refresh, pause, move the deadline or
+/// report the time left, one command per line, see `handle_control_command`.
+#[cfg(unix)]
+pub fn run_control_socket() {
+    use std::os::unix::net::UnixListener;
+
+    let path = control_socket_path();
+
+    // Left behind by a schedular that didn't exit cleanly
+    fs::remove_file(&path).ok();
+
+    let listener = match UnixListener::bind(&path) {
+        Ok(listener) => listener,
+        Err(_) => return,
+    };
+
+    for stream in listener.incoming().flatten() {
+        thread::spawn(move || serve_control_connection(stream));
+    }
+}
+
+/// Windows has no Unix sockets in std, the same protocol goes over a local port there.
+#[cfg(not(unix))]
+pub fn run_control_socket() {
+    use deadliner_core::control_port;
+    use std::net::TcpListener;
+
+    let listener = match TcpListener::bind(("127.0.0.1", control_port())) {
+        Ok(listener) => listener,
+        Err(_) => return,
+    };
+
+    for stream in listener.incoming().flatten() {
+        thread::spawn(move || serve_control_connection(stream));
+    }
+}
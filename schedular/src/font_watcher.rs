@@ -0,0 +1,75 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use deadliner_core::{font_path, new_path, SanitizedConf};
+
+use crate::{log_refresh, refresh_with_watchdog};
+
+const POLL_EVERY: Duration = Duration::from_secs(2);
+
+/// Path, modification time and size of a watched file.
+type FileStamp = (PathBuf, Option<SystemTime>, u64);
+
+/// Re-renders the wallpaper whenever the font or anything in the assets directory
+/// is replaced, so typography can be iterated on without saving from the GUI again.
+pub fn watch_fonts(conf: &SanitizedConf) {
+    let conf = conf.clone();
+
+    thread::spawn(move || {
+        let mut last = font_stamps(&conf);
+
+        loop {
+            thread::sleep(POLL_EVERY);
+
+            let stamps = font_stamps(&conf);
+            if stamps == last {
+                continue;
+            }
+
+            // Wait for the file to be fully written before rendering with it
+            thread::sleep(POLL_EVERY);
+            let settled = font_stamps(&conf);
+            if settled != stamps {
+                continue;
+            }
+
+            log_refresh("the font or the assets changed, re-rendering");
+
+            refresh_with_watchdog(&conf);
+            last = settled;
+        }
+    });
+}
+
+fn font_stamps(conf: &SanitizedConf) -> Vec<FileStamp> {
+    let mut stamps = Vec::new();
+
+    stamp(&font_path(conf), &mut stamps);
+    stamp(&new_path("assets"), &mut stamps);
+
+    stamps
+}
+
+fn stamp(path: &Path, stamps: &mut Vec<FileStamp>) {
+    let metadata = match fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(_) => return,
+    };
+
+    if metadata.is_dir() {
+        let mut entries: Vec<PathBuf> = fs::read_dir(path)
+            .map(|entries| entries.flatten().map(|entry| entry.path()).collect())
+            .unwrap_or_default();
+        entries.sort();
+
+        for entry in entries {
+            stamp(&entry, stamps);
+        }
+    } else {
+        stamps.push((path.to_path_buf(), metadata.modified().ok(), metadata.len()));
+    }
+}
@@ -1,28 +1,63 @@
-mod macros;
+mod broadcast;
+mod calendar_sync;
+mod control_socket;
+mod font_watcher;
 mod notify;
+mod screen_changes;
 mod server;
 mod startup_launch;
+mod subscribers;
 mod system_tray;
+mod watchdog;
 
 use std::{
-    env, fs,
+    env,
     sync::{Arc, Mutex},
+    thread,
 };
 
-use chrono::{Local, NaiveDateTime};
-use deadliner_gui::{generate_deadline_over_wallpaper, new_path, update_wallpaper, SanitizedConf};
-pub use macros::*;
+pub use broadcast::*;
+pub use calendar_sync::*;
+pub use control_socket::*;
+use deadliner_core::{
+    enter_safe_mode_if_crashed, generate_deadline_over_wallpaper, is_paused, load_sanitized_conf,
+    milestone_events, publish, remove_sanitized_conf, restore_original_wallpaper,
+    set_desktop_wallpaper, subscribe, update_wallpaper, with_synced_deadlines, DeadlinerEvent,
+    OverdueBehavior, SanitizedConf, TimeUnit,
+};
+pub use font_watcher::*;
 pub use notify::*;
+pub use screen_changes::*;
 pub use server::*;
 pub use startup_launch::*;
+pub use subscribers::*;
 pub use system_tray::*;
 use tokio_cron_scheduler::{Job, JobScheduler};
+pub use watchdog::*;
+
+/// The scheduler with the jobs updating the wallpaper, kept so they can be replaced
+/// when new inputs are saved while the schedular runs.
+struct Scheduled {
+    sched: JobScheduler,
+    jobs: Vec<Job>,
+    exit: Arc<Mutex<bool>>,
+}
+
+static SCHEDULED: Mutex<Option<Scheduled>> = Mutex::new(None);
 
 pub fn start_schedular(exit: Arc<Mutex<bool>>) {
-    let conf_str =
-        fs::read_to_string(new_path("config.json")).expect("Can't read Config JSON file!");
+    let conf = match load_sanitized_conf() {
+        Ok(conf) => conf,
+        Err(msg) => return eprintln!("Not scheduling any updates: {}", msg),
+    };
+
+    // The last run crashed while rendering, keep rendering with the default styling
+    enter_safe_mode_if_crashed();
 
-    let conf: SanitizedConf = serde_json::from_str(&conf_str).unwrap();
+    if conf.broadcast.enabled {
+        let broadcast_conf = conf.clone();
+        thread::spawn(move || run_broadcast_server(broadcast_conf));
+    }
 
     let args: Vec<String> = env::args().collect();
     let skip_update_on_startup = args.get(1) == Some(&"skip-update-on-launch".to_string());
@@ -30,17 +65,20 @@ pub fn start_schedular(exit: Arc<Mutex<bool>>) {
 
     if !skip_update_on_startup {
         // Run on OS launch
-        update_wallpaper(&conf, false).unwrap();
+        refresh_with_watchdog(&conf);
+
+        // Synced deadlines from the last run count until the next sync
+        let with_synced = with_synced_deadlines(&conf);
 
-        let minutes = get_minutes_left(&conf);
-        if minutes <= 0 {
-            set_deadline_is_over(&conf);
+        let minutes = with_synced.minutes_left();
+        if minutes <= 0 && !conf.keeps_running() {
+            set_deadline_is_over(&with_synced, true);
 
             let mut exit = exit.lock().unwrap();
             *exit = true;
             schedule = false;
-        } else if minutes < 60 {
-            update_wallpaper(&conf, false).unwrap();
+        } else if with_synced.closest_minutes_left() < 60 {
+            refresh_with_watchdog(&conf);
         }
     }
 
@@ -48,10 +86,71 @@ pub fn start_schedular(exit: Arc<Mutex<bool>>) {
         return;
     }
 
+    spawn_subscribers(&conf);
+    watch_fonts(&conf);
+    start_calendar_sync(&conf);
+
     let mut sched = JobScheduler::new();
+    let jobs = schedule_updates(&mut sched, &conf, Arc::clone(&exit));
+
+    sched.start();
+    *SCHEDULED.lock().unwrap() = Some(Scheduled { sched, jobs, exit });
 
-    if conf.show_hours {
-        let minutes_till_next_hour = get_minutes_left(&conf) % 60 + 1;
+    reschedule_on_saved_inputs();
+}
+
+/// Inputs saved by a control command don't restart the schedular,
+/// the updates are scheduled again for them instead.
+fn reschedule_on_saved_inputs() {
+    let events = subscribe();
+
+    thread::spawn(move || {
+        for event in events {
+            if !matches!(event, DeadlinerEvent::InputsSaved) {
+                continue;
+            }
+
+            let conf = match load_sanitized_conf() {
+                Ok(conf) => conf,
+                Err(e) => {
+                    log_refresh(&format!("couldn't reschedule the updates: {}", e));
+                    continue;
+                }
+            };
+
+            if let Some(scheduled) = SCHEDULED.lock().unwrap().as_mut() {
+                for job in scheduled.jobs.drain(..) {
+                    scheduled.sched.remove(&job.guid()).ok();
+                }
+
+                scheduled.jobs =
+                    schedule_updates(&mut scheduled.sched, &conf, Arc::clone(&scheduled.exit));
+            }
+        }
+    });
+}
+
+/// Adds the jobs that keep the wallpaper updated for `conf` and returns them.
+fn schedule_updates(
+    sched: &mut JobScheduler,
+    conf: &SanitizedConf,
+    exit: Arc<Mutex<bool>>,
+) -> Vec<Job> {
+    let mut jobs = Vec::new();
+
+    let shown_units = conf.shown_units();
+    let updates_every_minute = conf.focus.enabled
+        || shown_units.contains(&TimeUnit::Second)
+        || shown_units.contains(&TimeUnit::Minute);
+
+    if shown_units.contains(&TimeUnit::Second) {
+        // Run every second
+        jobs.push(instantiate_job("* * * * * * *", conf.clone()));
+    } else if shown_units.contains(&TimeUnit::Minute) || conf.focus.enabled {
+        // Run every minute, the focus timer counts in minutes too
+        jobs.push(instantiate_job("0 * * * * * *", conf.clone()));
+    } else if conf.show_hours {
+        let minutes_till_next_hour = conf.closest_minutes_left() % 60 + 1;
         let minutes_till_next_hour = if minutes_till_next_hour == 60 {
             minutes_till_next_hour - 1
         } else {
@@ -59,89 +158,100 @@ pub fn start_schedular(exit: Arc<Mutex<bool>>) {
         };
 
         // Run every minute 0, second 0 (aka: every begining of a local hour)
-        sched
-            .add(instantiate_job(
-                &format!("0 {} * * * * *", minutes_till_next_hour),
-                conf.clone(),
-            ))
-            .unwrap();
+        jobs.push(instantiate_job(
+            &format!("0 {} * * * * *", minutes_till_next_hour),
+            conf.clone(),
+        ));
     } else if conf.show_days {
         // Run every midnight
-        sched
-            .add(instantiate_job("0 0 0 * * * * *", conf.clone()))
-            .unwrap();
+        jobs.push(instantiate_job("0 0 0 * * * * *", conf.clone()));
     } else if conf.show_weeks {
         // Run every week
         // First day in the week = Sunday.
         // TODO: ask for the weekend of a user.
-        sched
-            .add(instantiate_job("0 0 0 * * 7 *", conf.clone()))
-            .unwrap();
+        jobs.push(instantiate_job("0 0 0 * * 7 *", conf.clone()));
     } else if conf.show_months {
         // Run every month
-        sched
-            .add(instantiate_job("0 0 0 1 * * *", conf.clone()))
-            .unwrap();
+        jobs.push(instantiate_job("0 0 0 1 * * *", conf.clone()));
     }
 
     // Setup another schedule that run every minute to check if we're near the deadline
     // by less than 60 minutes
-    sched
-        .add(
-            Job::new("0 * * * * * *", move |_uuid, _l| {
-                let minutes = get_minutes_left(&conf);
-
-                // Check every minute if the deadline is over.
-                // If so, exit and remove schedular from auto-startup
-                if minutes <= 0 {
-                    set_deadline_is_over(&conf);
-
-                    let mut exit = exit.lock().unwrap();
-                    *exit = true;
-                } else if minutes < 60 {
-                    update_wallpaper(&conf, false).unwrap();
-                }
-            })
-            .unwrap(),
-        )
-        .unwrap();
+    let conf = conf.clone();
+    jobs.push(
+        Job::new("0 * * * * * *", move |_uuid, _l| {
+            let conf = &with_synced_deadlines(&conf);
 
-    sched.start();
+            for event in milestone_events(conf) {
+                publish(event);
+            }
+
+            let minutes = conf.minutes_left();
+
+            // Check every minute if all of the deadlines are over.
+            // If so, exit and remove schedular from auto-startup unless
+            // the wallpaper should keep getting updated after that.
+            if minutes <= 0 && !conf.keeps_running() {
+                // A milestone notification could've just said it's over
+                set_deadline_is_over(conf, !conf.notifications.notifies_when_over());
+
+                let mut exit = exit.lock().unwrap();
+                *exit = true;
+            } else if conf.closest_minutes_left() < 60 && !updates_every_minute {
+                refresh_with_watchdog(conf);
+            }
+        })
+        .unwrap(),
+    );
+
+    for job in &jobs {
+        sched.add(job.clone()).unwrap();
+    }
+
+    jobs
 }
 
-fn instantiate_job<'a>(cron: &str, conf: SanitizedConf) -> Job {
-    let job = Job::new(cron, move |_uuid, _l| {
-        update_wallpaper(&conf, false).unwrap();
+fn instantiate_job(cron: &str, conf: SanitizedConf) -> Job {
+    Job::new(cron, move |_uuid, _l| {
+        refresh_with_watchdog(&conf);
     })
-    .unwrap();
-
-    job
+    .unwrap()
 }
 
-fn set_deadline_is_over(conf: &SanitizedConf) {
+fn set_deadline_is_over(conf: &SanitizedConf, notify: bool) {
     unregister_auto_launch();
-    fs::remove_file(new_path("config.json")).unwrap();
-
-    let file_path = generate_deadline_over_wallpaper("Deadline is Over", &conf);
+    remove_sanitized_conf();
 
-    match file_path {
-        Ok(file_path) => {
-            // Sets the wallpaper for the current desktop from a URL.
-            wallpaper::set_mode(conf.bg_mode.into()).unwrap();
-            wallpaper::set_from_path(&file_path).unwrap();
+    match conf.overdue {
+        OverdueBehavior::RestoreOriginal => {
+            restore_original_wallpaper().ok();
         }
-        _ => {}
+        // All deadlines are over by now so they're dropped from the wallpaper
+        OverdueBehavior::ClearText => {
+            update_wallpaper(conf, false).ok();
+        }
+        _ => set_party_wallpaper(conf),
     }
 
-    notify_deadline_over();
+    if notify {
+        notify_deadline_over();
+    }
 }
 
-fn get_minutes_left(conf: &SanitizedConf) -> i64 {
-    let today = Local::now().naive_local();
-    let deadline = NaiveDateTime::parse_from_str(&conf.deadline_str, "%Y-%m-%d %I:%M %p").unwrap();
-    let diff = deadline.signed_duration_since(today);
+fn set_party_wallpaper(conf: &SanitizedConf) {
+    if is_paused() {
+        return;
+    }
 
-    let minutes = diff.num_minutes();
+    let locale = conf
+        .deadlines
+        .first()
+        .map(|deadline| deadline.locale)
+        .unwrap_or_default();
 
-    minutes
+    let file_path = generate_deadline_over_wallpaper(locale.deadline_over(), conf);
+
+    if let Ok(file_path) = file_path {
+        set_desktop_wallpaper(&file_path, conf.os_mode().into()).ok();
+    }
 }
@@ -5,7 +5,11 @@ use std::{
     thread,
 };
 
-use deadliner_schedular::{bg_system_tray, register_auto_launch, run_server, start_schedular};
+use deadliner_core::load_sanitized_conf;
+use deadliner_schedular::{
+    bg_system_tray, register_auto_launch, run_control_socket, run_server, start_schedular,
+    unregister_auto_launch,
+};
 
 #[tokio::main]
 async fn main() {
@@ -21,6 +25,14 @@ async fn main() {
         run_server(server_exit);
     });
 
-    register_auto_launch();
+    // Scripts control the running schedular through it, Ex: `deadliner control pause`
+    thread::spawn(run_control_socket);
+
+    // The tray is all the schedular shows, so it's launched on login minimized to it
+    if load_sanitized_conf().map_or(true, |conf| conf.launch_on_login) {
+        register_auto_launch();
+    } else {
+        unregister_auto_launch();
+    }
     bg_system_tray(exit);
 }
@@ -1,3 +1,6 @@
+use std::process::Command;
+
+use deadliner_core::{DeadlineHookConf, SanitizedDeadline, SoundAlertsConf};
 use notify_rust::Notification;
 
 pub fn notify_deadline_over() {
@@ -8,3 +11,62 @@ pub fn notify_deadline_over() {
         .show()
         .unwrap();
 }
+
+pub fn notify_milestone(summary: &str, body: &str) {
+    Notification::new()
+        .summary(summary)
+        .body(body)
+        .auto_icon()
+        .show()
+        .ok();
+}
+
+/// Plays the alert sound with the OS's own player without waiting for it to finish.
+pub fn play_sound_alert(alerts: &SoundAlertsConf) {
+    let location = alerts.sound_location.as_str();
+    let volume = alerts.volume as f32 / 100.;
+
+    let mut command = if cfg!(target_os = "windows") {
+        let mut command = Command::new("powershell");
+        command.args([
+            "-NoProfile",
+            "-Command",
+            &format!(
+                "Add-Type -AssemblyName PresentationCore; $player = New-Object System.Windows.Media.MediaPlayer; $player.Open([uri]'{}'); $player.Volume = {}; $player.Play(); Start-Sleep -Seconds 15",
+                location.replace('\'', "''"),
+                volume
+            ),
+        ]);
+        command
+    } else if cfg!(target_os = "macos") {
+        let mut command = Command::new("afplay");
+        command.args(["-v", &volume.to_string(), location]);
+        command
+    } else {
+        // PulseAudio's volume goes from 0 to 65536
+        let mut command = Command::new("paplay");
+        command.args([&format!("--volume={}", (volume * 65536.) as u32), location]);
+        command
+    };
+
+    if command.spawn().is_err() && cfg!(target_os = "linux") {
+        // ALSA's player doesn't have a volume option
+        Command::new("aplay").arg(location).spawn().ok();
+    }
+}
+
+/// Runs the on deadline command without waiting for it, the reached deadline's title
+/// is passed in `DEADLINER_TITLE` for scripts that handle several deadlines.
+pub fn run_deadline_hook(hook: &DeadlineHookConf, deadline: &SanitizedDeadline) {
+    let result = Command::new(&hook.command)
+        .args(&hook.args)
+        .env("DEADLINER_TITLE", &deadline.title)
+        .spawn();
+
+    if let Err(e) = result {
+        notify_milestone(
+            "⌚ Deadliner",
+            &format!("Couldn't run \"{}\": {}", hook.command, e),
+        );
+    }
+}
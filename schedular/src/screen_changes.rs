@@ -0,0 +1,54 @@
+use std::sync::Mutex;
+
+use deadliner_core::{merge_monitors, MonitorConf, SanitizedConf, ScreenDimensions};
+
+/// The primary screen and the monitors as they were last detected.
+#[derive(Debug, Clone)]
+pub struct DetectedScreen {
+    pub primary: ScreenDimensions,
+    pub monitors: Vec<MonitorConf>,
+}
+
+impl DetectedScreen {
+    /// Only what's physically detected matters, not the monitors' settings.
+    fn layout(&self) -> (ScreenDimensions, Vec<(String, [i32; 2], ScreenDimensions)>) {
+        let monitors = self
+            .monitors
+            .iter()
+            .map(|m| (m.name.clone(), m.position, m.dimensions))
+            .collect();
+
+        (self.primary, monitors)
+    }
+}
+
+static DETECTED: Mutex<Option<DetectedScreen>> = Mutex::new(None);
+
+/// Remembers the detected screen, returns whether it's different from the last time
+/// (Ex: a laptop got docked) or from the saved configuration when it's the first time.
+pub fn screen_changed(detected: DetectedScreen, conf: &SanitizedConf) -> bool {
+    let mut last = DETECTED.lock().unwrap();
+
+    let previous = last.clone().unwrap_or_else(|| DetectedScreen {
+        primary: conf.screen_dimensions,
+        monitors: conf.monitors.clone(),
+    });
+    let changed = previous.layout() != detected.layout();
+
+    *last = Some(detected);
+
+    changed
+}
+
+/// `conf` rendered at the size of the screen as it's now instead of when it was saved.
+pub fn with_detected_screen(conf: &SanitizedConf) -> SanitizedConf {
+    match DETECTED.lock().unwrap().clone() {
+        // Some platforms can't list the monitors, their saved sizes are kept then
+        Some(detected) if !detected.monitors.is_empty() => SanitizedConf {
+            screen_dimensions: detected.primary,
+            monitors: merge_monitors(detected.monitors, &conf.monitors),
+            ..conf.clone()
+        },
+        _ => conf.clone(),
+    }
+}
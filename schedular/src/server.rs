@@ -1,19 +1,12 @@
-use deadliner_gui::new_path;
+use deadliner_core::{get_schedular_port, mark_shutting_down};
 use std::{
-    fs,
     io::prelude::*,
     net::{TcpListener, TcpStream},
     sync::{Arc, Mutex},
-    thread,
-    time::Duration,
 };
 
 pub fn run_server(exit: Arc<Mutex<bool>>) {
-    let port: u16 = fs::read_to_string(new_path("port.txt"))
-        .unwrap()
-        .trim()
-        .parse()
-        .unwrap();
+    let port = get_schedular_port();
     let addr = format!("127.0.0.1:{}", port);
 
     let listener = TcpListener::bind(&addr).unwrap();
@@ -31,15 +24,15 @@ pub fn run_server(exit: Arc<Mutex<bool>>) {
 
 fn handle_connection(mut stream: TcpStream, exit: Arc<Mutex<bool>>) -> bool {
     let mut buffer = [0; 1024];
-    stream
+    let read = stream
         .read(&mut buffer)
         .expect("Couldn't read the `TcpStream` buffer!");
 
-    let req = Request::new(&buffer).parse();
+    let req = Request::new(&buffer[..read]).parse();
 
     // hit any endpoint to check if the schedular is already running.
     stream
-        .write(response(200, "OK", "", "").as_bytes())
+        .write_all(response(200, "OK", "", "").as_bytes())
         .expect("Couldn't write bytes to the stream!");
 
     stream
@@ -47,6 +40,8 @@ fn handle_connection(mut stream: TcpStream, exit: Arc<Mutex<bool>>) -> bool {
         .expect("Couldn't write all bytes to the stream!");
 
     if req.is_some() && req.unwrap().uri == "/shutdown" {
+        mark_shutting_down();
+
         let mut exit = exit.lock().unwrap();
         *exit = true;
 
@@ -68,7 +63,7 @@ pub struct Request<'a> {
 #[derive(Debug, PartialEq)]
 pub struct ParsedRequest {
     method: String,
-    uri: String,
+    pub uri: String,
     http_version: f64,
     body: String,
 }
@@ -96,15 +91,11 @@ impl<'a> Request<'a> {
 
         let mut lines: Vec<&str> = req_str.lines().collect();
 
-        let mut parts = lines[0].split(" ");
+        let mut parts = lines.first()?.split(' ');
 
         let method = parts.next()?.to_string();
         let uri = parts.next()?.to_string();
-        let http_version = parts
-            .next()?
-            .replace("HTTP/", "")
-            .parse()
-            .expect("Couldn't parse http version!");
+        let http_version = parts.next()?.replace("HTTP/", "").parse().ok()?;
 
         lines.remove(0);
 
@@ -0,0 +1,78 @@
+use std::thread;
+
+use chrono::Utc;
+use deadliner_core::{
+    archive_deadline, deadline_hook_due, milestone_announcement, milestone_notification,
+    send_webhooks, sound_alert_due, speak, subscribe, webhook_payload, ArchivedDeadline,
+    DeadlinerEvent, SanitizedConf,
+};
+
+use crate::{notify_milestone, play_sound_alert, run_deadline_hook};
+
+/// Every integration listens on its own thread, so a slow one (Ex: a webhook
+/// on a bad connection) doesn't hold up the others.
+pub fn spawn_subscribers(conf: &SanitizedConf) {
+    spawn_subscriber(conf, notify_on_milestone);
+    spawn_subscriber(conf, announce_on_milestone);
+    spawn_subscriber(conf, play_sound_on_milestone);
+    spawn_subscriber(conf, call_webhooks_on_milestone);
+    spawn_subscriber(conf, run_hook_on_deadline);
+    spawn_subscriber(conf, archive_on_deadline);
+}
+
+fn spawn_subscriber(conf: &SanitizedConf, handle: fn(&SanitizedConf, &DeadlinerEvent)) {
+    let events = subscribe();
+    let conf = conf.clone();
+
+    thread::spawn(move || {
+        for event in events {
+            handle(&conf, &event);
+        }
+    });
+}
+
+fn notify_on_milestone(conf: &SanitizedConf, event: &DeadlinerEvent) {
+    if let DeadlinerEvent::MilestoneCrossed { deadline, minutes } = event {
+        if let Some((summary, body)) = milestone_notification(conf, deadline, *minutes) {
+            notify_milestone(&summary, &body);
+        }
+    }
+}
+
+fn announce_on_milestone(conf: &SanitizedConf, event: &DeadlinerEvent) {
+    if let DeadlinerEvent::MilestoneCrossed { deadline, minutes } = event {
+        if let Some(announcement) = milestone_announcement(conf, deadline, *minutes) {
+            speak(&announcement).ok();
+        }
+    }
+}
+
+fn play_sound_on_milestone(conf: &SanitizedConf, event: &DeadlinerEvent) {
+    if let DeadlinerEvent::MilestoneCrossed { minutes, .. } = event {
+        if sound_alert_due(conf, *minutes) {
+            play_sound_alert(&conf.sound_alerts);
+        }
+    }
+}
+
+fn call_webhooks_on_milestone(conf: &SanitizedConf, event: &DeadlinerEvent) {
+    if let DeadlinerEvent::MilestoneCrossed { deadline, minutes } = event {
+        if let Some(payload) = webhook_payload(conf, deadline, *minutes) {
+            send_webhooks(conf, &[payload]);
+        }
+    }
+}
+
+fn run_hook_on_deadline(conf: &SanitizedConf, event: &DeadlinerEvent) {
+    if let DeadlinerEvent::DeadlinePassed { deadline } = event {
+        if deadline_hook_due(conf, deadline) {
+            run_deadline_hook(&conf.deadline_hook, deadline);
+        }
+    }
+}
+
+fn archive_on_deadline(_conf: &SanitizedConf, event: &DeadlinerEvent) {
+    if let DeadlinerEvent::DeadlinePassed { deadline } = event {
+        archive_deadline(ArchivedDeadline::passed(deadline, Utc::now())).ok();
+    }
+}
@@ -5,14 +5,20 @@ use std::sync::{Arc, Mutex};
 #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
 // #[cfg(feature = "tray")]
 pub fn bg_system_tray(exit: Arc<Mutex<bool>>) {
-    use deadliner_gui::{get_current_file_ext, new_path};
-    #[cfg(target_os = "linux")]
-    use std::path::Path;
-    use std::process::{Child, Command};
+    use crate::refresh_with_watchdog;
+    use deadliner_core::{
+        get_current_file_ext, is_paused, load_sanitized_conf, mark_shutting_down, new_path,
+        set_paused, RefreshHealth,
+    };
+    use std::{
+        process::{Child, Command},
+        thread,
+        time::{Duration, Instant},
+    };
     #[cfg(target_os = "macos")]
     use tao::platform::macos::{CustomMenuItemExtMacOS, NativeImage, SystemTrayBuilderExtMacOS};
     use tao::{
-        event::{Event, WindowEvent},
+        event::{Event, StartCause, WindowEvent},
         event_loop::{ControlFlow, EventLoop},
         menu::{ContextMenu as Menu, MenuItemAttributes, MenuType},
         system_tray::SystemTrayBuilder,
@@ -24,6 +30,20 @@ pub fn bg_system_tray(exit: Arc<Mutex<bool>>) {
 
     let mut gui_handler: Option<Child> = None;
 
+    let health_title = || format!("Updates: {}", RefreshHealth::load().summary());
+
+    let mut health_item =
+        tray_menu.add_item(MenuItemAttributes::new(&health_title()).with_enabled(false));
+
+    let pause_title = || {
+        if is_paused() {
+            "Resume Updates"
+        } else {
+            "Pause Updates"
+        }
+    };
+
+    let mut pause_item = tray_menu.add_item(MenuItemAttributes::new(pause_title()));
     let show_gui = tray_menu.add_item(MenuItemAttributes::new("Open/Close Window"));
     let quit_element = tray_menu.add_item(MenuItemAttributes::new("Quit Program"));
 
@@ -50,30 +70,32 @@ pub fn bg_system_tray(exit: Arc<Mutex<bool>>) {
         .unwrap();
 
     event_loop.run(move |event, event_loop, control_flow| {
-        *control_flow = ControlFlow::Wait;
+        // Wake up every now and then to keep the updates' health and the screen size fresh
+        if let Event::NewEvents(StartCause::Init | StartCause::ResumeTimeReached { .. }) = event {
+            health_item.set_title(&health_title());
+            // The GUI could've paused or resumed the updates since
+            pause_item.set_title(pause_title());
+            refresh_on_screen_change(event_loop);
+        }
+        *control_flow = ControlFlow::WaitUntil(Instant::now() + Duration::from_secs(60));
 
         // Acquire the lock to check if an exit signal is sent.
         let exit_lock = exit.try_lock();
         if let Ok(value) = exit_lock {
-            if *value == true {
+            if *value {
                 *control_flow = ControlFlow::Exit;
             }
         }
 
         let open_gui = |icon_click: bool, gui_handler: &mut Option<Child>| {
-            let is_gui_running = if gui_handler.is_some() {
-                let gui_child = gui_handler.as_mut().unwrap();
-                match gui_child.try_wait() {
-                    Ok(Some(_status)) => false,
-                    _ => true,
-                }
-            } else {
-                false
+            let is_gui_running = match gui_handler.as_mut() {
+                Some(gui_child) => !matches!(gui_child.try_wait(), Ok(Some(_status))),
+                None => false,
             };
 
             if is_gui_running {
                 if !icon_click {
-                    gui_handler.as_mut().unwrap().kill();
+                    gui_handler.as_mut().unwrap().kill().ok();
                     *gui_handler = None;
                 }
             } else {
@@ -86,13 +108,9 @@ pub fn bg_system_tray(exit: Arc<Mutex<bool>>) {
         };
 
         match event {
-            Event::WindowEvent {
-                event, window_id, ..
-            } => {
-                if event == WindowEvent::CloseRequested {
-                    // Change tray icon
-                    system_tray.set_icon(icon.clone());
-                }
+            Event::WindowEvent { event, .. } if event == WindowEvent::CloseRequested => {
+                // Change tray icon
+                system_tray.set_icon(icon.clone());
             }
             // on Windows, habitually, we show the window with left click
             #[cfg(target_os = "windows")]
@@ -109,13 +127,27 @@ pub fn bg_system_tray(exit: Arc<Mutex<bool>>) {
             } => {
                 // click on `quit` item
                 if menu_id == quit_element.clone().id() {
-                    if gui_handler.is_some() {
-                        gui_handler.as_mut().unwrap().kill();
+                    if let Some(gui_child) = gui_handler.as_mut() {
+                        gui_child.kill().ok();
                     }
                     // tell our app to close at the end of the loop.
+                    mark_shutting_down();
                     *control_flow = ControlFlow::Exit;
                 }
 
+                if menu_id == pause_item.clone().id() {
+                    let resuming = is_paused();
+
+                    if set_paused(!resuming).is_ok() {
+                        pause_item.set_title(pause_title());
+
+                        // Catch up on the updates that were skipped
+                        if let (true, Ok(conf)) = (resuming, load_sanitized_conf()) {
+                            thread::spawn(move || refresh_with_watchdog(&conf));
+                        }
+                    }
+                }
+
                 if menu_id == show_gui.clone().id() {
                     // Pass an argument to tell it to only style
                     open_gui(false, &mut gui_handler)
@@ -125,3 +157,50 @@ pub fn bg_system_tray(exit: Arc<Mutex<bool>>) {
         }
     });
 }
+
+/// Regenerates the wallpaper at the new size when monitors got plugged, unplugged or resized.
+#[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+fn refresh_on_screen_change<T>(event_loop: &tao::event_loop::EventLoopWindowTarget<T>) {
+    use crate::{refresh_with_watchdog, screen_changed, DetectedScreen};
+    use deadliner_core::{load_sanitized_conf, MonitorConf, ScreenDimensions};
+    use std::thread;
+
+    let primary = match event_loop.primary_monitor() {
+        Some(monitor) => monitor.size(),
+        None => return,
+    };
+
+    let monitors = event_loop
+        .available_monitors()
+        .map(|monitor| {
+            let size = monitor.size();
+            let position = monitor.position();
+
+            MonitorConf::new(
+                monitor.name().unwrap_or_default(),
+                [position.x, position.y],
+                ScreenDimensions {
+                    width: size.width,
+                    height: size.height,
+                },
+            )
+        })
+        .collect();
+
+    let detected = DetectedScreen {
+        primary: ScreenDimensions {
+            width: primary.width,
+            height: primary.height,
+        },
+        monitors,
+    };
+
+    let conf = match load_sanitized_conf() {
+        Ok(conf) => conf,
+        Err(_) => return,
+    };
+
+    if screen_changed(detected, &conf) {
+        thread::spawn(move || refresh_with_watchdog(&conf));
+    }
+}
@@ -0,0 +1,129 @@
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use crate::with_detected_screen;
+use chrono::Local;
+use deadliner_core::{
+    get_cache_dir, is_paused, last_step, update_wallpaper_unless, with_synced_deadlines,
+    RefreshHealth, SanitizedConf,
+};
+
+/// Refreshes that timed out but whose thread hasn't finished yet.
+static STUCK_REFRESHES: AtomicUsize = AtomicUsize::new(0);
+
+/// New refreshes are skipped while this many are stuck so hung threads don't pile up.
+const MAX_STUCK_REFRESHES: usize = 2;
+
+const RETRY_AFTER: Duration = Duration::from_secs(30);
+
+/// Updates the wallpaper on another thread and gives up on it if it takes longer than
+/// `conf.refresh_timeout_secs` (Ex: a hanging download), logging why and retrying later
+/// so one bad tick can't stall all of the following ones.
+pub fn refresh_with_watchdog(conf: &SanitizedConf) {
+    // Paused updates aren't refreshes, they'd look healthy in the records
+    if is_paused() {
+        return;
+    }
+
+    if refresh_and_record(conf).is_err() {
+        let conf = conf.clone();
+
+        thread::spawn(move || {
+            thread::sleep(RETRY_AFTER);
+            log_refresh("retrying the refresh");
+            refresh_and_record(&conf).ok();
+        });
+    }
+}
+
+fn refresh_and_record(conf: &SanitizedConf) -> Result<(), String> {
+    let result = refresh(conf);
+
+    if let Err(e) = &result {
+        log_refresh(e);
+    }
+    RefreshHealth::record(&result);
+
+    result
+}
+
+/// Fails when the refresh failed or didn't finish in time.
+fn refresh(conf: &SanitizedConf) -> Result<(), String> {
+    // The screen could've been resized since the configuration was saved
+    let conf = &with_synced_deadlines(&with_detected_screen(conf));
+
+    if STUCK_REFRESHES.load(Ordering::SeqCst) >= MAX_STUCK_REFRESHES {
+        return Err("skipped the refresh, previous ones are still stuck".to_string());
+    }
+
+    let (sender, receiver) = mpsc::channel();
+    let abandoned = Arc::new(Mutex::new(false));
+
+    let worker_conf = conf.clone();
+    let worker_abandoned = abandoned.clone();
+
+    thread::spawn(move || {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            update_wallpaper_unless(&worker_conf, || *worker_abandoned.lock().unwrap())
+                .map_err(String::from)
+        }))
+        .unwrap_or_else(|_| Err("the refresh panicked".to_string()));
+
+        // Held while sending so the watchdog can't give up on it in between
+        let abandoned = worker_abandoned.lock().unwrap();
+
+        // The watchdog stopped waiting for this refresh, it's no longer stuck now
+        if *abandoned {
+            STUCK_REFRESHES.fetch_sub(1, Ordering::SeqCst);
+        } else {
+            sender.send(result).ok();
+        }
+    });
+
+    let timeout = Duration::from_secs(conf.refresh_timeout_secs as u64);
+
+    let result = receiver.recv_timeout(timeout).or_else(|_| {
+        let mut abandoned = abandoned.lock().unwrap();
+
+        // It could've finished right as the timeout hit
+        receiver.try_recv().map_err(|_| {
+            *abandoned = true;
+            STUCK_REFRESHES.fetch_add(1, Ordering::SeqCst);
+        })
+    });
+
+    match result {
+        Ok(result) => result.map_err(|e| format!("refresh failed: {}", e)),
+        Err(_) => Err(format!(
+            "refresh took longer than {}s and was aborted, last step: {}",
+            conf.refresh_timeout_secs,
+            last_step()
+        )),
+    }
+}
+
+pub(crate) fn log_refresh(message: &str) {
+    let log = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(get_cache_dir().join("schedular.log"));
+
+    if let Ok(mut log) = log {
+        writeln!(
+            log,
+            "[{}] {}",
+            Local::now().format("%Y-%m-%d %H:%M:%S"),
+            message
+        )
+        .ok();
+    }
+}
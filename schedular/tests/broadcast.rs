@@ -0,0 +1,73 @@
+//! The broadcast server is reachable from the whole local network,
+//! so whatever a client sends mustn't take it down.
+
+use std::{
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+    thread,
+    time::Duration,
+};
+
+use deadliner_core::{sanitize_inputs_with, Deadline, DeadlinerConf, ScreenDimensions};
+use deadliner_schedular::run_broadcast_server;
+
+fn free_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+/// Sends `request` and returns the whole response, `None` when the server can't be reached.
+fn send(port: u16, request: &str) -> Option<String> {
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).ok()?;
+    stream.write_all(request.as_bytes()).ok()?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).ok()?;
+
+    Some(response)
+}
+
+fn start_broadcast_server() -> u16 {
+    let mut conf = DeadlinerConf::new(
+        ScreenDimensions {
+            width: 480,
+            height: 270,
+        },
+        vec![],
+    );
+    conf.deadlines = vec![Deadline {
+        date: String::from("2999-01-01"),
+        ..Deadline::default()
+    }];
+
+    let mut conf = sanitize_inputs_with(&conf, None).unwrap();
+    let port = free_port();
+    conf.broadcast.enabled = true;
+    conf.broadcast.port = port;
+
+    thread::spawn(move || run_broadcast_server(conf));
+
+    for _ in 0..50 {
+        if TcpStream::connect(("127.0.0.1", port)).is_ok() {
+            return port;
+        }
+
+        thread::sleep(Duration::from_millis(20));
+    }
+
+    panic!("The broadcast server didn't start on port {}", port);
+}
+
+#[test]
+fn keeps_serving_after_a_malformed_request() {
+    let port = start_broadcast_server();
+
+    let response = send(port, "GET / HTTP/x\r\n\r\n").unwrap();
+    assert!(response.starts_with("HTTP/1.1 404"), "{}", response);
+
+    let response = send(port, "GET / HTTP/1.1\r\n\r\n").unwrap();
+    assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+}